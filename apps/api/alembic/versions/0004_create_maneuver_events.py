@@ -0,0 +1,57 @@
+"""Create maneuver_events table and maneuver_events_lookup_idx.
+
+Revision ID: 0004
+Revises: 0003
+Create Date: 2026-10-18 00:00:00.000000
+
+"""
+
+from __future__ import annotations
+
+from collections.abc import Sequence
+
+import sqlalchemy as sa
+from alembic import op
+
+# revision identifiers, used by Alembic.
+revision: str = "0004"
+down_revision: str | None = "0003"
+branch_labels: str | Sequence[str] | None = None
+depends_on: str | Sequence[str] | None = None
+
+
+def upgrade() -> None:
+    op.create_table(
+        "maneuver_events",
+        sa.Column("id", sa.BigInteger(), autoincrement=True, nullable=False),
+        sa.Column("norad_id", sa.Integer(), nullable=False),
+        sa.Column("from_tle_id", sa.BigInteger(), nullable=False),
+        sa.Column("to_tle_id", sa.BigInteger(), nullable=False),
+        sa.Column("epoch", sa.DateTime(timezone=True), nullable=False),
+        sa.Column("delta_v_m_s", sa.Float(), nullable=False),
+        sa.Column("delta_a_km", sa.Float(), nullable=False),
+        sa.Column("delta_i_deg", sa.Float(), nullable=False),
+        sa.Column("delta_e", sa.Float(), nullable=False),
+        sa.Column("residual_km", sa.Float(), nullable=False),
+        sa.Column(
+            "detected_at",
+            sa.DateTime(timezone=True),
+            server_default=sa.text("now()"),
+            nullable=False,
+        ),
+        sa.ForeignKeyConstraint(["norad_id"], ["satellites.norad_id"]),
+        sa.ForeignKeyConstraint(["from_tle_id"], ["tles.id"], ondelete="CASCADE"),
+        sa.ForeignKeyConstraint(["to_tle_id"], ["tles.id"], ondelete="CASCADE"),
+        sa.PrimaryKeyConstraint("id"),
+        sa.UniqueConstraint("from_tle_id", "to_tle_id"),
+    )
+    op.create_index(
+        "maneuver_events_lookup_idx",
+        "maneuver_events",
+        ["norad_id", "epoch"],
+    )
+
+
+def downgrade() -> None:
+    op.drop_index("maneuver_events_lookup_idx", table_name="maneuver_events")
+    op.drop_table("maneuver_events")
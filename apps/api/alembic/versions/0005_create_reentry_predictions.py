@@ -0,0 +1,59 @@
+"""Create reentry_predictions table and reentry_predictions_lookup_idx.
+
+Revision ID: 0005
+Revises: 0004
+Create Date: 2026-10-18 00:00:00.000000
+
+"""
+
+from __future__ import annotations
+
+from collections.abc import Sequence
+
+import sqlalchemy as sa
+from alembic import op
+
+# revision identifiers, used by Alembic.
+revision: str = "0005"
+down_revision: str | None = "0004"
+branch_labels: str | Sequence[str] | None = None
+depends_on: str | Sequence[str] | None = None
+
+
+def upgrade() -> None:
+    op.create_table(
+        "reentry_predictions",
+        sa.Column("id", sa.BigInteger(), autoincrement=True, nullable=False),
+        sa.Column("norad_id", sa.Integer(), nullable=False),
+        sa.Column("tle_id", sa.BigInteger(), nullable=False),
+        sa.Column("atmosphere", sa.Text(), nullable=False),
+        sa.Column("ballistic_coefficient_m2_kg", sa.Float(), nullable=False),
+        sa.Column("horizon_end", sa.DateTime(timezone=True), nullable=False),
+        sa.Column("reentry_epoch", sa.DateTime(timezone=True), nullable=True),
+        sa.Column("earliest_epoch", sa.DateTime(timezone=True), nullable=True),
+        sa.Column("latest_epoch", sa.DateTime(timezone=True), nullable=True),
+        sa.Column(
+            "computed_at",
+            sa.DateTime(timezone=True),
+            server_default=sa.text("now()"),
+            nullable=False,
+        ),
+        sa.CheckConstraint(
+            "atmosphere IN ('exponential', 'harris_priester')",
+            name="reentry_predictions_atmosphere_check",
+        ),
+        sa.ForeignKeyConstraint(["norad_id"], ["satellites.norad_id"]),
+        sa.ForeignKeyConstraint(["tle_id"], ["tles.id"], ondelete="CASCADE"),
+        sa.PrimaryKeyConstraint("id"),
+        sa.UniqueConstraint("tle_id", "atmosphere"),
+    )
+    op.create_index(
+        "reentry_predictions_lookup_idx",
+        "reentry_predictions",
+        ["norad_id", "computed_at"],
+    )
+
+
+def downgrade() -> None:
+    op.drop_index("reentry_predictions_lookup_idx", table_name="reentry_predictions")
+    op.drop_table("reentry_predictions")
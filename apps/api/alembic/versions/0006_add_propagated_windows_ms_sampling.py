@@ -0,0 +1,43 @@
+"""Add millisecond sampling columns to propagated_windows.
+
+Revision ID: 0006
+Revises: 0005
+Create Date: 2026-10-18 00:00:00.000001
+
+"""
+
+from __future__ import annotations
+
+from collections.abc import Sequence
+
+import sqlalchemy as sa
+from alembic import op
+
+# revision identifiers, used by Alembic.
+revision: str = "0006"
+down_revision: str | None = "0005"
+branch_labels: str | Sequence[str] | None = None
+depends_on: str | Sequence[str] | None = None
+
+
+def upgrade() -> None:
+    op.add_column(
+        "propagated_windows",
+        sa.Column("result_format", sa.SmallInteger(), nullable=False, server_default=sa.text("1")),
+    )
+    op.add_column("propagated_windows", sa.Column("step_ms", sa.Integer(), nullable=True))
+    op.add_column("propagated_windows", sa.Column("start_offset_ms", sa.Integer(), nullable=True))
+    op.create_check_constraint(
+        "propagated_windows_result_format_check",
+        "propagated_windows",
+        "result_format IN (1, 2)",
+    )
+
+
+def downgrade() -> None:
+    op.drop_constraint(
+        "propagated_windows_result_format_check", "propagated_windows", type_="check"
+    )
+    op.drop_column("propagated_windows", "start_offset_ms")
+    op.drop_column("propagated_windows", "step_ms")
+    op.drop_column("propagated_windows", "result_format")
@@ -0,0 +1,47 @@
+"""Create propagated_window_chunks table and add chunk_count to propagated_windows.
+
+Revision ID: 0008
+Revises: 0007
+Create Date: 2026-10-18 00:00:00.000003
+
+"""
+
+from __future__ import annotations
+
+from collections.abc import Sequence
+
+import sqlalchemy as sa
+from alembic import op
+from sqlalchemy.dialects.postgresql import JSONB
+
+# revision identifiers, used by Alembic.
+revision: str = "0008"
+down_revision: str | None = "0007"
+branch_labels: str | Sequence[str] | None = None
+depends_on: str | Sequence[str] | None = None
+
+
+def upgrade() -> None:
+    op.add_column("propagated_windows", sa.Column("chunk_count", sa.Integer(), nullable=True))
+    op.create_table(
+        "propagated_window_chunks",
+        sa.Column("hash", sa.Text(), nullable=False),
+        sa.Column("chunk_index", sa.Integer(), nullable=False),
+        sa.Column("tle_id", sa.BigInteger(), nullable=False),
+        sa.Column("start_t", sa.BigInteger(), nullable=False),
+        sa.Column("end_t", sa.BigInteger(), nullable=False),
+        sa.Column("samples", JSONB(), nullable=False),
+        sa.Column(
+            "computed_at",
+            sa.DateTime(timezone=True),
+            server_default=sa.text("now()"),
+            nullable=False,
+        ),
+        sa.ForeignKeyConstraint(["tle_id"], ["tles.id"], ondelete="CASCADE"),
+        sa.PrimaryKeyConstraint("hash", "chunk_index"),
+    )
+
+
+def downgrade() -> None:
+    op.drop_table("propagated_window_chunks")
+    op.drop_column("propagated_windows", "chunk_count")
@@ -0,0 +1,36 @@
+"""Add SGP4 gravity model and operation mode to propagated_windows.
+
+Revision ID: 0009
+Revises: 0008
+Create Date: 2026-10-18 00:00:00.000004
+
+"""
+
+from __future__ import annotations
+
+from collections.abc import Sequence
+
+import sqlalchemy as sa
+from alembic import op
+
+# revision identifiers, used by Alembic.
+revision: str = "0009"
+down_revision: str | None = "0008"
+branch_labels: str | Sequence[str] | None = None
+depends_on: str | Sequence[str] | None = None
+
+
+def upgrade() -> None:
+    op.add_column(
+        "propagated_windows",
+        sa.Column("gravity", sa.Text(), nullable=False, server_default=sa.text("'wgs84'")),
+    )
+    op.add_column(
+        "propagated_windows",
+        sa.Column("opsmode", sa.Text(), nullable=False, server_default=sa.text("'improved'")),
+    )
+
+
+def downgrade() -> None:
+    op.drop_column("propagated_windows", "opsmode")
+    op.drop_column("propagated_windows", "gravity")
@@ -0,0 +1,31 @@
+"""Add propagator to propagated_windows.
+
+Revision ID: 0010
+Revises: 0009
+Create Date: 2026-10-18 00:00:00.000005
+
+"""
+
+from __future__ import annotations
+
+from collections.abc import Sequence
+
+import sqlalchemy as sa
+from alembic import op
+
+# revision identifiers, used by Alembic.
+revision: str = "0010"
+down_revision: str | None = "0009"
+branch_labels: str | Sequence[str] | None = None
+depends_on: str | Sequence[str] | None = None
+
+
+def upgrade() -> None:
+    op.add_column(
+        "propagated_windows",
+        sa.Column("propagator", sa.Text(), nullable=False, server_default=sa.text("'sgp4'")),
+    )
+
+
+def downgrade() -> None:
+    op.drop_column("propagated_windows", "propagator")
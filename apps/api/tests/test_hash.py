@@ -14,6 +14,16 @@ The golden vector below was independently computed in Python using:
 The **same** expected value is pinned in ``apps/worker/src/hash.rs``
 ``#[test] fn golden_hash()``.  Both tests must pass with the same value; a
 divergence means the two implementations use different canonical formats.
+
+Millisecond windows (result format 2) use ``compute_hash_ms`` and the
+``v2:``-prefixed canonical string, pinned by ``golden_hash_ms`` on both sides.
+Explicit epoch lists use ``compute_hash_epochs``, pinned by
+``golden_hash_epochs``, and catalog snapshots use ``compute_hash_snapshot``,
+pinned by ``golden_hash_snapshot``.  Backward windows reuse ``compute_hash``
+with a negative ``duration_s``; centred windows use ``compute_hash_centered``.
+Both are pinned by ``golden_hash_backward_and_centered``.  A non-default
+propagator, SGP4 gravity model or operation mode is appended to the window and
+epoch-list keys (see ``_with_model``), pinned by ``golden_hash_model``.
 """
 
 from __future__ import annotations
@@ -21,6 +31,25 @@ from __future__ import annotations
 import hashlib
 from datetime import UTC, datetime
 
+DEFAULT_PROPAGATOR = "sgp4"
+DEFAULT_GRAVITY = "wgs84"
+DEFAULT_OPSMODE = "improved"
+
+
+def _with_model(canonical: str, propagator: str, gravity: str, opsmode: str) -> str:
+    """Append the propagation model unless it is the default.
+
+    SGP4 appends ``:{gravity}:{opsmode}``; the analytic propagators, which have
+    no operation mode, append ``:{propagator}:{gravity}``.  Mirrors
+    ``with_model`` in ``apps/worker/src/hash.rs``; default-model keys are
+    unchanged.
+    """
+    if (propagator, gravity, opsmode) == (DEFAULT_PROPAGATOR, DEFAULT_GRAVITY, DEFAULT_OPSMODE):
+        return canonical
+    if propagator != DEFAULT_PROPAGATOR:
+        return f"{canonical}:{propagator}:{gravity}"
+    return f"{canonical}:{gravity}:{opsmode}"
+
 
 def compute_hash(
     tle_id: int,
@@ -29,6 +58,9 @@ def compute_hash(
     step_s: int,
     frame: str,
     include_velocity: bool,
+    gravity: str = DEFAULT_GRAVITY,
+    opsmode: str = DEFAULT_OPSMODE,
+    propagator: str = DEFAULT_PROPAGATOR,
 ) -> str:
     """Compute a deterministic SHA-256 cache key for a propagation window.
 
@@ -47,6 +79,108 @@ def compute_hash(
     start_str = start_at.isoformat()
     iv_str = str(include_velocity).lower()
     canonical = f"{tle_id}:{start_str}:{duration_s}:{step_s}:{frame}:{iv_str}"
+    canonical = _with_model(canonical, propagator, gravity, opsmode)
+    digest = hashlib.sha256(canonical.encode()).hexdigest()
+    return f"sha256:{digest}"
+
+
+def compute_hash_centered(
+    tle_id: int,
+    center_at: datetime,
+    duration_s: int,
+    step_s: int,
+    frame: str,
+    include_velocity: bool,
+    gravity: str = DEFAULT_GRAVITY,
+    opsmode: str = DEFAULT_OPSMODE,
+    propagator: str = DEFAULT_PROPAGATOR,
+) -> str:
+    """Compute the cache key for a window centred on ``center_at``.
+
+    The canonical string format is::
+
+        "center:{tle_id}:{center_at_rfc3339}:{duration_s}:{step_s}:{frame}:{include_velocity}"
+
+    This format **must** stay in sync with ``hash::compute_centered`` in
+    ``apps/worker/src/hash.rs``.
+    """
+    center_str = center_at.isoformat()
+    iv_str = str(include_velocity).lower()
+    canonical = f"center:{tle_id}:{center_str}:{duration_s}:{step_s}:{frame}:{iv_str}"
+    canonical = _with_model(canonical, propagator, gravity, opsmode)
+    digest = hashlib.sha256(canonical.encode()).hexdigest()
+    return f"sha256:{digest}"
+
+
+def compute_hash_ms(
+    tle_id: int,
+    start_at: datetime,
+    duration_s: int,
+    step_ms: int,
+    start_offset_ms: int,
+    frame: str,
+    include_velocity: bool,
+    gravity: str = DEFAULT_GRAVITY,
+    opsmode: str = DEFAULT_OPSMODE,
+    propagator: str = DEFAULT_PROPAGATOR,
+) -> str:
+    """Compute the cache key for a window sampled in milliseconds.
+
+    The canonical string format is::
+
+        "v2:{tle_id}:{start_at_rfc3339}:{duration_s}:{step_ms}:{start_offset_ms}:{frame}:{include_velocity}"
+
+    This format **must** stay in sync with ``hash::compute_ms`` in
+    ``apps/worker/src/hash.rs``.
+    """
+    start_str = start_at.isoformat()
+    iv_str = str(include_velocity).lower()
+    canonical = (
+        f"v2:{tle_id}:{start_str}:{duration_s}:{step_ms}:{start_offset_ms}:{frame}:{iv_str}"
+    )
+    canonical = _with_model(canonical, propagator, gravity, opsmode)
+    digest = hashlib.sha256(canonical.encode()).hexdigest()
+    return f"sha256:{digest}"
+
+
+def compute_hash_epochs(
+    tle_id: int,
+    epochs: list[datetime],
+    frame: str,
+    include_velocity: bool,
+    gravity: str = DEFAULT_GRAVITY,
+    opsmode: str = DEFAULT_OPSMODE,
+    propagator: str = DEFAULT_PROPAGATOR,
+) -> str:
+    """Compute the cache key for a ``propagate_epochs`` job.
+
+    Each epoch is rendered in UTC with microseconds and a ``Z`` suffix (e.g.
+    ``"2026-04-25T12:00:07.250000Z"``); the newline-joined list is digested
+    and the canonical string is::
+
+        "epochs:{tle_id}:{hex(sha256(list))}:{frame}:{include_velocity}"
+
+    This format **must** stay in sync with ``hash::compute_epochs`` in
+    ``apps/worker/src/hash.rs``.
+    """
+    listing = "\n".join(e.astimezone(UTC).strftime("%Y-%m-%dT%H:%M:%S.%fZ") for e in epochs)
+    list_digest = hashlib.sha256(listing.encode()).hexdigest()
+    iv_str = str(include_velocity).lower()
+    canonical = f"epochs:{tle_id}:{list_digest}:{frame}:{iv_str}"
+    canonical = _with_model(canonical, propagator, gravity, opsmode)
+    digest = hashlib.sha256(canonical.encode()).hexdigest()
+    return f"sha256:{digest}"
+
+
+def compute_hash_snapshot(epoch: datetime, geodetic: bool) -> str:
+    """Compute the cache key for a ``snapshot`` job::
+
+        "snapshot:{epoch_isoformat}:{geodetic}"
+
+    This format **must** stay in sync with ``hash::compute_snapshot`` in
+    ``apps/worker/src/hash.rs``.
+    """
+    canonical = f"snapshot:{epoch.isoformat()}:{str(geodetic).lower()}"
     digest = hashlib.sha256(canonical.encode()).hexdigest()
     return f"sha256:{digest}"
 
@@ -68,6 +202,83 @@ def test_golden_hash() -> None:
     assert result == "sha256:9cdb94ff65c6df3af52c16c1eae7365a558545dd3aedd37bc1567332c07f1f14"
 
 
+def test_golden_hash_backward_and_centered() -> None:
+    """Cross-language golden vectors: must match Rust hash::tests::golden_hash_backward_and_centered."""
+    at = datetime(2026, 4, 25, 12, 0, 0, tzinfo=UTC)
+    assert (
+        compute_hash(1234, at, -2700, 10, "eci_j2000", True)
+        == "sha256:c4074d732682f5f1a51cdaee7feb90600ffe8c7e74d3baa6b5b0e639b675ec97"
+    )
+    assert (
+        compute_hash_centered(1234, at, 5400, 10, "eci_j2000", True)
+        == "sha256:719ee6245ee5afb03adb4372bf89d22b8197b8b76358f94760c5b866b68285c8"
+    )
+
+
+def test_golden_hash_ms() -> None:
+    """Cross-language golden vector: must match Rust hash::tests::golden_hash_ms."""
+    start_at = datetime(2026, 4, 25, 12, 0, 0, tzinfo=UTC)
+    result = compute_hash_ms(
+        tle_id=1234,
+        start_at=start_at,
+        duration_s=3600,
+        step_ms=100,
+        start_offset_ms=0,
+        frame="eci_j2000",
+        include_velocity=True,
+    )
+    assert result == "sha256:d2cc6d9f9ead68eb096f0e3bf26ada27dc1e1b339002196c001054023d081546"
+
+
+def test_ms_and_s_windows_do_not_collide() -> None:
+    """A millisecond window must not share a key with the equivalent whole-second window."""
+    start_at = datetime(2026, 4, 25, 12, 0, 0, tzinfo=UTC)
+    h_s = compute_hash(1234, start_at, 3600, 10, "eci_j2000", True)
+    h_ms = compute_hash_ms(1234, start_at, 3600, 10_000, 0, "eci_j2000", True)
+    assert h_s != h_ms
+
+
+def test_golden_hash_epochs() -> None:
+    """Cross-language golden vector: must match Rust hash::tests::golden_hash_epochs."""
+    epochs = [
+        datetime(2026, 4, 25, 12, 0, 0, tzinfo=UTC),
+        datetime(2026, 4, 25, 12, 0, 7, 250_000, tzinfo=UTC),
+    ]
+    result = compute_hash_epochs(1234, epochs, "eci_j2000", True)
+    assert result == "sha256:245b4bbb5286027033a1098c9377dc8e912bdd1b1e390574bbb01cbfdd0161fb"
+    assert compute_hash_epochs(1234, epochs[::-1], "eci_j2000", True) != result
+
+
+def test_golden_hash_snapshot() -> None:
+    """Cross-language golden vector: must match Rust hash::tests::golden_hash_snapshot."""
+    epoch = datetime(2026, 4, 25, 12, 0, 0, tzinfo=UTC)
+    result = compute_hash_snapshot(epoch, True)
+    assert result == "sha256:8d5741d0d3aed1c1a92feb8badae2ec0909b2427e7011826991571ef8681e842"
+    assert compute_hash_snapshot(epoch, False) != result
+
+
+def test_golden_hash_model() -> None:
+    """Cross-language golden vectors: must match Rust hash::tests::golden_hash_model."""
+    start_at = datetime(2026, 4, 25, 12, 0, 0, tzinfo=UTC)
+    args = (1234, start_at, 3600, 10, "eci_j2000", True)
+    assert (
+        compute_hash(*args, gravity="wgs72", opsmode="afspc")
+        == "sha256:e7ee84215dff2eb96ba92bac733d2f3b45cb84bfc00789af2367115e427ef452"
+    )
+    assert (
+        compute_hash(*args, gravity="wgs72", opsmode="improved")
+        == "sha256:2ac7c1e4c6179a1ab60be2ea2c66c14c08300a009ccd10b1a033b5ecb72c04dd"
+    )
+    assert compute_hash(*args, gravity="wgs84", opsmode="improved") == compute_hash(*args)
+    assert (
+        compute_hash(*args, propagator="two_body")
+        == "sha256:98750c8a02852a0c9ddba557190adde1ddb31b8ce375f2a9dc8dc0d88fd86efb"
+    )
+    assert compute_hash(*args, propagator="two_body", opsmode="afspc") == compute_hash(
+        *args, propagator="two_body"
+    )
+
+
 def test_false_velocity_different_hash() -> None:
     """include_velocity=False must produce a different hash than True."""
     start_at = datetime(2026, 4, 25, 12, 0, 0, tzinfo=UTC)
@@ -0,0 +1,211 @@
+//! Analytic comparison propagators: pure two-body Kepler motion and J2
+//! secular drift.
+//!
+//! Both start from a TLE's mean elements and ignore drag, third bodies and
+//! every periodic term, so they show how far a simpler orbit model drifts
+//! from SGP4.  TLE elements are SGP4 *mean* elements, so even at the epoch
+//! the two differ by SGP4's short-period terms: roughly 5–30 km for the
+//! curated satellites (see `tests/accuracy.rs`).
+//!
+//! # Two-body
+//!
+//! A fixed ellipse whose semi-major axis follows from the Kozai mean motion
+//! by Kepler's third law, as in [`crate::elements`].
+//!
+//! # J2 secular
+//!
+//! Recovers the Brouwer mean motion and semi-major axis exactly as SGP4's
+//! initialisation does, then advances the node, argument of perigee and mean
+//! anomaly at the first-order J2 secular rates.  Velocities are those of the
+//! instantaneous ellipse; the slow rotation of the plane is not added.
+//!
+//! Gravity constants (μ, J2, equatorial radius) come from the same
+//! `sgp4::Geopotential` as SGP4, so `gravity` selects them for every
+//! propagator.
+
+use anyhow::{ensure, Result};
+use sgp4::{Elements, Geopotential, Prediction};
+use std::f64::consts::TAU;
+
+/// Mean elements and their secular rates, angles in radians and rates in
+/// radians per minute.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalyticOrbit {
+    semi_major_axis_km: f64,
+    eccentricity: f64,
+    inclination: f64,
+    raan: f64,
+    arg_perigee: f64,
+    mean_anomaly: f64,
+    raan_rate: f64,
+    arg_perigee_rate: f64,
+    mean_motion: f64,
+    /// μ in km³/s².
+    mu: f64,
+}
+
+impl AnalyticOrbit {
+    /// Unperturbed Kepler orbit of a TLE.
+    ///
+    /// # Errors
+    /// Returns an error unless the TLE describes a closed orbit.
+    pub fn two_body(elements: &Elements, geopotential: &Geopotential) -> Result<Self> {
+        let mut orbit = Self::epoch_elements(elements, geopotential)?;
+        orbit.semi_major_axis_km = (orbit.mu * 3_600.0 / orbit.mean_motion.powi(2)).cbrt();
+        Ok(orbit)
+    }
+
+    /// Kepler orbit with J2 secular drift of the node, perigee and mean
+    /// anomaly.
+    ///
+    /// # Errors
+    /// Returns an error unless the TLE describes a closed orbit.
+    pub fn j2_secular(elements: &Elements, geopotential: &Geopotential) -> Result<Self> {
+        let mut orbit = Self::epoch_elements(elements, geopotential)?;
+        let (ke, j2, ae) = (geopotential.ke, geopotential.j2, geopotential.ae);
+        let cos_i = orbit.inclination.cos();
+        let beta2 = 1.0 - orbit.eccentricity.powi(2);
+        let beta = beta2.sqrt();
+
+        // Kozai → Brouwer mean motion (Vallado's `initl`), in Earth radii.
+        let d1 = 0.75 * j2 * (3.0 * cos_i * cos_i - 1.0) / (beta * beta2);
+        let ak = (ke / orbit.mean_motion).powf(2.0 / 3.0);
+        let del = d1 / (ak * ak);
+        let adel = ak * (1.0 - del * del - del * (1.0 / 3.0 + 134.0 * del * del / 81.0));
+        let n0 = orbit.mean_motion / (1.0 + d1 / (adel * adel));
+        let a0 = (ke / n0).powf(2.0 / 3.0) * ae;
+
+        let k = j2 * (ae / (a0 * beta2)).powi(2);
+        orbit.semi_major_axis_km = a0;
+        orbit.raan_rate = -1.5 * n0 * k * cos_i;
+        orbit.arg_perigee_rate = 0.75 * n0 * k * (5.0 * cos_i * cos_i - 1.0);
+        orbit.mean_motion = n0 * (1.0 + 0.75 * k * beta * (3.0 * cos_i * cos_i - 1.0));
+        Ok(orbit)
+    }
+
+    /// TEME position (km) and velocity (km/s) `minutes` after the TLE epoch.
+    pub fn predict(&self, minutes: f64) -> Prediction {
+        let e = self.eccentricity;
+        let raan = self.raan + self.raan_rate * minutes;
+        let arg_perigee = self.arg_perigee + self.arg_perigee_rate * minutes;
+        let mean_anomaly = (self.mean_anomaly + self.mean_motion * minutes).rem_euclid(TAU);
+
+        let ecc_anomaly = solve_kepler(mean_anomaly, e);
+        let (sin_e, cos_e) = ecc_anomaly.sin_cos();
+        let a = self.semi_major_axis_km;
+        let beta = (1.0 - e * e).sqrt();
+        let r = a * (1.0 - e * cos_e);
+        let speed = (self.mu * a).sqrt() / r;
+
+        let (sin_o, cos_o) = raan.sin_cos();
+        let (sin_w, cos_w) = arg_perigee.sin_cos();
+        let (sin_i, cos_i) = self.inclination.sin_cos();
+        let rotate = |x: f64, y: f64| {
+            [
+                (cos_o * cos_w - sin_o * sin_w * cos_i) * x
+                    - (cos_o * sin_w + sin_o * cos_w * cos_i) * y,
+                (sin_o * cos_w + cos_o * sin_w * cos_i) * x
+                    + (cos_o * cos_w * cos_i - sin_o * sin_w) * y,
+                sin_w * sin_i * x + cos_w * sin_i * y,
+            ]
+        };
+        Prediction {
+            position: rotate(a * (cos_e - e), a * beta * sin_e),
+            velocity: rotate(-speed * sin_e, speed * beta * cos_e),
+        }
+    }
+
+    /// Epoch elements with no secular drift; the semi-major axis is filled
+    /// in by the constructors.
+    fn epoch_elements(elements: &Elements, geopotential: &Geopotential) -> Result<Self> {
+        ensure!(
+            (0.0..1.0).contains(&elements.eccentricity) && elements.mean_motion > 0.0,
+            "analytic propagators need a closed orbit (e = {}, n = {} rev/day)",
+            elements.eccentricity,
+            elements.mean_motion
+        );
+        let mean_motion = elements.mean_motion * TAU / 1_440.0;
+        Ok(Self {
+            semi_major_axis_km: 0.0,
+            eccentricity: elements.eccentricity,
+            inclination: elements.inclination.to_radians(),
+            raan: elements.right_ascension.to_radians(),
+            arg_perigee: elements.argument_of_perigee.to_radians(),
+            mean_anomaly: elements.mean_anomaly.to_radians(),
+            raan_rate: 0.0,
+            arg_perigee_rate: 0.0,
+            mean_motion,
+            // ke is √μ in Earth radii^1.5 per minute.
+            mu: (geopotential.ke * geopotential.ae.powf(1.5) / 60.0).powi(2),
+        })
+    }
+}
+
+/// Eccentric anomaly for mean anomaly `m` (Newton's method).
+fn solve_kepler(m: f64, e: f64) -> f64 {
+    let mut ecc_anomaly = if e < 0.8 { m } else { std::f64::consts::PI };
+    for _ in 0..50 {
+        let step = (ecc_anomaly - e * ecc_anomaly.sin() - m) / (1.0 - e * ecc_anomaly.cos());
+        ecc_anomaly -= step;
+        if step.abs() < 1e-12 {
+            break;
+        }
+    }
+    ecc_anomaly
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::MeanElements;
+
+    const ISS_LINE1: &str =
+        "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9999";
+    const ISS_LINE2: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000013";
+
+    fn iss() -> Elements {
+        Elements::from_tle(None, ISS_LINE1.as_bytes(), ISS_LINE2.as_bytes()).unwrap()
+    }
+
+    fn norm(v: [f64; 3]) -> f64 {
+        v.iter().map(|x| x * x).sum::<f64>().sqrt()
+    }
+
+    /// A two-body orbit closes after one period and keeps its energy.
+    #[test]
+    fn two_body_orbit_closes() {
+        let orbit = AnalyticOrbit::two_body(&iss(), &sgp4::WGS84).unwrap();
+        let period_min = 1_440.0 / 15.5;
+        let start = orbit.predict(0.0);
+        let end = orbit.predict(period_min);
+        let gap = norm(std::array::from_fn(|i| end.position[i] - start.position[i]));
+        assert!(gap < 1e-6, "orbit did not close: {gap} km");
+
+        let energy = |p: &Prediction| norm(p.velocity).powi(2) / 2.0 - orbit.mu / norm(p.position);
+        let mid = orbit.predict(period_min / 3.0);
+        assert!((energy(&start) - energy(&mid)).abs() < 1e-9);
+        assert!((norm(start.position) - 6_790.0).abs() < 30.0);
+    }
+
+    /// The J2 node rate matches [`MeanElements::raan_rate_deg_day`], and the
+    /// two models agree at the epoch to within the Kozai/Brouwer difference.
+    #[test]
+    fn j2_secular_regresses_node() {
+        let elements = iss();
+        let orbit = AnalyticOrbit::j2_secular(&elements, &sgp4::WGS84).unwrap();
+        let rate_deg_day = orbit.raan_rate.to_degrees() * 1_440.0;
+        let expected = MeanElements::from_elements(&elements).raan_rate_deg_day();
+        assert!((rate_deg_day - expected).abs() < 0.05, "{rate_deg_day} vs {expected}");
+
+        let two_body = AnalyticOrbit::two_body(&elements, &sgp4::WGS84).unwrap();
+        let (r_j2, r_kepler) = (orbit.predict(0.0).position, two_body.predict(0.0).position);
+        let offset = norm(std::array::from_fn(|i| r_j2[i] - r_kepler[i]));
+        assert!(offset < 15.0, "epoch offset {offset} km");
+        assert!(AnalyticOrbit::two_body(
+            &Elements { eccentricity: 1.2, ..elements },
+            &sgp4::WGS84
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,213 @@
+//! Solar beta angle time series.
+//!
+//! The beta angle is the angle between the orbit plane and the Sun vector:
+//!
+//! ```text
+//! β = asin(ĥ · ŝ)      ĥ = (r × v)̂ (orbit normal),  ŝ = Sun direction
+//! ```
+//!
+//! The orbit normal is taken from the SGP4 state at each sample, so nodal
+//! precession is included without a separate model.  The Sun comes from
+//! [`crate::sun`].
+//!
+//! # Eclipse fraction
+//!
+//! With a cylindrical Earth shadow and a circular orbit of radius `r`, the
+//! fraction of each orbit spent in shadow is
+//!
+//! ```text
+//! f = acos(√(r² − R²) / (r cos β)) / π     for |β| < β*,  else 0
+//! sin β* = R / r
+//! ```
+//!
+//! A *full-sun* orbit is one with `f = 0`.
+
+use crate::elements::EARTH_RADIUS_KM;
+use crate::frames;
+use crate::propagate::{self, Propagator};
+use crate::sun;
+use crate::timescale;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Beta angle and eclipse fraction at time offset `t`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BetaSample {
+    /// Seconds since `start_at`.  Always a multiple of `step_s`.
+    pub t: i64,
+    /// Solar beta angle in degrees, `[-90, 90]`.  Positive when the Sun is on
+    /// the orbit-normal side of the plane.
+    pub beta_deg: f64,
+    /// Fraction of the orbit in Earth shadow, `[0, 0.5]`.  `0` is a full-sun
+    /// orbit.
+    pub eclipse_fraction: f64,
+}
+
+/// A maximal run of consecutive full-sun samples.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FullSunPeriod {
+    /// `t` of the first full-sun sample.
+    pub start_t: i64,
+    /// `t` of the last full-sun sample.
+    pub end_t: i64,
+}
+
+/// Sample the beta angle of a parsed TLE over a window, at the offsets of
+/// [`propagate::propagate_window`].
+///
+/// # Errors
+/// Returns an error if SGP4 diverges.
+pub fn beta_window(
+    propagator: &Propagator,
+    start_at: &DateTime<Utc>,
+    duration_s: i64,
+    step_s: i64,
+) -> Result<Vec<BetaSample>> {
+    let offsets = propagate::window_offsets_ms(0, duration_s * 1_000, step_s * 1_000);
+    let states = propagate::sample_offsets_ms(propagator, start_at, &offsets, true)?;
+    Ok(states
+        .into_iter()
+        .map(|s| {
+            let t_secs = s.t / 1_000;
+            let sample_time = timescale::utc_after_si(start_at, t_secs as f64);
+            let sun_dir = sun::direction(&sample_time);
+            let beta_deg = beta_deg(s.r_km, s.v_km_s.unwrap_or_default(), sun_dir);
+            BetaSample {
+                t: t_secs,
+                beta_deg,
+                eclipse_fraction: eclipse_fraction(frames::norm(s.r_km), beta_deg),
+            }
+        })
+        .collect())
+}
+
+/// Beta angle in degrees for state `(r, v)` and unit Sun direction `sun_dir`.
+pub fn beta_deg(r_km: [f64; 3], v_km_s: [f64; 3], sun_dir: [f64; 3]) -> f64 {
+    let h_hat = frames::unit(frames::cross(r_km, v_km_s));
+    frames::dot(h_hat, sun_dir).clamp(-1.0, 1.0).asin().to_degrees()
+}
+
+/// Fraction of a circular orbit of radius `r_km` spent in the cylindrical
+/// Earth shadow at beta angle `beta_deg`.
+pub fn eclipse_fraction(r_km: f64, beta_deg: f64) -> f64 {
+    if r_km <= EARTH_RADIUS_KM {
+        return 0.5;
+    }
+    let cos_beta = beta_deg.to_radians().cos();
+    let x = (r_km * r_km - EARTH_RADIUS_KM * EARTH_RADIUS_KM).sqrt() / (r_km * cos_beta);
+    if x >= 1.0 {
+        0.0
+    } else {
+        x.acos() / std::f64::consts::PI
+    }
+}
+
+/// Group consecutive full-sun samples into periods.
+pub fn full_sun_periods(samples: &[BetaSample]) -> Vec<FullSunPeriod> {
+    let mut periods = Vec::new();
+    let mut current: Option<FullSunPeriod> = None;
+    for s in samples {
+        if s.eclipse_fraction == 0.0 {
+            match current.as_mut() {
+                Some(p) => p.end_t = s.t,
+                None => {
+                    current = Some(FullSunPeriod {
+                        start_t: s.t,
+                        end_t: s.t,
+                    })
+                }
+            }
+        } else if let Some(p) = current.take() {
+            periods.push(p);
+        }
+    }
+    periods.extend(current);
+    periods
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::propagate::PropagationModel;
+    use chrono::TimeZone;
+
+    const ISS_LINE1: &str =
+        "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9999";
+    const ISS_LINE2: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000013";
+
+    fn iss_epoch() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap()
+    }
+
+    fn iss() -> Propagator {
+        Propagator::new("ISS", ISS_LINE1, ISS_LINE2, PropagationModel::default()).unwrap()
+    }
+
+    /// Same inclusive sample count and `t` alignment as `propagate_window`.
+    #[test]
+    fn sampling_contract() {
+        let samples = beta_window(&iss(), &iss_epoch(), 30 * 86_400, 3_600).unwrap();
+        assert_eq!(samples.len(), 30 * 24 + 1);
+        for (i, s) in samples.iter().enumerate() {
+            assert_eq!(s.t, i as i64 * 3_600);
+        }
+    }
+
+    /// ISS beta never exceeds inclination + obliquity (≈ 75°).
+    #[test]
+    fn iss_beta_is_bounded() {
+        let samples = beta_window(&iss(), &iss_epoch(), 60 * 86_400, 21_600).unwrap();
+        for s in &samples {
+            assert!(s.beta_deg.abs() <= 75.1, "beta {} at t={}", s.beta_deg, s.t);
+        }
+    }
+
+    /// Sun in the orbit plane → β = 0; Sun along the normal → β = 90°.
+    #[test]
+    fn beta_geometry() {
+        let r = [7_000.0, 0.0, 0.0];
+        let v = [0.0, 7.5, 0.0];
+        assert!(beta_deg(r, v, [1.0, 0.0, 0.0]).abs() < 1e-12);
+        assert!((beta_deg(r, v, [0.0, 0.0, 1.0]) - 90.0).abs() < 1e-6);
+        assert!((beta_deg(r, v, [0.0, 0.0, -1.0]) + 90.0).abs() < 1e-6);
+    }
+
+    /// ISS-like orbit: ~38 % eclipse at β = 0, full sun above β* ≈ 70°.
+    #[test]
+    fn eclipse_fraction_limits() {
+        let r = EARTH_RADIUS_KM + 420.0;
+        let f0 = eclipse_fraction(r, 0.0);
+        assert!((f0 - 0.38).abs() < 0.01, "f(0) = {f0}");
+        let beta_star = (EARTH_RADIUS_KM / r).asin().to_degrees();
+        assert_eq!(eclipse_fraction(r, beta_star + 0.1), 0.0);
+        assert!(eclipse_fraction(r, beta_star - 1.0) > 0.0);
+    }
+
+    #[test]
+    fn full_sun_periods_group_runs() {
+        let mk = |t, f| BetaSample {
+            t,
+            beta_deg: 0.0,
+            eclipse_fraction: f,
+        };
+        let samples = vec![
+            mk(0, 0.0),
+            mk(10, 0.0),
+            mk(20, 0.3),
+            mk(30, 0.0),
+            mk(40, 0.2),
+            mk(50, 0.0),
+            mk(60, 0.0),
+        ];
+        assert_eq!(
+            full_sun_periods(&samples),
+            vec![
+                FullSunPeriod { start_t: 0, end_t: 10 },
+                FullSunPeriod { start_t: 30, end_t: 30 },
+                FullSunPeriod { start_t: 50, end_t: 60 },
+            ]
+        );
+    }
+}
@@ -1,5 +1,7 @@
 //! Configuration read from environment variables.
 
+use std::path::PathBuf;
+
 /// Worker runtime configuration.
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -13,13 +15,43 @@ pub struct Config {
     ///
     /// Defaults to the hostname if `WORKER_NAME` is not set.
     pub worker_name: String,
+
+    /// IERS/NTP `leap-seconds.list` file (`LEAP_SECONDS_PATH`).  The
+    /// built-in table is used when unset; a file that is set but unreadable
+    /// stops the worker.
+    pub leap_seconds_path: Option<PathBuf>,
+
+    /// Two-column `MJD UT1-UTC` file (`UT1_UTC_PATH`).  Takes precedence
+    /// over the EOP file's UT1 − UTC column when set.
+    pub ut1_utc_path: Option<PathBuf>,
+
+    /// IERS `finals2000A` / `finals.daily` file (`EOP_PATH`).  The bundled
+    /// snapshot is used when unset; a file that is set but unreadable stops
+    /// the worker.
+    pub eop_path: Option<PathBuf>,
+
+    /// Most jobs processed at once (`WORKER_CONCURRENCY`).
+    ///
+    /// Defaults to the number of available CPU cores.
+    pub concurrency: usize,
+
+    /// Seconds between catalog snapshot refreshes (`SNAPSHOT_CADENCE_S`),
+    /// also the grid snapshot jobs without an epoch snap to.  Defaults to
+    /// 300; `0` disables the background refresh.
+    pub snapshot_cadence_s: u64,
+
+    /// Parsed TLEs kept in [`crate::tle_cache`] (`TLE_CACHE_CAPACITY`).
+    /// Defaults to [`crate::tle_cache::DEFAULT_CAPACITY`].
+    pub tle_cache_capacity: usize,
 }
 
 impl Config {
     /// Build a [`Config`] from environment variables.
     ///
     /// # Errors
-    /// Returns an error if `DATABASE_URL` or `REDIS_URL` are missing.
+    /// Returns an error if `DATABASE_URL` is missing, `WORKER_CONCURRENCY`
+    /// or `TLE_CACHE_CAPACITY` is not a positive integer, or
+    /// `SNAPSHOT_CADENCE_S` is not a non-negative integer.
     pub fn from_env() -> anyhow::Result<Self> {
         let database_url = std::env::var("DATABASE_URL")
             .map_err(|_| anyhow::anyhow!("DATABASE_URL environment variable is required"))?;
@@ -49,10 +81,44 @@ impl Config {
                 "worker-1".to_owned()
             }
         });
+        let concurrency = match std::env::var("WORKER_CONCURRENCY").ok().filter(|v| !v.is_empty()) {
+            Some(v) => v
+                .parse::<usize>()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| anyhow::anyhow!("WORKER_CONCURRENCY must be a positive integer, got {v:?}"))?,
+            None => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        };
+        let snapshot_cadence_s = match std::env::var("SNAPSHOT_CADENCE_S").ok().filter(|v| !v.is_empty()) {
+            Some(v) => v
+                .parse::<u64>()
+                .map_err(|_| anyhow::anyhow!("SNAPSHOT_CADENCE_S must be a non-negative integer, got {v:?}"))?,
+            None => 300,
+        };
+        let tle_cache_capacity = match std::env::var("TLE_CACHE_CAPACITY").ok().filter(|v| !v.is_empty()) {
+            Some(v) => v
+                .parse::<usize>()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| anyhow::anyhow!("TLE_CACHE_CAPACITY must be a positive integer, got {v:?}"))?,
+            None => crate::tle_cache::DEFAULT_CAPACITY,
+        };
         Ok(Self {
             database_url,
             redis_url,
             worker_name,
+            leap_seconds_path: path_var("LEAP_SECONDS_PATH"),
+            ut1_utc_path: path_var("UT1_UTC_PATH"),
+            eop_path: path_var("EOP_PATH"),
+            concurrency,
+            snapshot_cadence_s,
+            tle_cache_capacity,
         })
     }
 }
+
+/// A path from the environment; unset and empty (as Compose passes an unset
+/// `${VAR:-}`) both mean `None`.
+fn path_var(key: &str) -> Option<PathBuf> {
+    std::env::var_os(key).filter(|v| !v.is_empty()).map(PathBuf::from)
+}
@@ -0,0 +1,307 @@
+//! Ground-station network contact plans.
+//!
+//! Every (station, satellite) pair is searched with
+//! [`crate::passes::find_passes`] using the station's own elevation mask, so
+//! contacts agree exactly with single-observer jobs such as `"doppler"`.
+//!
+//! # Overlaps
+//!
+//! A station with one antenna can only track one satellite at a time.  Two
+//! contacts *overlap* when they share a station and their `[aos, los]`
+//! intervals intersect; each overlap is reported once with its common
+//! interval.
+//!
+//! # Utilization
+//!
+//! A station's busy time is the length of the union of its contact intervals
+//! (overlapping contacts are not double counted); utilization is busy time
+//! divided by the window length.
+
+use crate::earth::Observer;
+use crate::passes::{self, Tracker};
+use crate::tle_cache::Parsed;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A ground station with its elevation mask.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroundStation {
+    /// Station identifier, unique within a plan.
+    pub name: String,
+    #[serde(flatten)]
+    pub observer: Observer,
+    /// Elevation mask in degrees.  Defaults to `0`.
+    #[serde(default)]
+    pub min_elevation_deg: f64,
+}
+
+/// An initialised TLE taking part in a plan, shared with
+/// [`crate::tle_cache`].
+pub struct PlanSatellite {
+    pub tle_id: i64,
+    pub name: String,
+    pub propagator: Parsed,
+}
+
+/// One contact between a station and a satellite.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Contact {
+    pub station: String,
+    pub tle_id: i64,
+    pub satellite: String,
+    pub aos: DateTime<Utc>,
+    pub tca: DateTime<Utc>,
+    pub los: DateTime<Utc>,
+    pub max_elevation_deg: f64,
+    pub duration_s: f64,
+}
+
+/// Two contacts competing for the same station.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContactOverlap {
+    pub station: String,
+    /// Indices into [`ContactPlan::contacts`], earlier contact first.
+    pub contacts: [usize; 2],
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Per-station load over the plan window.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StationUtilization {
+    pub station: String,
+    pub contacts: usize,
+    /// Length of the union of the station's contact intervals.
+    pub busy_s: f64,
+    /// `busy_s` divided by the window length, `[0, 1]`.
+    pub utilization: f64,
+}
+
+/// A merged, time-ordered contact schedule.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContactPlan {
+    /// All contacts ordered by AOS (then station, then `tle_id`).
+    pub contacts: Vec<Contact>,
+    pub overlaps: Vec<ContactOverlap>,
+    /// One entry per station, in request order.
+    pub utilization: Vec<StationUtilization>,
+}
+
+/// Build the contact plan for every station/satellite pair in `[start, end]`.
+///
+/// # Errors
+/// Returns an error if SGP4 diverges for any satellite.
+pub fn plan(
+    stations: &[GroundStation],
+    satellites: &[PlanSatellite],
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+) -> Result<ContactPlan> {
+    let mut contacts = Vec::new();
+    for station in stations {
+        for sat in satellites {
+            let tracker = Tracker {
+                elements: &sat.propagator.elements,
+                constants: &sat.propagator.constants,
+                observer: station.observer,
+            };
+            let found = passes::find_passes(&tracker, station.min_elevation_deg, start, end)
+                .with_context(|| {
+                    format!("pass search failed for tle_id {} over {}", sat.tle_id, station.name)
+                })?;
+            contacts.extend(found.into_iter().map(|p| Contact {
+                station: station.name.clone(),
+                tle_id: sat.tle_id,
+                satellite: sat.name.clone(),
+                duration_s: seconds(&p.aos, &p.los),
+                aos: p.aos,
+                tca: p.tca,
+                los: p.los,
+                max_elevation_deg: p.max_elevation_deg,
+            }));
+        }
+    }
+    contacts.sort_by(|a, b| {
+        (a.aos, &a.station, a.tle_id).cmp(&(b.aos, &b.station, b.tle_id))
+    });
+
+    let window_s = seconds(start, end);
+    let utilization = stations
+        .iter()
+        .map(|s| utilization(&s.name, &contacts, window_s))
+        .collect();
+
+    Ok(ContactPlan {
+        overlaps: overlaps(&contacts),
+        utilization,
+        contacts,
+    })
+}
+
+/// Overlapping contact pairs at the same station.  `contacts` must be sorted
+/// by AOS.
+pub fn overlaps(contacts: &[Contact]) -> Vec<ContactOverlap> {
+    let mut out = Vec::new();
+    for (i, a) in contacts.iter().enumerate() {
+        // Later contacts start no earlier than `a`; stop once past its LOS.
+        for (j, b) in contacts.iter().enumerate().skip(i + 1) {
+            if b.aos >= a.los {
+                break;
+            }
+            if a.station == b.station {
+                out.push(ContactOverlap {
+                    station: a.station.clone(),
+                    contacts: [i, j],
+                    start: b.aos,
+                    end: a.los.min(b.los),
+                });
+            }
+        }
+    }
+    out
+}
+
+/// Busy time and utilization of one station.  `contacts` must be sorted by
+/// AOS.
+pub fn utilization(station: &str, contacts: &[Contact], window_s: f64) -> StationUtilization {
+    let mut count = 0;
+    let mut busy_s = 0.0;
+    let mut current: Option<(DateTime<Utc>, DateTime<Utc>)> = None;
+    for c in contacts.iter().filter(|c| c.station == station) {
+        count += 1;
+        current = match current {
+            Some((s, e)) if c.aos <= e => Some((s, e.max(c.los))),
+            Some((s, e)) => {
+                busy_s += seconds(&s, &e);
+                Some((c.aos, c.los))
+            }
+            None => Some((c.aos, c.los)),
+        };
+    }
+    if let Some((s, e)) = current {
+        busy_s += seconds(&s, &e);
+    }
+    StationUtilization {
+        station: station.to_owned(),
+        contacts: count,
+        busy_s,
+        utilization: if window_s > 0.0 { busy_s / window_s } else { 0.0 },
+    }
+}
+
+fn seconds(from: &DateTime<Utc>, to: &DateTime<Utc>) -> f64 {
+    (*to - *from).num_milliseconds() as f64 / 1_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::propagate::{self, PropagationModel, Propagator};
+    use chrono::{Duration, TimeZone};
+    use std::sync::Arc;
+
+    const ISS_LINE1: &str =
+        "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9999";
+    const ISS_LINE2: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000013";
+
+    fn at(h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 4, 26, h, m, 0).unwrap()
+    }
+
+    fn contact(station: &str, tle_id: i64, aos: DateTime<Utc>, los: DateTime<Utc>) -> Contact {
+        Contact {
+            station: station.to_owned(),
+            tle_id,
+            satellite: format!("SAT {tle_id}"),
+            aos,
+            tca: aos,
+            los,
+            max_elevation_deg: 45.0,
+            duration_s: seconds(&aos, &los),
+        }
+    }
+
+    /// Only same-station intersections count as overlaps.
+    #[test]
+    fn overlaps_are_per_station() {
+        let contacts = vec![
+            contact("A", 1, at(12, 0), at(12, 10)),
+            contact("B", 2, at(12, 2), at(12, 8)),
+            contact("A", 2, at(12, 5), at(12, 15)),
+            contact("A", 3, at(12, 20), at(12, 25)),
+        ];
+        let found = overlaps(&contacts);
+        assert_eq!(
+            found,
+            vec![ContactOverlap {
+                station: "A".to_owned(),
+                contacts: [0, 2],
+                start: at(12, 5),
+                end: at(12, 10),
+            }]
+        );
+    }
+
+    /// Busy time is the union of intervals, not the sum.
+    #[test]
+    fn utilization_merges_overlaps() {
+        let contacts = vec![
+            contact("A", 1, at(12, 0), at(12, 10)),
+            contact("A", 2, at(12, 5), at(12, 15)),
+            contact("A", 3, at(12, 30), at(12, 36)),
+        ];
+        let u = utilization("A", &contacts, 3_600.0);
+        assert_eq!(u.contacts, 3);
+        assert_eq!(u.busy_s, 21.0 * 60.0);
+        assert!((u.utilization - 0.35).abs() < 1e-12);
+        assert_eq!(utilization("B", &contacts, 3_600.0).busy_s, 0.0);
+    }
+
+    /// The plan for one station matches `find_passes` for that station and is
+    /// ordered by AOS across stations.
+    #[test]
+    fn plan_matches_single_station_passes() {
+        let (elements, constants) = propagate::init("ISS", ISS_LINE1, ISS_LINE2).unwrap();
+        let stations = vec![
+            GroundStation {
+                name: "london".to_owned(),
+                observer: Observer { lat_deg: 51.5, lon_deg: -0.13, alt_m: 20.0 },
+                min_elevation_deg: 10.0,
+            },
+            GroundStation {
+                name: "madrid".to_owned(),
+                observer: Observer { lat_deg: 40.4, lon_deg: -3.7, alt_m: 650.0 },
+                min_elevation_deg: 5.0,
+            },
+        ];
+        let start = at(12, 0);
+        let end = start + Duration::days(1);
+        let propagator =
+            Propagator::new("ISS", ISS_LINE1, ISS_LINE2, PropagationModel::default()).unwrap();
+        let sats = vec![PlanSatellite {
+            tle_id: 42,
+            name: "ISS".to_owned(),
+            propagator: Arc::new(propagator),
+        }];
+        let plan = plan(&stations, &sats, &start, &end).unwrap();
+
+        let london = passes::find_passes(
+            &Tracker { elements: &elements, constants: &constants, observer: stations[0].observer },
+            10.0,
+            &start,
+            &end,
+        )
+        .unwrap();
+        let planned: Vec<_> = plan.contacts.iter().filter(|c| c.station == "london").collect();
+        assert_eq!(planned.len(), london.len());
+        for (c, p) in planned.iter().zip(&london) {
+            assert_eq!((c.aos, c.los), (p.aos, p.los));
+        }
+        assert!(plan.contacts.windows(2).all(|w| w[0].aos <= w[1].aos));
+        assert_eq!(plan.utilization.len(), 2);
+        // One satellite can never conflict with itself at a station.
+        assert!(plan.overlaps.is_empty());
+    }
+}
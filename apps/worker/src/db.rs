@@ -1,4 +1,8 @@
-//! Database writes for propagated trajectory windows.
+//! Database access for the worker: writes for propagated trajectory windows
+//! (and the chunks of long ones), maneuver events and reentry predictions,
+//! read-only queries over the append-only `tles` history (one satellite's
+//! history, or the latest element set of every satellite), and the stored
+//! maneuvers of one satellite.
 //!
 //! Only `apps/api` (Alembic) owns schema migrations; the worker writes to
 //! `propagated_windows` with an explicit column list so a forgotten migration
@@ -7,11 +11,25 @@
 //! The `ON CONFLICT (hash) DO NOTHING` clause makes the insert idempotent:
 //! re-delivering the same job twice produces exactly one row.
 
-use crate::job::{PropagationResult, Sample};
+use crate::job::{PropagationResult, Sample, WindowChunk, RESULT_FORMAT_SECONDS};
+use crate::lifetime::LifetimeEstimate;
+use crate::maneuver::ManeuverEvent;
+use crate::propagate::PropagationModel;
+use crate::timescale::TimeScale;
 use anyhow::{Context, Result};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 
+/// One row of the `tles` table.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct TleRow {
+    pub id: i64,
+    pub norad_id: i32,
+    pub line1: String,
+    pub line2: String,
+    pub epoch: DateTime<Utc>,
+}
+
 /// Insert a propagated window into `propagated_windows`.
 ///
 /// Returns `Ok(())` whether the row was inserted or already existed (conflict
@@ -29,9 +47,11 @@ pub async fn insert_window(pool: &PgPool, result: &PropagationResult) -> Result<
     sqlx::query(
         r#"
         INSERT INTO propagated_windows
-            (hash, tle_id, start_at, duration_s, step_s, frame, include_velocity, samples)
+            (hash, tle_id, start_at, duration_s, step_s, frame, include_velocity, samples,
+             result_format, step_ms, start_offset_ms, centered, chunk_count, gravity, opsmode,
+             propagator)
         VALUES
-            ($1, $2, $3, $4, $5, $6, $7, $8)
+            ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
         ON CONFLICT (hash) DO NOTHING
         "#,
     )
@@ -43,6 +63,14 @@ pub async fn insert_window(pool: &PgPool, result: &PropagationResult) -> Result<
     .bind(&result.frame)
     .bind(result.include_velocity)
     .bind(samples_json)
+    .bind(i16::from(result.result_format))
+    .bind(result.step_ms.map(|v| v as i32))
+    .bind(result.start_offset_ms.map(|v| v as i32))
+    .bind(result.centered)
+    .bind(result.chunk_count.map(|n| n as i32))
+    .bind(result.model.gravity.as_str())
+    .bind(result.model.opsmode.as_str())
+    .bind(result.model.propagator.as_str())
     .execute(pool)
     .await
     .context("INSERT INTO propagated_windows failed")?;
@@ -50,6 +78,163 @@ pub async fn insert_window(pool: &PgPool, result: &PropagationResult) -> Result<
     Ok(())
 }
 
+/// Insert one chunk of a long window into `propagated_window_chunks`.
+///
+/// Idempotent on `(hash, chunk_index)`.  The parent `propagated_windows` row
+/// is written by [`insert_window`] only after the last chunk, so its presence
+/// means every chunk is stored.
+pub async fn insert_window_chunk(pool: &PgPool, tle_id: i64, chunk: &WindowChunk) -> Result<()> {
+    let samples_json = serde_json::to_value(&chunk.samples)
+        .context("failed to serialise chunk samples to JSON")?;
+    sqlx::query(
+        r#"
+        INSERT INTO propagated_window_chunks
+            (hash, chunk_index, tle_id, start_t, end_t, samples)
+        VALUES
+            ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (hash, chunk_index) DO NOTHING
+        "#,
+    )
+    .bind(&chunk.hash)
+    .bind(chunk.chunk_index as i32)
+    .bind(tle_id)
+    .bind(chunk.samples.first().map_or(0, |s| s.t))
+    .bind(chunk.samples.last().map_or(0, |s| s.t))
+    .bind(samples_json)
+    .execute(pool)
+    .await
+    .context("INSERT INTO propagated_window_chunks failed")?;
+    Ok(())
+}
+
+/// Fetch the `limit` most recent TLE rows for `norad_id`, oldest first.
+///
+/// Returns an empty `Vec` if the satellite has no TLEs on file.
+pub async fn fetch_tle_history(pool: &PgPool, norad_id: i64, limit: i64) -> Result<Vec<TleRow>> {
+    sqlx::query_as::<_, TleRow>(
+        r#"
+        SELECT id, norad_id, line1, line2, epoch
+        FROM (
+            SELECT id, norad_id, line1, line2, epoch
+            FROM tles
+            WHERE norad_id = $1
+            ORDER BY epoch DESC
+            LIMIT $2
+        ) recent
+        ORDER BY epoch ASC
+        "#,
+    )
+    .bind(norad_id as i32)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("SELECT FROM tles failed")
+}
+
+/// Fetch the most recent TLE row of every satellite, by NORAD id.
+pub async fn fetch_latest_tles(pool: &PgPool) -> Result<Vec<TleRow>> {
+    sqlx::query_as::<_, TleRow>(
+        r#"
+        SELECT DISTINCT ON (norad_id) id, norad_id, line1, line2, epoch
+        FROM tles
+        ORDER BY norad_id, epoch DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("SELECT latest FROM tles failed")
+}
+
+/// Insert detected maneuvers into `maneuver_events` in one transaction.
+///
+/// Idempotent on `(from_tle_id, to_tle_id)`: re-running detection over the
+/// same history does not duplicate events.
+pub async fn insert_maneuver_events(pool: &PgPool, events: &[ManeuverEvent]) -> Result<()> {
+    let mut tx = pool.begin().await.context("BEGIN failed")?;
+    for event in events {
+        sqlx::query(
+            r#"
+            INSERT INTO maneuver_events
+                (norad_id, from_tle_id, to_tle_id, epoch, delta_v_m_s,
+                 delta_a_km, delta_i_deg, delta_e, residual_km)
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (from_tle_id, to_tle_id) DO NOTHING
+            "#,
+        )
+        .bind(event.norad_id)
+        .bind(event.from_tle_id)
+        .bind(event.to_tle_id)
+        .bind(event.epoch)
+        .bind(event.delta_v_m_s)
+        .bind(event.delta_a_km)
+        .bind(event.delta_i_deg)
+        .bind(event.delta_e)
+        .bind(event.residual_km)
+        .execute(&mut *tx)
+        .await
+        .context("INSERT INTO maneuver_events failed")?;
+    }
+    tx.commit().await.context("COMMIT of maneuver_events failed")
+}
+
+/// Fetch all stored maneuvers for `norad_id`, oldest first.
+pub async fn fetch_maneuver_events(pool: &PgPool, norad_id: i64) -> Result<Vec<ManeuverEvent>> {
+    sqlx::query_as::<_, ManeuverEvent>(
+        r#"
+        SELECT norad_id, from_tle_id, to_tle_id, epoch, delta_v_m_s,
+               delta_a_km, delta_i_deg, delta_e, residual_km
+        FROM maneuver_events
+        WHERE norad_id = $1
+        ORDER BY epoch ASC
+        "#,
+    )
+    .bind(norad_id as i32)
+    .fetch_all(pool)
+    .await
+    .context("SELECT FROM maneuver_events failed")
+}
+
+/// Upsert a reentry prediction into `reentry_predictions`.
+///
+/// Keyed on `(tle_id, atmosphere)`: re-running the job for the same TLE and
+/// model replaces the stored prediction.
+pub async fn upsert_reentry_prediction(
+    pool: &PgPool,
+    norad_id: i64,
+    tle_id: i64,
+    estimate: &LifetimeEstimate,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO reentry_predictions
+            (norad_id, tle_id, atmosphere, ballistic_coefficient_m2_kg, horizon_end,
+             reentry_epoch, earliest_epoch, latest_epoch)
+        VALUES
+            ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (tle_id, atmosphere) DO UPDATE SET
+            ballistic_coefficient_m2_kg = EXCLUDED.ballistic_coefficient_m2_kg,
+            horizon_end = EXCLUDED.horizon_end,
+            reentry_epoch = EXCLUDED.reentry_epoch,
+            earliest_epoch = EXCLUDED.earliest_epoch,
+            latest_epoch = EXCLUDED.latest_epoch,
+            computed_at = now()
+        "#,
+    )
+    .bind(norad_id as i32)
+    .bind(tle_id)
+    .bind(estimate.atmosphere.as_str())
+    .bind(estimate.ballistic_coefficient_m2_kg)
+    .bind(estimate.horizon_end)
+    .bind(estimate.reentry_epoch)
+    .bind(estimate.earliest_epoch)
+    .bind(estimate.latest_epoch)
+    .execute(pool)
+    .await
+    .context("INSERT INTO reentry_predictions failed")?;
+    Ok(())
+}
+
 /// Fetch samples for a given hash from `propagated_windows`, if the row exists.
 ///
 /// Used by integration tests to verify idempotency without parsing the full
@@ -66,10 +251,12 @@ pub async fn row_exists(pool: &PgPool, hash: &str) -> Result<bool> {
     Ok(row > 0)
 }
 
-/// Build a [`PropagationResult`] from raw parts.
+/// Build a whole-second (format 1), uncentred [`PropagationResult`] from raw
+/// parts; callers set `centered` and the format 2 fields as needed.
 ///
 /// This is a convenience constructor used by both `worker.rs` and the
 /// integration test harness.
+#[allow(clippy::too_many_arguments)]
 pub fn build_result(
     job_id: String,
     tle_id: i64,
@@ -90,6 +277,13 @@ pub fn build_result(
         duration_s,
         step_s,
         include_velocity,
+        centered: false,
+        model: PropagationModel::default(),
+        time_scale: TimeScale::Utc,
+        result_format: RESULT_FORMAT_SECONDS,
+        step_ms: None,
+        start_offset_ms: None,
+        chunk_count: None,
         samples,
         computed_at: Utc::now(),
     }
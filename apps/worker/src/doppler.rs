@@ -0,0 +1,207 @@
+//! Doppler shift tables for a ground station and carrier frequency.
+//!
+//! For each pass found by [`crate::passes::find_passes`] the downlink is
+//! sampled from AOS to LOS.  The received frequency offset is the first-order
+//! Doppler shift
+//!
+//! ```text
+//! Δf = −f₀ · ṙ / c
+//! ```
+//!
+//! where `ṙ` is the observer-to-satellite range rate (positive when
+//! receding).  The offset rate `d(Δf)/dt` uses a central difference of `ṙ`
+//! over [`RATE_HALF_STEP_S`] either side of the sample.
+//!
+//! Results are returned as JSON and, for radio software that wants a plain
+//! table, as CSV (see [`to_csv`]).
+
+use crate::passes::{self, Pass, Tracker};
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+/// Speed of light in km/s.
+pub const SPEED_OF_LIGHT_KM_S: f64 = 299_792.458;
+
+/// Half-width of the range-rate central difference, in seconds.
+pub const RATE_HALF_STEP_S: f64 = 0.5;
+
+/// Doppler state at one instant of a pass.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DopplerSample {
+    pub time: DateTime<Utc>,
+    pub azimuth_deg: f64,
+    pub elevation_deg: f64,
+    pub range_km: f64,
+    /// Positive when receding.
+    pub range_rate_km_s: f64,
+    /// Received minus transmitted frequency, in Hz.
+    pub offset_hz: f64,
+    /// Rate of change of `offset_hz`, in Hz/s.
+    pub rate_hz_s: f64,
+}
+
+/// One pass with its Doppler table.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DopplerPass {
+    #[serde(flatten)]
+    pub pass: Pass,
+    pub samples: Vec<DopplerSample>,
+}
+
+/// First-order Doppler offset in Hz for a carrier `frequency_hz`.
+pub fn offset_hz(frequency_hz: f64, range_rate_km_s: f64) -> f64 {
+    -frequency_hz * range_rate_km_s / SPEED_OF_LIGHT_KM_S
+}
+
+/// Find passes in `[start, end]` and sample each every `step_s` seconds from
+/// AOS, always including LOS as the last sample.
+///
+/// # Errors
+/// Returns an error if SGP4 diverges anywhere in the window.
+pub fn doppler_passes(
+    tracker: &Tracker<'_>,
+    frequency_hz: f64,
+    min_elevation_deg: f64,
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+    step_s: i64,
+) -> Result<Vec<DopplerPass>> {
+    passes::find_passes(tracker, min_elevation_deg, start, end)?
+        .into_iter()
+        .map(|pass| {
+            let mut times = Vec::new();
+            let mut t = pass.aos;
+            while t < pass.los {
+                times.push(t);
+                t += chrono::Duration::seconds(step_s);
+            }
+            times.push(pass.los);
+            let samples = times
+                .iter()
+                .map(|at| sample(tracker, frequency_hz, at))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(DopplerPass { pass, samples })
+        })
+        .collect()
+}
+
+fn sample(tracker: &Tracker<'_>, frequency_hz: f64, at: &DateTime<Utc>) -> Result<DopplerSample> {
+    let look = tracker.look_at(at)?;
+    let before = tracker.look_after(at, -RATE_HALF_STEP_S)?;
+    let after = tracker.look_after(at, RATE_HALF_STEP_S)?;
+    let range_accel = (after.range_rate_km_s - before.range_rate_km_s) / (2.0 * RATE_HALF_STEP_S);
+    Ok(DopplerSample {
+        time: *at,
+        azimuth_deg: look.azimuth_deg,
+        elevation_deg: look.elevation_deg,
+        range_km: look.range_km,
+        range_rate_km_s: look.range_rate_km_s,
+        offset_hz: offset_hz(frequency_hz, look.range_rate_km_s),
+        rate_hz_s: offset_hz(frequency_hz, range_accel),
+    })
+}
+
+/// Render Doppler passes as CSV with a header row.
+///
+/// Columns: `pass,time_utc,azimuth_deg,elevation_deg,range_km,range_rate_km_s,
+/// offset_hz,rate_hz_s,received_hz`.  `pass` is the 1-based pass number and
+/// `received_hz = frequency_hz + offset_hz`.
+pub fn to_csv(frequency_hz: f64, passes: &[DopplerPass]) -> String {
+    let mut csv = String::from(
+        "pass,time_utc,azimuth_deg,elevation_deg,range_km,range_rate_km_s,offset_hz,rate_hz_s,received_hz\n",
+    );
+    for (i, p) in passes.iter().enumerate() {
+        for s in &p.samples {
+            // Writing to a String cannot fail.
+            let _ = writeln!(
+                csv,
+                "{},{},{:.3},{:.3},{:.3},{:.6},{:.1},{:.2},{:.1}",
+                i + 1,
+                s.time.to_rfc3339_opts(SecondsFormat::Millis, true),
+                s.azimuth_deg,
+                s.elevation_deg,
+                s.range_km,
+                s.range_rate_km_s,
+                s.offset_hz,
+                s.rate_hz_s,
+                frequency_hz + s.offset_hz,
+            );
+        }
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earth::Observer;
+    use crate::propagate;
+    use chrono::{Duration, TimeZone};
+
+    const ISS_LINE1: &str =
+        "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9999";
+    const ISS_LINE2: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000013";
+
+    /// Approaching (ṙ < 0) raises the received frequency.
+    #[test]
+    fn offset_sign_and_magnitude() {
+        // ISS 145.8 MHz at −7 km/s → about +3.4 kHz.
+        let f = offset_hz(145.8e6, -7.0);
+        assert!((f - 3_404.3).abs() < 1.0, "offset {f}");
+        assert!(offset_hz(145.8e6, 7.0) < 0.0);
+    }
+
+    /// Over a pass the offset goes from positive (approaching) to negative
+    /// (receding), and the last sample is LOS.
+    #[test]
+    fn pass_offset_crosses_zero() {
+        let (elements, constants) = propagate::init("ISS", ISS_LINE1, ISS_LINE2).unwrap();
+        let tracker = Tracker {
+            elements: &elements,
+            constants: &constants,
+            observer: Observer { lat_deg: 51.5, lon_deg: -0.13, alt_m: 20.0 },
+        };
+        let start = Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap();
+        let end = start + Duration::days(1);
+        let passes = doppler_passes(&tracker, 145.8e6, 10.0, &start, &end, 10).unwrap();
+        let full = passes
+            .iter()
+            .find(|p| p.pass.aos > start && p.pass.los < end)
+            .expect("a complete pass in 24 h");
+        assert!(full.samples.first().unwrap().offset_hz > 0.0);
+        assert!(full.samples.last().unwrap().offset_hz < 0.0);
+        assert_eq!(full.samples.last().unwrap().time, full.pass.los);
+        // Offset decreases through the pass.
+        assert!(full.samples.iter().all(|s| s.rate_hz_s <= 0.0));
+    }
+
+    /// CSV has a header plus one line per sample.
+    #[test]
+    fn csv_has_one_row_per_sample() {
+        let at = Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap();
+        let s = DopplerSample {
+            time: at,
+            azimuth_deg: 10.0,
+            elevation_deg: 20.0,
+            range_km: 900.0,
+            range_rate_km_s: -5.0,
+            offset_hz: 2_431.6,
+            rate_hz_s: -12.5,
+        };
+        let pass = DopplerPass {
+            pass: Pass { aos: at, tca: at, los: at, max_elevation_deg: 20.0 },
+            samples: vec![s.clone(), s],
+        };
+        let csv = to_csv(145.8e6, &[pass]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("pass,time_utc,"));
+        assert_eq!(
+            lines[1],
+            "1,2026-04-26T12:00:00.000Z,10.000,20.000,900.000,-5.000000,2431.6,-12.50,145802431.6"
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! Earth rotation, WGS84 geodesy and topocentric look angles.
+//!
+//! # TEME → ECEF
+//!
+//! SGP4 output is rotated about Z by Greenwich Mean Sidereal Time (IAU 1982
+//! model, see [`gmst_rad`]), evaluated at UT1 from the installed UT1 − UTC,
+//! and then by polar motion `(x_p, y_p)` from the installed EOP series (see
+//! [`crate::eop`]) in [`teme_to_ecef`], giving ITRF.
+//!
+//! # Observer frame
+//!
+//! Look angles are computed in the local East-North-Up frame of a WGS84
+//! geodetic observer: azimuth clockwise from north, elevation above the
+//! ellipsoid-normal horizon.
+
+use crate::elements::EARTH_RADIUS_KM;
+use crate::eop::{self, EopValues};
+use crate::frames;
+use crate::sun::julian_date;
+use crate::timescale::{self, TimeScale};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// WGS84 flattening.
+pub const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+/// Earth rotation rate in rad/s.
+pub const EARTH_ROTATION_RAD_S: f64 = 7.292_115e-5;
+
+/// A ground observer on the WGS84 ellipsoid.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Observer {
+    /// Geodetic latitude in degrees, `[-90, 90]`.
+    pub lat_deg: f64,
+    /// Longitude in degrees, east positive.
+    pub lon_deg: f64,
+    /// Height above the ellipsoid in metres.
+    #[serde(default)]
+    pub alt_m: f64,
+}
+
+/// Topocentric view of a satellite from an [`Observer`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Look {
+    /// Azimuth in degrees clockwise from north, `[0, 360)`.
+    pub azimuth_deg: f64,
+    /// Elevation above the horizon in degrees.
+    pub elevation_deg: f64,
+    /// Slant range in km.
+    pub range_km: f64,
+    /// Range rate in km/s; positive when receding.
+    pub range_rate_km_s: f64,
+}
+
+impl Observer {
+    /// Observer position in ECEF km.
+    pub fn ecef_km(&self) -> [f64; 3] {
+        geodetic_to_ecef(self.lat_deg, self.lon_deg, self.alt_m / 1_000.0)
+    }
+
+    /// Rows of the ECEF → ENU rotation: east, north, up.
+    pub fn enu_basis(&self) -> [[f64; 3]; 3] {
+        let (sin_lat, cos_lat) = self.lat_deg.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = self.lon_deg.to_radians().sin_cos();
+        [
+            [-sin_lon, cos_lon, 0.0],
+            [-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat],
+            [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat],
+        ]
+    }
+
+    /// Look angles to a satellite at TEME state `(r, v)` at time `at`.
+    pub fn look(&self, r_teme_km: [f64; 3], v_teme_km_s: [f64; 3], at: &DateTime<Utc>) -> Look {
+        let (r, v) = teme_to_ecef(r_teme_km, v_teme_km_s, at);
+        let rel = frames::sub(r, self.ecef_km());
+        let range_km = frames::norm(rel);
+        let [east, north, up] = self.enu_basis();
+        let (e, n, u) = (frames::dot(east, rel), frames::dot(north, rel), frames::dot(up, rel));
+        Look {
+            azimuth_deg: e.atan2(n).to_degrees().rem_euclid(360.0),
+            elevation_deg: (u / range_km).clamp(-1.0, 1.0).asin().to_degrees(),
+            range_km,
+            // The observer is fixed in ECEF, so the relative velocity is v.
+            range_rate_km_s: frames::dot(rel, v) / range_km,
+        }
+    }
+}
+
+/// Greenwich Mean Sidereal Time in radians, `[0, 2π)` (IAU 1982).
+///
+/// UT1 is UTC plus the installed UT1 − UTC (see [`crate::timescale`]; zero
+/// when no series is loaded).
+pub fn gmst_rad(at: &DateTime<Utc>) -> f64 {
+    let dut1_days = timescale::scales().offset_s(TimeScale::Ut1, at) / 86_400.0;
+    let t = (julian_date(at) + dut1_days - 2_451_545.0) / 36_525.0;
+    let seconds = 67_310.548_41
+        + (876_600.0 * 3_600.0 + 8_640_184.812_866) * t
+        + 0.093_104 * t * t
+        - 6.2e-6 * t * t * t;
+    (seconds.rem_euclid(86_400.0) / 240.0).to_radians()
+}
+
+/// Rotate a TEME state into ECEF (ITRF): sidereal rotation, then polar
+/// motion from the installed EOP series (see [`crate::eop`]).
+///
+/// The velocity is relative to the rotating frame.
+pub fn teme_to_ecef(
+    r_km: [f64; 3],
+    v_km_s: [f64; 3],
+    at: &DateTime<Utc>,
+) -> ([f64; 3], [f64; 3]) {
+    let (s, c) = gmst_rad(at).sin_cos();
+    let rot = |x: [f64; 3]| [c * x[0] + s * x[1], -s * x[0] + c * x[1], x[2]];
+    let r_pef = rot(r_km);
+    let omega_cross_r = [-EARTH_ROTATION_RAD_S * r_pef[1], EARTH_ROTATION_RAD_S * r_pef[0], 0.0];
+    let v_pef = frames::sub(rot(v_km_s), omega_cross_r);
+    let pm = polar_motion(&eop::current().at(at));
+    (pm(r_pef), pm(v_pef))
+}
+
+/// PEF → ITRF rotation `Wᵀ` for polar motion `(x_p, y_p)` (Vallado 3-77).
+fn polar_motion(values: &EopValues) -> impl Fn([f64; 3]) -> [f64; 3] {
+    let arcsec = std::f64::consts::PI / (180.0 * 3_600.0);
+    let (sx, cx) = (values.x_arcsec * arcsec).sin_cos();
+    let (sy, cy) = (values.y_arcsec * arcsec).sin_cos();
+    move |p: [f64; 3]| {
+        [
+            cx * p[0] + sx * sy * p[1] + sx * cy * p[2],
+            cy * p[1] - sy * p[2],
+            -sx * p[0] + cx * sy * p[1] + cx * cy * p[2],
+        ]
+    }
+}
+
+/// WGS84 geodetic coordinates to ECEF km.
+pub fn geodetic_to_ecef(lat_deg: f64, lon_deg: f64, alt_km: f64) -> [f64; 3] {
+    let e2 = WGS84_F * (2.0 - WGS84_F);
+    let (sin_lat, cos_lat) = lat_deg.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = lon_deg.to_radians().sin_cos();
+    let n = EARTH_RADIUS_KM / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+    [
+        (n + alt_km) * cos_lat * cos_lon,
+        (n + alt_km) * cos_lat * sin_lon,
+        (n * (1.0 - e2) + alt_km) * sin_lat,
+    ]
+}
+
+/// ECEF km to WGS84 geodetic `(lat_deg, lon_deg, alt_km)`.
+///
+/// Fixed-point iteration on the latitude; converges to well below a
+/// millimetre in a handful of steps for any point outside the Earth's core.
+pub fn ecef_to_geodetic(r_km: [f64; 3]) -> (f64, f64, f64) {
+    let e2 = WGS84_F * (2.0 - WGS84_F);
+    let p = r_km[0].hypot(r_km[1]);
+    let lon = r_km[1].atan2(r_km[0]);
+    let mut lat = r_km[2].atan2(p * (1.0 - e2));
+    let mut alt = 0.0;
+    for _ in 0..10 {
+        let sin_lat = lat.sin();
+        let n = EARTH_RADIUS_KM / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+        alt = if lat.cos().abs() > 1e-10 {
+            p / lat.cos() - n
+        } else {
+            r_km[2].abs() - n * (1.0 - e2)
+        };
+        lat = r_km[2].atan2(p * (1.0 - e2 * n / (n + alt)));
+    }
+    (lat.to_degrees(), lon.to_degrees(), alt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// GMST at 2000-01-01 12:00 UT1 is 280.46062°.
+    #[test]
+    fn gmst_at_j2000() {
+        let at = Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+        assert!((gmst_rad(&at).to_degrees() - 280.460_62).abs() < 1e-4);
+    }
+
+    /// Equator/prime meridian sits on +X at the equatorial radius; the pole
+    /// sits on +Z at the polar radius.
+    #[test]
+    fn geodetic_to_ecef_reference_points() {
+        let eq = geodetic_to_ecef(0.0, 0.0, 0.0);
+        assert!((eq[0] - EARTH_RADIUS_KM).abs() < 1e-9 && eq[1].abs() < 1e-9);
+        let pole = geodetic_to_ecef(90.0, 0.0, 0.0);
+        assert!((pole[2] - 6_356.752_314).abs() < 1e-3, "polar radius {}", pole[2]);
+    }
+
+    /// Geodetic → ECEF → geodetic is the identity, including at the poles.
+    #[test]
+    fn ecef_to_geodetic_roundtrip() {
+        for &(lat, lon, alt) in &[
+            (0.0, 0.0, 0.0),
+            (51.5, -0.13, 0.02),
+            (-33.9, 151.2, 420.0),
+            (89.999, 45.0, 850.0),
+            (-90.0, 0.0, 35_786.0),
+        ] {
+            let (lat2, lon2, alt2) = ecef_to_geodetic(geodetic_to_ecef(lat, lon, alt));
+            assert!((lat2 - lat).abs() < 1e-9, "lat {lat} → {lat2}");
+            assert!((alt2 - alt).abs() < 1e-6, "alt {alt} → {alt2}");
+            if lat.abs() < 90.0 {
+                assert!((lon2 - lon).abs() < 1e-9, "lon {lon} → {lon2}");
+            }
+        }
+    }
+
+    /// A satellite straight overhead is at 90° elevation and `h` km range.
+    #[test]
+    fn zenith_look() {
+        let at = Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap();
+        let obs = Observer { lat_deg: 0.0, lon_deg: 0.0, alt_m: 0.0 };
+        // Place the satellite over (0°, 0°) in ECEF, then rotate back to TEME.
+        let g = gmst_rad(&at);
+        let r_ecef = [EARTH_RADIUS_KM + 500.0, 0.0, 0.0];
+        let r_teme = [r_ecef[0] * g.cos(), r_ecef[0] * g.sin(), 0.0];
+        let look = obs.look(r_teme, [0.0, 0.0, 0.0], &at);
+        // Polar motion (< 1″) tilts the PEF placement slightly off zenith.
+        assert!((look.elevation_deg - 90.0).abs() < 1e-3);
+        assert!((look.range_km - 500.0).abs() < 1e-6);
+    }
+
+    /// Approaching satellite → negative range rate.
+    #[test]
+    fn approaching_has_negative_range_rate() {
+        let at = Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap();
+        let obs = Observer { lat_deg: 0.0, lon_deg: 0.0, alt_m: 0.0 };
+        let g = gmst_rad(&at);
+        let r = EARTH_RADIUS_KM + 500.0;
+        let r_teme = [r * g.cos(), r * g.sin(), 0.0];
+        // Falling straight down towards the observer.
+        let v_teme = [-g.cos(), -g.sin(), 0.0];
+        assert!(obs.look(r_teme, v_teme, &at).range_rate_km_s < 0.0);
+    }
+}
@@ -0,0 +1,152 @@
+//! Classical mean elements derived from a parsed TLE.
+//!
+//! The `sgp4` crate exposes the raw TLE fields (mean motion in rev/day, angles
+//! in degrees).  This module turns them into the quantities the analysis jobs
+//! reason about — semi-major axis, period, perigee/apogee altitude — so every
+//! feature derives them the same way.
+//!
+//! The semi-major axis is computed from the Kozai mean motion with Kepler's
+//! third law.  It differs from SGP4's internal Brouwer value by a few km for
+//! LEO, which is irrelevant for the comparisons made between TLEs of the same
+//! object.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Earth gravitational parameter μ in km³/s² (WGS84).
+pub const MU_EARTH_KM3_S2: f64 = 398_600.441_8;
+
+/// Earth equatorial radius in km (WGS84).
+pub const EARTH_RADIUS_KM: f64 = 6_378.137;
+
+/// Earth second zonal harmonic J2 (WGS84).
+pub const J2: f64 = 1.08262998905e-3;
+
+/// Seconds per day.
+pub const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Mean orbital elements at the TLE epoch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MeanElements {
+    /// TLE epoch (UTC).
+    pub epoch: DateTime<Utc>,
+    /// Semi-major axis in km.
+    pub semi_major_axis_km: f64,
+    pub eccentricity: f64,
+    pub inclination_deg: f64,
+    /// Right ascension of the ascending node in degrees.
+    pub raan_deg: f64,
+    pub arg_perigee_deg: f64,
+    pub mean_anomaly_deg: f64,
+    /// Kozai mean motion in rev/day.
+    pub mean_motion_rev_day: f64,
+    /// First derivative of mean motion divided by two, in rev/day² (the TLE
+    /// Line 1 field as published).
+    pub mean_motion_dot: f64,
+    /// SGP4 drag term B* in 1/Earth radii.
+    pub bstar: f64,
+}
+
+impl MeanElements {
+    /// Derive mean elements from a parsed TLE.
+    pub fn from_elements(elements: &sgp4::Elements) -> Self {
+        Self {
+            epoch: DateTime::from_naive_utc_and_offset(elements.datetime, Utc),
+            semi_major_axis_km: semi_major_axis_km(elements.mean_motion),
+            eccentricity: elements.eccentricity,
+            inclination_deg: elements.inclination,
+            raan_deg: elements.right_ascension,
+            arg_perigee_deg: elements.argument_of_perigee,
+            mean_anomaly_deg: elements.mean_anomaly,
+            mean_motion_rev_day: elements.mean_motion,
+            mean_motion_dot: elements.mean_motion_dot,
+            bstar: elements.drag_term,
+        }
+    }
+
+    /// Orbital period in minutes.
+    pub fn period_min(&self) -> f64 {
+        1_440.0 / self.mean_motion_rev_day
+    }
+
+    /// Mean motion in rad/s.
+    pub fn mean_motion_rad_s(&self) -> f64 {
+        self.mean_motion_rev_day * std::f64::consts::TAU / SECONDS_PER_DAY
+    }
+
+    /// Perigee altitude above the equatorial radius in km.
+    pub fn perigee_alt_km(&self) -> f64 {
+        self.semi_major_axis_km * (1.0 - self.eccentricity) - EARTH_RADIUS_KM
+    }
+
+    /// Apogee altitude above the equatorial radius in km.
+    pub fn apogee_alt_km(&self) -> f64 {
+        self.semi_major_axis_km * (1.0 + self.eccentricity) - EARTH_RADIUS_KM
+    }
+
+    /// Secular semi-major-axis rate in km/day implied by the TLE's
+    /// `mean_motion_dot` (drag), from `ȧ = -2/3 · a/n · ṅ`.
+    pub fn drag_decay_km_day(&self) -> f64 {
+        // The TLE field is ṅ/2.
+        let n_dot = 2.0 * self.mean_motion_dot;
+        -2.0 / 3.0 * self.semi_major_axis_km / self.mean_motion_rev_day * n_dot
+    }
+
+    /// Secular nodal precession rate due to J2 in degrees/day.
+    pub fn raan_rate_deg_day(&self) -> f64 {
+        let a = self.semi_major_axis_km;
+        let p = a * (1.0 - self.eccentricity * self.eccentricity);
+        let rate_rad_s = -1.5 * self.mean_motion_rad_s() * J2 * (EARTH_RADIUS_KM / p).powi(2)
+            * self.inclination_deg.to_radians().cos();
+        rate_rad_s.to_degrees() * SECONDS_PER_DAY
+    }
+}
+
+/// Semi-major axis in km from a mean motion in rev/day.
+pub fn semi_major_axis_km(mean_motion_rev_day: f64) -> f64 {
+    let n_rad_s = mean_motion_rev_day * std::f64::consts::TAU / SECONDS_PER_DAY;
+    (MU_EARTH_KM3_S2 / (n_rad_s * n_rad_s)).cbrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISS_LINE1: &str =
+        "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9999";
+    const ISS_LINE2: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000013";
+
+    fn iss() -> MeanElements {
+        let (elements, _) = crate::propagate::init("ISS", ISS_LINE1, ISS_LINE2).unwrap();
+        MeanElements::from_elements(&elements)
+    }
+
+    /// ISS at 15.5 rev/day sits at roughly 6 790 km, ~92.9 min period.
+    #[test]
+    fn iss_semi_major_axis_and_period() {
+        let m = iss();
+        assert!((m.semi_major_axis_km - 6_790.0).abs() < 10.0, "a = {}", m.semi_major_axis_km);
+        assert!((m.period_min() - 92.9).abs() < 0.1, "period = {}", m.period_min());
+        assert!(m.perigee_alt_km() > 400.0 && m.apogee_alt_km() < 430.0);
+    }
+
+    /// Positive `mean_motion_dot` means the orbit is decaying.
+    #[test]
+    fn drag_decay_is_negative_for_positive_ndot() {
+        assert!(iss().drag_decay_km_day() < 0.0);
+    }
+
+    /// ISS node regresses ~5 deg/day.
+    #[test]
+    fn iss_nodal_regression() {
+        let rate = iss().raan_rate_deg_day();
+        assert!((rate + 5.0).abs() < 0.3, "raan rate = {rate}");
+    }
+
+    /// GEO mean motion (1.0027 rev/day) gives a ≈ 42 164 km.
+    #[test]
+    fn geo_semi_major_axis() {
+        assert!((semi_major_axis_km(1.002_737_9) - 42_164.0).abs() < 2.0);
+    }
+}
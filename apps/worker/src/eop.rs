@@ -0,0 +1,199 @@
+//! IERS Earth orientation parameters (polar motion and UT1 − UTC).
+//!
+//! # Sources
+//!
+//! - An IERS `finals2000A` file (the full series from 1973, or the shorter
+//!   `finals.daily` / `finals2000A.daily` covering the last 90 days plus 90
+//!   days of predictions).  Both share the same fixed-column layout; the
+//!   Bulletin A columns are read.  The path comes from
+//!   [`crate::config::Config::eop_path`].
+//! - A bundled snapshot, `apps/worker/data/finals2000A-fallback.txt`, used
+//!   only when no path is configured (a configured file that cannot be read
+//!   or parsed stops startup instead), mirroring `apps/api/data/celestrak-fallback.json` for TLEs.  It is an
+//!   unmodified excerpt of the USNO `finals2000A.all` (IAU 2000) series,
+//!   <https://maia.usno.navy.mil/ser7/finals2000A.all>: the daily rows from
+//!   2025-01-01 to the last predicted one.  Rows up to 2026-07-09 are
+//!   Bulletin A values (flag `I`), later ones predictions (flag `P`) to
+//!   2027-07-17.  It was taken on 2026-10-18 from the copy shipped in the
+//!   `brahe` 1.7.0 crate (`data/eop/finals.all.iau2000.txt`).  Predictions
+//!   degrade with distance from their issue date, so configure a current
+//!   file for production.
+//!
+//! # Interpolation
+//!
+//! Values are linearly interpolated in MJD between rows and held
+//! constant past either end of the table.  UT1 − UTC is interpolated as is;
+//! rows straddling a leap second are not expected within the short spans
+//! the worker queries.
+
+use crate::timescale::{self, Ut1Utc};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// The bundled fallback snapshot.
+const FALLBACK: &str = include_str!("../data/finals2000A-fallback.txt");
+
+/// Earth orientation at one instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EopValues {
+    /// Polar motion `x_p` in arcseconds.
+    pub x_arcsec: f64,
+    /// Polar motion `y_p` in arcseconds.
+    pub y_arcsec: f64,
+    /// UT1 − UTC in seconds.
+    pub ut1_utc_s: f64,
+}
+
+/// A daily EOP series, ascending MJD.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Eop {
+    rows: Vec<(f64, EopValues)>,
+}
+
+impl Eop {
+    /// Parse a `finals2000A` / `finals.daily` file.
+    ///
+    /// Rows without polar motion or UT1 − UTC (the far end of the
+    /// prediction span) are skipped.
+    ///
+    /// # Errors
+    /// Returns an error if a populated column cannot be parsed or no usable
+    /// row remains.
+    pub fn parse_finals(text: &str) -> Result<Self> {
+        let mut rows: Vec<(f64, EopValues)> = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            let field = |from: usize, to: usize| line.get(from..to).map(str::trim).unwrap_or("");
+            let (mjd, x, y, dut1) = (field(7, 15), field(18, 27), field(37, 46), field(58, 68));
+            if mjd.is_empty() || x.is_empty() || y.is_empty() || dut1.is_empty() {
+                continue;
+            }
+            let parse = |s: &str, what: &str| {
+                s.parse::<f64>()
+                    .with_context(|| format!("line {}: invalid {what} {s:?}", n + 1))
+            };
+            let mjd = parse(mjd, "MJD")?;
+            if rows.last().is_some_and(|(last, _)| *last >= mjd) {
+                bail!("line {}: MJD {mjd} is not after the previous row", n + 1);
+            }
+            rows.push((
+                mjd,
+                EopValues {
+                    x_arcsec: parse(x, "PM-x")?,
+                    y_arcsec: parse(y, "PM-y")?,
+                    ut1_utc_s: parse(dut1, "UT1-UTC")?,
+                },
+            ));
+        }
+        if rows.is_empty() {
+            bail!("no EOP rows with polar motion and UT1-UTC");
+        }
+        Ok(Self { rows })
+    }
+
+    /// The bundled snapshot.
+    pub fn bundled() -> Self {
+        Self::parse_finals(FALLBACK).expect("bundled EOP snapshot is valid")
+    }
+
+    /// Load `path`, or the bundled snapshot when `path` is `None`.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        match path {
+            Some(path) => {
+                let text = std::fs::read_to_string(path)
+                    .with_context(|| format!("failed to read {}", path.display()))?;
+                Self::parse_finals(&text)
+                    .with_context(|| format!("invalid EOP file {}", path.display()))
+            }
+            None => Ok(Self::bundled()),
+        }
+    }
+
+    /// MJD span covered by the table.
+    pub fn span_mjd(&self) -> (f64, f64) {
+        (self.rows[0].0, self.rows[self.rows.len() - 1].0)
+    }
+
+    /// Interpolated values at a UTC instant.
+    pub fn at(&self, at: &DateTime<Utc>) -> EopValues {
+        let mjd = timescale::mjd(at);
+        EopValues {
+            x_arcsec: timescale::interpolate(&self.rows, mjd, |r| r.1.x_arcsec),
+            y_arcsec: timescale::interpolate(&self.rows, mjd, |r| r.1.y_arcsec),
+            ut1_utc_s: timescale::interpolate(&self.rows, mjd, |r| r.1.ut1_utc_s),
+        }
+    }
+
+    /// The UT1 − UTC column as a [`Ut1Utc`] series for [`crate::timescale`].
+    pub fn ut1_utc(&self) -> Ut1Utc {
+        let points = self.rows.iter().map(|(mjd, v)| (*mjd, v.ut1_utc_s)).collect();
+        Ut1Utc::new(points).expect("EOP rows are non-empty and ascending")
+    }
+}
+
+static INSTALLED: OnceLock<Eop> = OnceLock::new();
+
+/// Make `eop` the process-wide series.  Returns `false` (and keeps the
+/// existing series) if one was already installed or used.
+pub fn install(eop: Eop) -> bool {
+    INSTALLED.set(eop).is_ok()
+}
+
+/// The process-wide series (the bundled snapshot until [`install`] is
+/// called).
+pub fn current() -> &'static Eop {
+    INSTALLED.get_or_init(Eop::bundled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    // Two finals2000A rows (Bulletin A part) and a prediction row without UT1.
+    const SAMPLE: &str = "\
+26 1 1 61041.00 I  0.100000 0.000050  0.300000 0.000050  I 0.0500000 0.0000100
+26 1 2 61042.00 I  0.102000 0.000050  0.298000 0.000050  I 0.0490000 0.0000100
+26 1 3 61043.00 P  0.104000 0.000500  0.296000 0.000500
+";
+
+    #[test]
+    fn parses_and_interpolates_finals() {
+        let eop = Eop::parse_finals(SAMPLE).unwrap();
+        assert_eq!(eop.span_mjd(), (61_041.0, 61_042.0));
+        let noon = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        let v = eop.at(&noon);
+        assert!((v.x_arcsec - 0.101).abs() < 1e-12);
+        assert!((v.y_arcsec - 0.299).abs() < 1e-12);
+        assert!((v.ut1_utc_s - 0.0495).abs() < 1e-12);
+        // Held constant past the end.
+        let later = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+        assert_eq!(eop.at(&later).ut1_utc_s, 0.049);
+        assert_eq!(eop.ut1_utc().at(&noon), v.ut1_utc_s);
+    }
+
+    #[test]
+    fn rejects_bad_files() {
+        assert!(Eop::parse_finals("").is_err());
+        let rows: Vec<&str> = SAMPLE.lines().collect();
+        assert!(Eop::parse_finals(&format!("{}\n{}", rows[1], rows[0])).is_err());
+        assert!(Eop::load(Some(Path::new("/nonexistent/finals2000A.all"))).is_err());
+    }
+
+    /// The bundled snapshot parses, is daily, and covers the fallback TLE
+    /// epochs.
+    #[test]
+    fn bundled_snapshot_covers_fallback_epochs() {
+        let eop = Eop::load(None).unwrap();
+        assert!(eop.rows.windows(2).all(|w| w[1].0 - w[0].0 == 1.0));
+        let tle_epoch = timescale::mjd(&Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap());
+        let (first, last) = eop.span_mjd();
+        assert!(first < tle_epoch && tle_epoch < last);
+        let v = eop.at(&Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap());
+        assert!(v.x_arcsec.abs() < 1.0 && v.y_arcsec.abs() < 1.0 && v.ut1_utc_s.abs() < 0.9);
+    }
+}
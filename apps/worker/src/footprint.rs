@@ -0,0 +1,507 @@
+//! Ground footprint (coverage) polygons on the WGS84 ellipsoid.
+//!
+//! # Geometry
+//!
+//! For each of `vertices` azimuths `α` around the sub-satellite point, a ray
+//! is cast from the satellite at off-nadir angle `η` towards azimuth `α`
+//! (nadir is the geodetic vertical at the sub-satellite point) and
+//! intersected with the ellipsoid.  The boundary vertex is the hit point for
+//! the largest `η` allowed by the [`FootprintMask`]:
+//!
+//! - **`min_elevation`** — the ground point from which the satellite is seen
+//!   exactly at the mask elevation.  Found by bisection on `η`, since
+//!   elevation falls monotonically from 90° at nadir to 0° at the horizon.
+//! - **`half_cone`** — the sensor edge `η = half_cone_deg`.  A cone wider than
+//!   the Earth's limb is clipped to the horizon (0° elevation).
+//!
+//! # Polygon output
+//!
+//! Footprints are GeoJSON `MultiPolygon` coordinates (RFC 7946 §3.1.7): a
+//! list of polygons, each a list of rings holding one closed exterior ring of
+//! `[lon_deg, lat_deg]` vertices.  Exterior rings run counterclockwise
+//! (§3.1.6) and longitudes stay in `[-180, 180]` (§3.1.9).
+//!
+//! - A ring that crosses the antimeridian is split into one polygon on each
+//!   side.
+//! - A ring that encloses a pole is closed along the antimeridian through the
+//!   pole, so the single polygon covers the polar cap.
+
+use crate::earth::{self, Observer, WGS84_F};
+use crate::elements::EARTH_RADIUS_KM;
+use crate::frames;
+use crate::propagate::{self, Propagator};
+use crate::timescale;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Bisection iterations on the off-nadir angle (≈ 1e-15 rad resolution).
+const BISECTION_ITERATIONS: usize = 50;
+
+/// Which edge bounds the footprint.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FootprintMask {
+    /// Ground points that see the satellite at or above an elevation.
+    MinElevation { min_elevation_deg: f64 },
+    /// Ground points inside a nadir-pointing sensor cone.
+    HalfCone { half_cone_deg: f64 },
+}
+
+impl FootprintMask {
+    /// Check the angle is physically meaningful.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when the angle is out of range.
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            Self::MinElevation { min_elevation_deg } if !(0.0..90.0).contains(&min_elevation_deg) => {
+                Err(format!("min_elevation_deg must be in [0, 90), got {min_elevation_deg}"))
+            }
+            Self::HalfCone { half_cone_deg } if !(half_cone_deg > 0.0 && half_cone_deg < 90.0) => {
+                Err(format!("half_cone_deg must be in (0, 90), got {half_cone_deg}"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Default number of boundary vertices (one every 5° of azimuth).
+pub const DEFAULT_VERTICES: usize = 72;
+
+/// Allowed range for the number of boundary vertices.
+pub const VERTICES_RANGE: std::ops::RangeInclusive<usize> = 8..=720;
+
+/// Footprint edge and polygon resolution.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct FootprintOptions {
+    #[serde(flatten)]
+    pub mask: FootprintMask,
+    /// Boundary vertices per footprint, evenly spaced in azimuth.
+    #[serde(default = "default_vertices")]
+    pub vertices: usize,
+}
+
+fn default_vertices() -> usize {
+    DEFAULT_VERTICES
+}
+
+impl FootprintOptions {
+    /// Check the mask angle and resolution.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when a bound is violated.
+    pub fn validate(&self) -> Result<(), String> {
+        if !VERTICES_RANGE.contains(&self.vertices) {
+            return Err(format!(
+                "vertices must be in [{}, {}], got {}",
+                VERTICES_RANGE.start(),
+                VERTICES_RANGE.end(),
+                self.vertices
+            ));
+        }
+        self.mask.validate()
+    }
+}
+
+/// GeoJSON `MultiPolygon` coordinates: polygons → rings → positions.
+pub type MultiPolygon = Vec<Vec<Vec<[f64; 2]>>>;
+
+/// Sub-satellite point and footprint at time offset `t`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FootprintSample {
+    /// Seconds since `start_at`.  Always a multiple of `step_s`.
+    pub t: i64,
+    /// Geodetic latitude of the sub-satellite point in degrees.
+    pub lat_deg: f64,
+    /// Longitude of the sub-satellite point in degrees, `[-180, 180]`.
+    pub lon_deg: f64,
+    /// Height above the ellipsoid in km.
+    pub alt_km: f64,
+    /// GeoJSON `MultiPolygon` coordinates (see the module docs).
+    pub footprint: MultiPolygon,
+}
+
+/// Sample the footprint of a parsed TLE over a window, at the offsets of
+/// [`propagate::propagate_window`].
+///
+/// # Errors
+/// Returns an error if SGP4 diverges.
+pub fn footprint_window(
+    propagator: &Propagator,
+    start_at: &DateTime<Utc>,
+    duration_s: i64,
+    step_s: i64,
+    options: &FootprintOptions,
+) -> Result<Vec<FootprintSample>> {
+    let offsets = propagate::window_offsets_ms(0, duration_s * 1_000, step_s * 1_000);
+    let states = propagate::sample_offsets_ms(propagator, start_at, &offsets, true)?;
+    Ok(states
+        .into_iter()
+        .map(|s| {
+            let t_secs = s.t / 1_000;
+            let sample_time = timescale::utc_after_si(start_at, t_secs as f64);
+            let (r_ecef, _) =
+                earth::teme_to_ecef(s.r_km, s.v_km_s.unwrap_or_default(), &sample_time);
+            let (lat_deg, lon_deg, alt_km) = earth::ecef_to_geodetic(r_ecef);
+            FootprintSample {
+                t: t_secs,
+                lat_deg,
+                lon_deg,
+                alt_km,
+                footprint: to_multipolygon(
+                    &boundary(r_ecef, options.mask, options.vertices),
+                    lat_deg,
+                ),
+            }
+        })
+        .collect())
+}
+
+/// Footprint boundary of a satellite at ECEF position `r_ecef_km`, as
+/// `vertices` `[lon_deg, lat_deg]` points counterclockwise from north
+/// (unclosed, longitudes wrapped to `[-180, 180]`).
+pub fn boundary(r_ecef_km: [f64; 3], mask: FootprintMask, vertices: usize) -> Vec<[f64; 2]> {
+    let (lat, lon, _) = earth::ecef_to_geodetic(r_ecef_km);
+    let [east, north, up] = Observer { lat_deg: lat, lon_deg: lon, alt_m: 0.0 }.enu_basis();
+    let nadir = frames::scale(up, -1.0);
+
+    (0..vertices)
+        .map(|i| {
+            // North, then west: counterclockwise on the map.
+            let az = -std::f64::consts::TAU * i as f64 / vertices as f64;
+            let (sin_az, cos_az) = az.sin_cos();
+            let towards = frames::add(frames::scale(north, cos_az), frames::scale(east, sin_az));
+            let ray = |eta: f64| {
+                let (s, c) = eta.sin_cos();
+                frames::add(frames::scale(nadir, c), frames::scale(towards, s))
+            };
+
+            // Largest off-nadir angle that still hits the ellipsoid.
+            let (mut lo, mut hi) = (0.0, std::f64::consts::FRAC_PI_2);
+            for _ in 0..BISECTION_ITERATIONS {
+                let mid = 0.5 * (lo + hi);
+                if intersect_ellipsoid(r_ecef_km, ray(mid)).is_some() {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            let horizon_eta = lo;
+
+            let eta = match mask {
+                FootprintMask::HalfCone { half_cone_deg } => half_cone_deg.to_radians().min(horizon_eta),
+                FootprintMask::MinElevation { min_elevation_deg } => {
+                    let elevation_at = |eta: f64| {
+                        intersect_ellipsoid(r_ecef_km, ray(eta))
+                            .map_or(0.0, |g| elevation_deg(g, r_ecef_km))
+                    };
+                    let (mut lo, mut hi) = (0.0, horizon_eta);
+                    for _ in 0..BISECTION_ITERATIONS {
+                        let mid = 0.5 * (lo + hi);
+                        if elevation_at(mid) >= min_elevation_deg {
+                            lo = mid;
+                        } else {
+                            hi = mid;
+                        }
+                    }
+                    lo
+                }
+            };
+
+            let ground = intersect_ellipsoid(r_ecef_km, ray(eta)).unwrap_or(r_ecef_km);
+            let (g_lat, g_lon, _) = earth::ecef_to_geodetic(ground);
+            [g_lon, g_lat]
+        })
+        .collect()
+}
+
+/// Nearest intersection of the ray `origin + s·dir` (`s > 0`) with the WGS84
+/// ellipsoid, if any.
+fn intersect_ellipsoid(origin: [f64; 3], dir: [f64; 3]) -> Option<[f64; 3]> {
+    // Scale z so the ellipsoid becomes a sphere of radius a.
+    let k = 1.0 / (1.0 - WGS84_F);
+    let o = [origin[0], origin[1], origin[2] * k];
+    let d = [dir[0], dir[1], dir[2] * k];
+    let a = frames::dot(d, d);
+    let b = 2.0 * frames::dot(o, d);
+    let c = frames::dot(o, o) - EARTH_RADIUS_KM * EARTH_RADIUS_KM;
+    let disc = b * b - 4.0 * a * c;
+    if disc < 0.0 {
+        return None;
+    }
+    let s = (-b - disc.sqrt()) / (2.0 * a);
+    (s > 0.0).then(|| frames::add(origin, frames::scale(dir, s)))
+}
+
+/// Elevation of `target` seen from a point `ground` on the ellipsoid.
+fn elevation_deg(ground: [f64; 3], target: [f64; 3]) -> f64 {
+    let e2 = WGS84_F * (2.0 - WGS84_F);
+    let normal = frames::unit([ground[0], ground[1], ground[2] / (1.0 - e2)]);
+    let los = frames::unit(frames::sub(target, ground));
+    frames::dot(normal, los).clamp(-1.0, 1.0).asin().to_degrees()
+}
+
+/// Turn a boundary ring into GeoJSON `MultiPolygon` coordinates, splitting at
+/// the antimeridian and closing pole-enclosing rings through the pole.
+///
+/// `ring` runs counterclockwise, as from [`boundary`]; `center_lat_deg` picks
+/// which pole a pole-enclosing ring contains.
+pub fn to_multipolygon(ring: &[[f64; 2]], center_lat_deg: f64) -> MultiPolygon {
+    if ring.len() < 3 {
+        return Vec::new();
+    }
+
+    // Unwrap longitudes so consecutive vertices never jump by more than 180°.
+    let mut unwrapped = Vec::with_capacity(ring.len());
+    unwrapped.push(ring[0]);
+    for p in &ring[1..] {
+        let prev = unwrapped[unwrapped.len() - 1][0];
+        unwrapped.push([prev + wrap_delta(p[0] - prev), p[1]]);
+    }
+    let last = unwrapped[unwrapped.len() - 1][0];
+    let winding = last + wrap_delta(ring[0][0] - last) - ring[0][0];
+
+    if winding.abs() > 180.0 {
+        vec![vec![pole_polygon(ring, center_lat_deg.signum() * 90.0)]]
+    } else {
+        let mut polygons = Vec::new();
+        for shift in [-360.0, 0.0, 360.0] {
+            // Strip [-180, 180] shifted back by `shift`.
+            let clipped = clip_strip(&unwrapped, -180.0 - shift, 180.0 - shift);
+            if clipped.len() >= 3 {
+                polygons.push(vec![close(clipped.iter().map(|p| [p[0] + shift, p[1]]).collect())]);
+            }
+        }
+        polygons
+    }
+}
+
+/// Signed longitude difference wrapped to `(-180, 180]`.
+fn wrap_delta(d: f64) -> f64 {
+    let w = (d + 180.0).rem_euclid(360.0) - 180.0;
+    if w == -180.0 {
+        180.0
+    } else {
+        w
+    }
+}
+
+/// Close a ring by repeating its first vertex.
+fn close(mut ring: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    if let Some(&first) = ring.first() {
+        ring.push(first);
+    }
+    ring
+}
+
+/// Sutherland–Hodgman clip of a ring to `lo ≤ lon ≤ hi`.
+fn clip_strip(ring: &[[f64; 2]], lo: f64, hi: f64) -> Vec<[f64; 2]> {
+    let half = clip_half(ring, |p| p[0] >= lo, lo);
+    clip_half(&half, |p| p[0] <= hi, hi)
+}
+
+/// Clip a ring to the half-plane where `inside` holds; `edge` is the
+/// longitude of the boundary.
+fn clip_half(ring: &[[f64; 2]], inside: impl Fn(&[f64; 2]) -> bool, edge: f64) -> Vec<[f64; 2]> {
+    let crossing = |a: [f64; 2], b: [f64; 2]| {
+        let f = (edge - a[0]) / (b[0] - a[0]);
+        [edge, a[1] + f * (b[1] - a[1])]
+    };
+    let mut out = Vec::with_capacity(ring.len() + 2);
+    for (i, &cur) in ring.iter().enumerate() {
+        let prev = ring[(i + ring.len() - 1) % ring.len()];
+        match (inside(&prev), inside(&cur)) {
+            (true, true) => out.push(cur),
+            (true, false) => out.push(crossing(prev, cur)),
+            (false, true) => {
+                out.push(crossing(prev, cur));
+                out.push(cur);
+            }
+            (false, false) => {}
+        }
+    }
+    out
+}
+
+/// Single polygon for a ring that winds once around the pole at `pole_lat`.
+fn pole_polygon(ring: &[[f64; 2]], pole_lat: f64) -> Vec<[f64; 2]> {
+    let n = ring.len();
+    // The edge that crosses the antimeridian.
+    let k = (0..n)
+        .find(|&i| (ring[(i + 1) % n][0] - ring[i][0]).abs() > 180.0)
+        .unwrap_or(n - 1);
+    let a = ring[k];
+    let b = ring[(k + 1) % n];
+    let b_lon = a[0] + wrap_delta(b[0] - a[0]);
+    let edge = if b_lon > a[0] { 180.0 } else { -180.0 };
+    let f = (edge - a[0]) / (b_lon - a[0]);
+    let lat_x = a[1] + f * (b[1] - a[1]);
+
+    let mut out = Vec::with_capacity(n + 5);
+    out.push([-edge, lat_x]);
+    out.extend((1..=n).map(|j| ring[(k + j) % n]));
+    out.push([edge, lat_x]);
+    out.push([edge, pole_lat]);
+    out.push([-edge, pole_lat]);
+    close(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn satellite_over(lat: f64, lon: f64, alt_km: f64) -> [f64; 3] {
+        earth::geodetic_to_ecef(lat, lon, alt_km)
+    }
+
+    fn great_circle_deg(a: [f64; 2], b: [f64; 2]) -> f64 {
+        let (la1, la2) = (a[1].to_radians(), b[1].to_radians());
+        let dlon = (b[0] - a[0]).to_radians();
+        (la1.sin() * la2.sin() + la1.cos() * la2.cos() * dlon.cos())
+            .clamp(-1.0, 1.0)
+            .acos()
+            .to_degrees()
+    }
+
+    /// Twice the signed area of a closed ring in the lon/lat plane; positive
+    /// when it runs counterclockwise.
+    fn signed_area2(ring: &[[f64; 2]]) -> f64 {
+        ring.windows(2)
+            .map(|w| w[0][0] * w[1][1] - w[1][0] * w[0][1])
+            .sum()
+    }
+
+    /// The mask is flattened into the options object; `vertices` defaults.
+    #[test]
+    fn options_deserialise_with_defaults() {
+        let options: FootprintOptions =
+            serde_json::from_str(r#"{"type":"half_cone","half_cone_deg":55.0}"#).unwrap();
+        assert_eq!(options.mask, FootprintMask::HalfCone { half_cone_deg: 55.0 });
+        assert_eq!(options.vertices, DEFAULT_VERTICES);
+        assert!(options.validate().is_ok());
+        let coarse = FootprintOptions { vertices: 3, ..options };
+        assert!(coarse.validate().is_err());
+        let flat = FootprintOptions {
+            mask: FootprintMask::MinElevation { min_elevation_deg: 90.0 },
+            ..options
+        };
+        assert!(flat.validate().is_err());
+    }
+
+    /// Every `min_elevation` vertex sees the satellite at the mask angle.
+    #[test]
+    fn min_elevation_boundary_is_on_the_mask() {
+        let r = satellite_over(40.0, 10.0, 850.0);
+        let ring = boundary(r, FootprintMask::MinElevation { min_elevation_deg: 10.0 }, 36);
+        assert_eq!(ring.len(), 36);
+        for p in &ring {
+            let g = earth::geodetic_to_ecef(p[1], p[0], 0.0);
+            assert!((elevation_deg(g, r) - 10.0).abs() < 1e-6, "vertex {p:?}");
+        }
+    }
+
+    /// Spherical check: an 850 km satellite with a 0° mask covers an Earth
+    /// central angle of acos(R / (R + h)) ≈ 29°.
+    #[test]
+    fn horizon_radius_matches_spherical_estimate() {
+        let r = satellite_over(0.0, 0.0, 850.0);
+        let ring = boundary(r, FootprintMask::MinElevation { min_elevation_deg: 0.0 }, 8);
+        let expected = (EARTH_RADIUS_KM / (EARTH_RADIUS_KM + 850.0)).acos().to_degrees();
+        for p in &ring {
+            let lambda = great_circle_deg([0.0, 0.0], *p);
+            assert!((lambda - expected).abs() < 0.3, "{lambda} vs {expected}");
+        }
+    }
+
+    /// A narrow sensor cone is much smaller than the horizon; a cone wider
+    /// than the limb is clipped to it.
+    #[test]
+    fn half_cone_is_clipped_to_horizon() {
+        let r = satellite_over(0.0, 0.0, 550.0);
+        let narrow = boundary(r, FootprintMask::HalfCone { half_cone_deg: 10.0 }, 8);
+        let wide = boundary(r, FootprintMask::HalfCone { half_cone_deg: 89.0 }, 8);
+        let horizon = boundary(r, FootprintMask::MinElevation { min_elevation_deg: 0.0 }, 8);
+        let radius = |ring: &[[f64; 2]]| great_circle_deg([0.0, 0.0], ring[0]);
+        assert!(radius(&narrow) < 1.0, "narrow {}", radius(&narrow));
+        assert!((radius(&wide) - radius(&horizon)).abs() < 1e-6);
+    }
+
+    /// A footprint straddling the antimeridian becomes two closed polygons,
+    /// all within [-180, 180].
+    #[test]
+    fn antimeridian_split() {
+        let r = satellite_over(0.0, 179.0, 850.0);
+        let ring = boundary(r, FootprintMask::MinElevation { min_elevation_deg: 0.0 }, 72);
+        let polys = to_multipolygon(&ring, 0.0);
+        assert_eq!(polys.len(), 2);
+        for poly in &polys {
+            let exterior = &poly[0];
+            assert_eq!(exterior.first(), exterior.last());
+            assert!(exterior.iter().all(|p| (-180.0..=180.0).contains(&p[0])));
+        }
+        let east = polys.iter().any(|p| p[0].iter().any(|v| v[0] > 170.0));
+        let west = polys.iter().any(|p| p[0].iter().any(|v| v[0] < -170.0));
+        assert!(east && west);
+    }
+
+    /// A footprint away from the antimeridian stays a single polygon.
+    #[test]
+    fn plain_footprint_is_one_polygon() {
+        let r = satellite_over(45.0, 0.0, 850.0);
+        let ring = boundary(r, FootprintMask::MinElevation { min_elevation_deg: 5.0 }, 72);
+        let polys = to_multipolygon(&ring, 45.0);
+        assert_eq!(polys.len(), 1);
+        assert_eq!(polys[0].len(), 1);
+        assert_eq!(polys[0][0].len(), 73);
+    }
+
+    /// A footprint over the pole is closed through the pole and spans all
+    /// longitudes.
+    #[test]
+    fn pole_is_enclosed() {
+        let r = satellite_over(85.0, 30.0, 850.0);
+        let ring = boundary(r, FootprintMask::MinElevation { min_elevation_deg: 0.0 }, 72);
+        let polys = to_multipolygon(&ring, 85.0);
+        assert_eq!(polys.len(), 1);
+        let poly = &polys[0][0];
+        assert_eq!(poly.first(), poly.last());
+        assert!(poly.contains(&[180.0, 90.0]) || poly.contains(&[-180.0, 90.0]));
+        assert!(poly.iter().any(|p| p[0] == 180.0) && poly.iter().any(|p| p[0] == -180.0));
+        assert!(poly.iter().all(|p| (-180.0..=180.0).contains(&p[0])));
+    }
+
+    /// A serialised sample is a valid GeoJSON `MultiPolygon`: polygons hold
+    /// rings of `[lon, lat]` positions, and every exterior ring is closed and
+    /// counterclockwise, including split and polar footprints.
+    #[test]
+    fn footprints_are_rfc7946_multipolygons() {
+        let mask = FootprintMask::MinElevation { min_elevation_deg: 0.0 };
+        for (lat, lon) in [(45.0, 0.0), (0.0, 179.0), (85.0, 30.0), (-85.0, -120.0)] {
+            let r = satellite_over(lat, lon, 850.0);
+            let sample = FootprintSample {
+                t: 0,
+                lat_deg: lat,
+                lon_deg: lon,
+                alt_km: 850.0,
+                footprint: to_multipolygon(&boundary(r, mask, 72), lat),
+            };
+            let json = serde_json::to_value(&sample).unwrap();
+            let polygons = json["footprint"].as_array().unwrap();
+            assert!(!polygons.is_empty());
+            for polygon in polygons {
+                let rings = polygon.as_array().unwrap();
+                assert_eq!(rings.len(), 1, "one exterior ring, no holes");
+                let positions = rings[0].as_array().unwrap();
+                assert!(positions.len() >= 4, "a linear ring has at least four positions");
+                assert!(positions.iter().all(|p| p.as_array().unwrap().len() == 2));
+            }
+            for polygon in &sample.footprint {
+                let exterior = &polygon[0];
+                assert_eq!(exterior.first(), exterior.last());
+                let area = signed_area2(exterior);
+                assert!(area > 0.0, "({lat}, {lon}): clockwise exterior ring, area {area}");
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Small 3-vector helpers and local orbital reference frames.
+//!
+//! # RIC frame
+//!
+//! The radial / in-track / cross-track frame is built from a reference state
+//! `(r, v)`:
+//! - **R** = r̂ (points away from the Earth's centre).
+//! - **C** = (r × v)̂ (orbit normal).
+//! - **I** = C × R (completes the right-handed triad; equals the velocity
+//!   direction for a circular orbit).
+//!
+//! The frame rotates with the reference orbit at angular velocity
+//! `ω = (r × v) / |r|²` about **C**, so [`relative_ric`] removes the
+//! `ω × δr` transport term to give the velocity an observer riding the
+//! reference (LVLH) would measure.
+//!
+//! All inputs and outputs are TEME vectors in km or km/s, matching
+//! [`crate::propagate`].
+
+/// Dot product of two 3-vectors.
+pub fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Cross product `a × b`.
+pub fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Euclidean norm of a 3-vector.
+pub fn norm(v: [f64; 3]) -> f64 {
+    dot(v, v).sqrt()
+}
+
+/// Element-wise `a + b`.
+pub fn add(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+/// Element-wise `a - b`.
+pub fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+/// Scale a 3-vector by `k`.
+pub fn scale(v: [f64; 3], k: f64) -> [f64; 3] {
+    [v[0] * k, v[1] * k, v[2] * k]
+}
+
+/// Unit vector in the direction of `v`.  Returns the zero vector for `|v| = 0`.
+pub fn unit(v: [f64; 3]) -> [f64; 3] {
+    let n = norm(v);
+    if n == 0.0 {
+        [0.0; 3]
+    } else {
+        scale(v, 1.0 / n)
+    }
+}
+
+/// Rows of the TEME → RIC rotation matrix for the reference state `(r, v)`.
+///
+/// Row 0 is R̂, row 1 is Î, row 2 is Ĉ.
+pub fn ric_basis(r_km: [f64; 3], v_km_s: [f64; 3]) -> [[f64; 3]; 3] {
+    let r_hat = unit(r_km);
+    let c_hat = unit(cross(r_km, v_km_s));
+    let i_hat = cross(c_hat, r_hat);
+    [r_hat, i_hat, c_hat]
+}
+
+/// Express a TEME vector `d` in the RIC frame of the reference state `(r, v)`.
+///
+/// Typically `d` is a position difference `r_other - r_ref`, giving the
+/// radial, in-track and cross-track separation in km.
+pub fn to_ric(r_km: [f64; 3], v_km_s: [f64; 3], d: [f64; 3]) -> [f64; 3] {
+    let basis = ric_basis(r_km, v_km_s);
+    [dot(basis[0], d), dot(basis[1], d), dot(basis[2], d)]
+}
+
+/// Position and velocity of `other` relative to `reference`, in the rotating
+/// RIC frame of `reference`.
+///
+/// Shared by every feature that compares two states: TLE residuals,
+/// maneuver detection and relative-motion jobs.
+pub fn relative_ric(
+    r_ref_km: [f64; 3],
+    v_ref_km_s: [f64; 3],
+    r_other_km: [f64; 3],
+    v_other_km_s: [f64; 3],
+) -> ([f64; 3], [f64; 3]) {
+    let basis = ric_basis(r_ref_km, v_ref_km_s);
+    let rotate = |x: [f64; 3]| [dot(basis[0], x), dot(basis[1], x), dot(basis[2], x)];
+    let dr = rotate(sub(r_other_km, r_ref_km));
+    let dv = rotate(sub(v_other_km_s, v_ref_km_s));
+    // ω expressed in RIC is [0, 0, |h| / |r|²].
+    let omega = norm(cross(r_ref_km, v_ref_km_s)) / dot(r_ref_km, r_ref_km);
+    let transport = [-omega * dr[1], omega * dr[0], 0.0];
+    (dr, sub(dv, transport))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// For a circular equatorial orbit the RIC axes line up with r, v and +Z.
+    #[test]
+    fn ric_basis_circular_equatorial() {
+        let basis = ric_basis([7000.0, 0.0, 0.0], [0.0, 7.5, 0.0]);
+        let expected = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        for (axis, want) in basis.iter().zip(expected) {
+            assert!(norm(sub(*axis, want)) < 1e-12, "axis {axis:?} != {want:?}");
+        }
+    }
+
+    /// A companion on the same circular orbit, slightly ahead, sits on the
+    /// in-track axis and is at rest in the rotating frame.
+    #[test]
+    fn relative_ric_same_circular_orbit() {
+        let (r, mu) = (7_000.0_f64, 398_600.441_8_f64);
+        let speed = (mu / r).sqrt();
+        let state = |theta: f64| {
+            let (s, c) = theta.sin_cos();
+            ([r * c, r * s, 0.0], [-speed * s, speed * c, 0.0])
+        };
+        let (r0, v0) = state(0.0);
+        let (r1, v1) = state(1e-3);
+        let (dr, dv) = relative_ric(r0, v0, r1, v1);
+        assert!(dr[0].abs() < 0.01 && dr[2].abs() < 1e-12, "dr {dr:?}");
+        assert!((dr[1] - r * 1e-3).abs() < 0.01, "dr {dr:?}");
+        assert!(norm(dv) < 1e-5, "dv {dv:?}");
+    }
+
+    /// Rotation into RIC must preserve vector length.
+    #[test]
+    fn to_ric_preserves_norm() {
+        let r = [4000.0, -3000.0, 4500.0];
+        let v = [5.1, 4.2, -1.7];
+        let d = [1.5, -2.0, 0.25];
+        let ric = to_ric(r, v, d);
+        assert!((norm(ric) - norm(d)).abs() < 1e-12);
+    }
+}
@@ -10,12 +10,57 @@
 //! hash      = "sha256:" + hex(SHA-256(canonical.as_bytes()))
 //! ```
 //!
+//! A backward window (negative `duration_s`, sampled back from `start_at`)
+//! uses the same string with the negative duration, e.g. `…:-2700:10:…`;
+//! forward durations are always positive, so the two never collide.
+//!
+//! A window centred on `center_at` (see [`compute_centered`]) has its own
+//! prefix, since `t = 0` sits mid-window rather than at the first sample:
+//!
+//! ```text
+//! canonical = "center:{tle_id}:{center_at_rfc3339}:{duration_s}:{step_s}:{frame}:{include_velocity}"
+//! ```
+//!
+//! Millisecond windows (result format 2, see [`compute_ms`]) use a separate
+//! canonical string, so every existing key is unchanged:
+//!
+//! ```text
+//! canonical = "v2:{tle_id}:{start_at_rfc3339}:{duration_s}:{step_ms}:{start_offset_ms}:{frame}:{include_velocity}"
+//! ```
+//!
+//! Explicit epoch lists (see [`compute_epochs`]) are reduced to a digest of
+//! the list first, so the key stays short however long the list is:
+//!
+//! ```text
+//! epochs    = join("\n", [rfc3339_micros_z(e) for e in epochs])   // e.g. 2026-04-25T12:00:00.250000Z
+//! canonical = "epochs:{tle_id}:{hex(SHA-256(epochs))}:{frame}:{include_velocity}"
+//! ```
+//!
+//! Catalog snapshots (see [`compute_snapshot`]) cover every tracked object,
+//! so the key names only the epoch and whether geodetic coordinates are
+//! included:
+//!
+//! ```text
+//! canonical = "snapshot:{epoch_rfc3339}:{geodetic}"
+//! ```
+//!
+//! A window or epoch list propagated with a non-default [`PropagationModel`]
+//! (anything but SGP4 with WGS84 and the improved mode) appends the model to
+//! its canonical string, so default-model keys are unchanged.  The analytic
+//! propagators ignore `opsmode`, so it is left out of their suffix:
+//!
+//! ```text
+//! canonical = "{canonical}:{gravity}:{opsmode}"      // SGP4, e.g. "…:eci_j2000:true:wgs72:afspc"
+//! canonical = "{canonical}:{propagator}:{gravity}"   // e.g. "…:eci_j2000:true:two_body:wgs84"
+//! ```
+//!
 //! The canonical string must stay identical between this implementation and the
 //! Python implementation in `apps/api` (M4).  A committed golden-vector test
 //! covers both sides; any change requires updating both implementations in the
 //! same PR.
 
-use chrono::{DateTime, Utc};
+use crate::propagate::PropagationModel;
+use chrono::{DateTime, SecondsFormat, Utc};
 use sha2::{Digest, Sha256};
 
 /// Compute the deterministic cache key for a propagation window.
@@ -27,6 +72,7 @@ use sha2::{Digest, Sha256};
 /// * `step_s` — sampling interval in seconds.
 /// * `frame` — coordinate frame label (e.g. `"eci_j2000"`).
 /// * `include_velocity` — whether velocity vectors are included.
+/// * `model` — propagator, gravity constants and SGP4 operation mode.
 ///
 /// # Returns
 /// A `"sha256:{hex}"` string.
@@ -37,6 +83,7 @@ pub fn compute(
     step_s: i64,
     frame: &str,
     include_velocity: bool,
+    model: PropagationModel,
 ) -> String {
     // RFC 3339 with UTC offset +00:00 (not Z) so Python's datetime.isoformat()
     // produces the same string: `2026-04-25T12:00:00+00:00`.
@@ -44,6 +91,96 @@ pub fn compute(
     let canonical = format!(
         "{tle_id}:{start_str}:{duration_s}:{step_s}:{frame}:{include_velocity}"
     );
+    digest(&with_model(canonical, model))
+}
+
+/// Compute the cache key for a window centred on `center_at`, spanning
+/// `duration_s / 2` either side.
+pub fn compute_centered(
+    tle_id: i64,
+    center_at: &DateTime<Utc>,
+    duration_s: i64,
+    step_s: i64,
+    frame: &str,
+    include_velocity: bool,
+    model: PropagationModel,
+) -> String {
+    let center_str = center_at.to_rfc3339();
+    let canonical = format!(
+        "center:{tle_id}:{center_str}:{duration_s}:{step_s}:{frame}:{include_velocity}"
+    );
+    digest(&with_model(canonical, model))
+}
+
+/// Compute the cache key for a window sampled in milliseconds (result
+/// format 2).
+///
+/// The `v2:` prefix keeps these keys disjoint from [`compute`] even when the
+/// sample times coincide, since the two formats report `t` in different
+/// units.
+///
+/// # Arguments
+/// As [`compute`], with `step_ms` (sampling interval) and `start_offset_ms`
+/// (first sample after `start_at`) in milliseconds.
+#[allow(clippy::too_many_arguments)]
+pub fn compute_ms(
+    tle_id: i64,
+    start_at: &DateTime<Utc>,
+    duration_s: i64,
+    step_ms: i64,
+    start_offset_ms: i64,
+    frame: &str,
+    include_velocity: bool,
+    model: PropagationModel,
+) -> String {
+    let start_str = start_at.to_rfc3339();
+    let canonical = format!(
+        "v2:{tle_id}:{start_str}:{duration_s}:{step_ms}:{start_offset_ms}:{frame}:{include_velocity}"
+    );
+    digest(&with_model(canonical, model))
+}
+
+/// Compute the cache key for a `"propagate_epochs"` job.
+///
+/// Epochs are hashed in the order given, at microsecond resolution (the
+/// finest Python's `datetime` carries), so reordering the list changes the
+/// key.
+pub fn compute_epochs(
+    tle_id: i64,
+    epochs: &[DateTime<Utc>],
+    frame: &str,
+    include_velocity: bool,
+    model: PropagationModel,
+) -> String {
+    let list = epochs
+        .iter()
+        .map(|e| e.to_rfc3339_opts(SecondsFormat::Micros, true))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let list_digest = hex::encode(Sha256::digest(list.as_bytes()));
+    let canonical = format!("epochs:{tle_id}:{list_digest}:{frame}:{include_velocity}");
+    digest(&with_model(canonical, model))
+}
+
+/// Compute the cache key for a `"snapshot"` job at `epoch`.
+///
+/// The key does not depend on which TLEs were current; the hot-cache TTL
+/// (one snapshot cadence) bounds how stale a cached snapshot can be.
+pub fn compute_snapshot(epoch: &DateTime<Utc>, geodetic: bool) -> String {
+    let canonical = format!("snapshot:{}:{geodetic}", epoch.to_rfc3339());
+    digest(&canonical)
+}
+
+/// Append a non-default `model` to `canonical`.
+fn with_model(canonical: String, model: PropagationModel) -> String {
+    if model == PropagationModel::default() {
+        canonical
+    } else {
+        format!("{canonical}:{}", model.label())
+    }
+}
+
+fn digest(canonical: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(canonical.as_bytes());
     let digest = hasher.finalize();
@@ -53,8 +190,15 @@ pub fn compute(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::propagate::{Gravity, OpsMode, PropagatorKind};
     use chrono::TimeZone;
 
+    const D: PropagationModel = PropagationModel {
+        propagator: PropagatorKind::Sgp4,
+        gravity: Gravity::Wgs84,
+        opsmode: OpsMode::Improved,
+    };
+
     /// Golden vector: the expected SHA-256 was independently computed in Python
     /// using `hashlib.sha256(canonical.encode()).hexdigest()` with the same
     /// canonical format.  Both this test and
@@ -63,7 +207,7 @@ mod tests {
     #[test]
     fn golden_hash() {
         let start_at = Utc.with_ymd_and_hms(2026, 4, 25, 12, 0, 0).unwrap();
-        let result = compute(1234, &start_at, 3600, 10, "eci_j2000", true);
+        let result = compute(1234, &start_at, 3600, 10, "eci_j2000", true, D);
         assert_eq!(
             result,
             "sha256:9cdb94ff65c6df3af52c16c1eae7365a558545dd3aedd37bc1567332c07f1f14"
@@ -74,8 +218,8 @@ mod tests {
     #[test]
     fn different_include_velocity_produces_different_hash() {
         let start_at = Utc.with_ymd_and_hms(2026, 4, 25, 12, 0, 0).unwrap();
-        let h_true = compute(1234, &start_at, 3600, 10, "eci_j2000", true);
-        let h_false = compute(1234, &start_at, 3600, 10, "eci_j2000", false);
+        let h_true = compute(1234, &start_at, 3600, 10, "eci_j2000", true, D);
+        let h_false = compute(1234, &start_at, 3600, 10, "eci_j2000", false, D);
         assert_ne!(h_true, h_false);
     }
 
@@ -83,8 +227,8 @@ mod tests {
     #[test]
     fn different_tle_ids_produce_different_hashes() {
         let start_at = Utc.with_ymd_and_hms(2026, 4, 25, 12, 0, 0).unwrap();
-        let h1 = compute(1, &start_at, 3600, 10, "eci_j2000", true);
-        let h2 = compute(2, &start_at, 3600, 10, "eci_j2000", true);
+        let h1 = compute(1, &start_at, 3600, 10, "eci_j2000", true, D);
+        let h2 = compute(2, &start_at, 3600, 10, "eci_j2000", true, D);
         assert_ne!(h1, h2);
     }
 
@@ -92,16 +236,129 @@ mod tests {
     #[test]
     fn output_has_sha256_prefix() {
         let start_at = Utc.with_ymd_and_hms(2026, 4, 25, 12, 0, 0).unwrap();
-        let h = compute(1, &start_at, 3600, 10, "eci_j2000", true);
+        let h = compute(1, &start_at, 3600, 10, "eci_j2000", true, D);
         assert!(h.starts_with("sha256:"), "hash must start with 'sha256:'");
     }
 
+    /// Golden vectors for backward and centred windows, pinned alongside
+    /// the others in `apps/api/tests/test_hash.py`.
+    #[test]
+    fn golden_hash_backward_and_centered() {
+        let at = Utc.with_ymd_and_hms(2026, 4, 25, 12, 0, 0).unwrap();
+        assert_eq!(
+            compute(1234, &at, -2700, 10, "eci_j2000", true, D),
+            "sha256:c4074d732682f5f1a51cdaee7feb90600ffe8c7e74d3baa6b5b0e639b675ec97"
+        );
+        assert_eq!(
+            compute_centered(1234, &at, 5400, 10, "eci_j2000", true, D),
+            "sha256:719ee6245ee5afb03adb4372bf89d22b8197b8b76358f94760c5b866b68285c8"
+        );
+        assert_ne!(
+            compute(1234, &at, 5400, 10, "eci_j2000", true, D),
+            compute_centered(1234, &at, 5400, 10, "eci_j2000", true, D)
+        );
+    }
+
+    /// Golden vector for millisecond windows, pinned alongside the v1 vector
+    /// in `apps/api/tests/test_hash.py`.
+    #[test]
+    fn golden_hash_ms() {
+        let start_at = Utc.with_ymd_and_hms(2026, 4, 25, 12, 0, 0).unwrap();
+        let result = compute_ms(1234, &start_at, 3600, 100, 0, "eci_j2000", true, D);
+        assert_eq!(
+            result,
+            "sha256:d2cc6d9f9ead68eb096f0e3bf26ada27dc1e1b339002196c001054023d081546"
+        );
+    }
+
+    /// A millisecond window never shares a key with the whole-second window
+    /// at the same sample times.
+    #[test]
+    fn ms_and_s_windows_do_not_collide() {
+        let start_at = Utc.with_ymd_and_hms(2026, 4, 25, 12, 0, 0).unwrap();
+        let h_s = compute(1234, &start_at, 3600, 10, "eci_j2000", true, D);
+        let h_ms = compute_ms(1234, &start_at, 3600, 10_000, 0, "eci_j2000", true, D);
+        assert_ne!(h_s, h_ms);
+        let h_shifted = compute_ms(1234, &start_at, 3600, 10_000, 500, "eci_j2000", true, D);
+        assert_ne!(h_ms, h_shifted);
+    }
+
+    /// Golden vector for epoch lists, pinned alongside the others in
+    /// `apps/api/tests/test_hash.py`.
+    #[test]
+    fn golden_hash_epochs() {
+        let epochs = [
+            Utc.with_ymd_and_hms(2026, 4, 25, 12, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 4, 25, 12, 0, 7).unwrap()
+                + chrono::Duration::milliseconds(250),
+        ];
+        let result = compute_epochs(1234, &epochs, "eci_j2000", true, D);
+        assert_eq!(result, "sha256:245b4bbb5286027033a1098c9377dc8e912bdd1b1e390574bbb01cbfdd0161fb");
+        let reversed = [epochs[1], epochs[0]];
+        assert_ne!(compute_epochs(1234, &reversed, "eci_j2000", true, D), result);
+    }
+
+    /// Golden vector for catalog snapshots, pinned alongside the others in
+    /// `apps/api/tests/test_hash.py`.
+    #[test]
+    fn golden_hash_snapshot() {
+        let epoch = Utc.with_ymd_and_hms(2026, 4, 25, 12, 0, 0).unwrap();
+        let result = compute_snapshot(&epoch, true);
+        assert_eq!(result, "sha256:8d5741d0d3aed1c1a92feb8badae2ec0909b2427e7011826991571ef8681e842");
+        assert_ne!(compute_snapshot(&epoch, false), result);
+    }
+
+    /// Golden vectors for non-default propagation models, pinned alongside the
+    /// others in `apps/api/tests/test_hash.py`; the explicit default model
+    /// keeps the v1 key.
+    #[test]
+    fn golden_hash_model() {
+        let start_at = Utc.with_ymd_and_hms(2026, 4, 25, 12, 0, 0).unwrap();
+        let wgs72_afspc = PropagationModel {
+            propagator: PropagatorKind::Sgp4,
+            gravity: Gravity::Wgs72,
+            opsmode: OpsMode::Afspc,
+        };
+        assert_eq!(
+            compute(1234, &start_at, 3600, 10, "eci_j2000", true, wgs72_afspc),
+            "sha256:e7ee84215dff2eb96ba92bac733d2f3b45cb84bfc00789af2367115e427ef452"
+        );
+        let wgs72_improved = PropagationModel {
+            opsmode: OpsMode::Improved,
+            ..wgs72_afspc
+        };
+        assert_eq!(
+            compute(1234, &start_at, 3600, 10, "eci_j2000", true, wgs72_improved),
+            "sha256:2ac7c1e4c6179a1ab60be2ea2c66c14c08300a009ccd10b1a033b5ecb72c04dd"
+        );
+        assert_eq!(
+            compute(1234, &start_at, 3600, 10, "eci_j2000", true, PropagationModel::default()),
+            "sha256:9cdb94ff65c6df3af52c16c1eae7365a558545dd3aedd37bc1567332c07f1f14"
+        );
+        let two_body = PropagationModel {
+            propagator: PropagatorKind::TwoBody,
+            ..PropagationModel::default()
+        };
+        assert_eq!(
+            compute(1234, &start_at, 3600, 10, "eci_j2000", true, two_body),
+            "sha256:98750c8a02852a0c9ddba557190adde1ddb31b8ce375f2a9dc8dc0d88fd86efb"
+        );
+        let two_body_afspc = PropagationModel {
+            opsmode: OpsMode::Afspc,
+            ..two_body
+        };
+        assert_eq!(
+            compute(1234, &start_at, 3600, 10, "eci_j2000", true, two_body_afspc),
+            compute(1234, &start_at, 3600, 10, "eci_j2000", true, two_body)
+        );
+    }
+
     /// Hash function must be deterministic: same inputs → same output.
     #[test]
     fn deterministic() {
         let start_at = Utc.with_ymd_and_hms(2026, 4, 25, 12, 0, 0).unwrap();
-        let h1 = compute(42, &start_at, 1800, 30, "eci_j2000", false);
-        let h2 = compute(42, &start_at, 1800, 30, "eci_j2000", false);
+        let h1 = compute(42, &start_at, 1800, 30, "eci_j2000", false, D);
+        let h2 = compute(42, &start_at, 1800, 30, "eci_j2000", false, D);
         assert_eq!(h1, h2);
     }
 }
@@ -0,0 +1,267 @@
+//! Satellite-to-satellite line-of-sight windows.
+//!
+//! # Visibility test
+//!
+//! The link is clear when every point of the segment between the two
+//! satellites stays above `EARTH_RADIUS_KM + grazing_alt_km`.  The grazing
+//! altitude is an atmospheric margin (refraction and absorption near the
+//! limb); the Earth is taken as a sphere of equatorial radius, which is
+//! conservative by up to the 21 km polar flattening.
+//!
+//! # Sampling
+//!
+//! Both objects are sampled at the offsets of
+//! [`crate::propagate::propagate_window`].  Visibility edges between two
+//! samples are then bisected to [`crate::passes::TIME_TOLERANCE_S`], reusing
+//! each object's [`Propagator`].
+
+use crate::elements::EARTH_RADIUS_KM;
+use crate::frames;
+use crate::passes::{self, bisect};
+use crate::propagate::{self, Propagator};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Line-of-sight geometry at time offset `t`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LinkSample {
+    /// Seconds since `start_at`.  Always a multiple of `step_s`.
+    pub t: i64,
+    pub range_km: f64,
+    /// Positive when the satellites are separating.
+    pub range_rate_km_s: f64,
+    /// Altitude of the lowest point of the line of sight above the spherical
+    /// Earth, in km.
+    pub min_alt_km: f64,
+    /// `min_alt_km >= grazing_alt_km`.
+    pub visible: bool,
+}
+
+/// A maximal interval of clear line of sight.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VisibilityInterval {
+    /// Start of the interval (the window start if already visible).
+    pub start: DateTime<Utc>,
+    /// End of the interval (the window end if still visible).
+    pub end: DateTime<Utc>,
+    /// Range extremes over the samples inside the interval.
+    pub min_range_km: f64,
+    pub max_range_km: f64,
+}
+
+/// One end of a link: a TLE name and its parsed elements.
+pub struct LinkEnd<'a> {
+    pub name: &'a str,
+    pub propagator: &'a Propagator,
+}
+
+/// Sample the link geometry and extract visibility intervals.
+///
+/// # Errors
+/// Returns an error if SGP4 diverges for either TLE.
+pub fn visibility_window(
+    a: &LinkEnd<'_>,
+    b: &LinkEnd<'_>,
+    start_at: &DateTime<Utc>,
+    duration_s: i64,
+    step_s: i64,
+    grazing_alt_km: f64,
+) -> Result<(Vec<LinkSample>, Vec<VisibilityInterval>)> {
+    let offsets = propagate::window_offsets_ms(0, duration_s * 1_000, step_s * 1_000);
+    let window = |end: &LinkEnd<'_>| {
+        propagate::sample_offsets_ms(end.propagator, start_at, &offsets, true)
+            .with_context(|| format!("propagation of '{}' failed", end.name))
+    };
+    let (samples_a, samples_b) = (window(a)?, window(b)?);
+
+    let samples: Vec<LinkSample> = samples_a
+        .iter()
+        .zip(&samples_b)
+        .map(|(sa, sb)| {
+            let (range_km, range_rate_km_s, min_alt_km) = geometry(
+                sa.r_km,
+                sa.v_km_s.unwrap_or_default(),
+                sb.r_km,
+                sb.v_km_s.unwrap_or_default(),
+            );
+            LinkSample {
+                t: sa.t / 1_000,
+                range_km,
+                range_rate_km_s,
+                min_alt_km,
+                visible: min_alt_km >= grazing_alt_km,
+            }
+        })
+        .collect();
+
+    let position = |end: &LinkEnd<'_>, t: f64| {
+        let p = end.propagator;
+        propagate::state_after_si(&p.elements, &p.constants, start_at, t).map(|(r, _)| r)
+    };
+    let clearance = |t: f64| -> Result<f64> {
+        Ok(min_altitude_km(position(a, t)?, position(b, t)?) - grazing_alt_km)
+    };
+
+    let mut intervals = Vec::new();
+    let mut open: Option<(f64, f64, f64)> = None; // (start_t, min_range, max_range)
+    for (i, s) in samples.iter().enumerate() {
+        let t = s.t as f64;
+        match (&mut open, s.visible) {
+            (None, true) => {
+                let start_t = if i == 0 {
+                    0.0
+                } else {
+                    bisect(&clearance, samples[i - 1].t as f64, t)?
+                };
+                open = Some((start_t, s.range_km, s.range_km));
+            }
+            (Some((_, lo, hi)), true) => {
+                *lo = lo.min(s.range_km);
+                *hi = hi.max(s.range_km);
+            }
+            (Some(_), false) => {
+                let (start_t, lo, hi) = open.take().unwrap_or_default();
+                let end_t = bisect(&clearance, samples[i - 1].t as f64, t)?;
+                intervals.push(interval(start_at, start_t, end_t, lo, hi));
+            }
+            (None, false) => {}
+        }
+    }
+    if let Some((start_t, lo, hi)) = open {
+        intervals.push(interval(start_at, start_t, duration_s as f64, lo, hi));
+    }
+    Ok((samples, intervals))
+}
+
+/// Range, range rate and lowest line-of-sight altitude between two TEME
+/// states.
+pub fn geometry(
+    r_a: [f64; 3],
+    v_a: [f64; 3],
+    r_b: [f64; 3],
+    v_b: [f64; 3],
+) -> (f64, f64, f64) {
+    let d = frames::sub(r_b, r_a);
+    let range = frames::norm(d);
+    let range_rate = if range > 0.0 {
+        frames::dot(d, frames::sub(v_b, v_a)) / range
+    } else {
+        0.0
+    };
+    (range, range_rate, min_altitude_km(r_a, r_b))
+}
+
+/// Altitude above the spherical Earth of the point on segment `a`–`b`
+/// closest to the Earth's centre.
+pub fn min_altitude_km(r_a: [f64; 3], r_b: [f64; 3]) -> f64 {
+    let d = frames::sub(r_b, r_a);
+    let dd = frames::dot(d, d);
+    let s = if dd > 0.0 {
+        (-frames::dot(r_a, d) / dd).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    frames::norm(frames::add(r_a, frames::scale(d, s))) - EARTH_RADIUS_KM
+}
+
+fn interval(start_at: &DateTime<Utc>, from: f64, to: f64, lo: f64, hi: f64) -> VisibilityInterval {
+    VisibilityInterval {
+        start: passes::offset(start_at, from),
+        end: passes::offset(start_at, to),
+        min_range_km: lo,
+        max_range_km: hi,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::propagate::PropagationModel;
+    use chrono::TimeZone;
+
+    const ISS_LINE1: &str =
+        "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9999";
+    const ISS_LINE2: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000013";
+    // Same plane as the ISS, 180° ahead in mean anomaly.
+    const OPPOSITE_LINE1: &str =
+        "1 99999U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9994";
+    const OPPOSITE_LINE2: &str =
+        "2 99999  51.6400 127.0000 0004000  20.0000 160.0000 15.50000000000018";
+    // Higher, in a different plane: drifts in and out of view.
+    const OTHER_LINE1: &str =
+        "1 99998U 98067A   26116.50000000  .00000000  00000-0  00000-0 0  9995";
+    const OTHER_LINE2: &str =
+        "2 99998  97.6000  20.0000 0010000  90.0000   0.0000 14.20000000000018";
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap()
+    }
+
+    fn parse(line1: &str, line2: &str) -> Propagator {
+        Propagator::new("SAT", line1, line2, PropagationModel::default()).unwrap()
+    }
+
+    fn end(propagator: &Propagator) -> LinkEnd<'_> {
+        LinkEnd { name: "SAT", propagator }
+    }
+
+    /// A line through the Earth's centre has min altitude −R; a line well
+    /// above it keeps the endpoint altitude.
+    #[test]
+    fn min_altitude_geometry() {
+        let r = EARTH_RADIUS_KM + 500.0;
+        let through = min_altitude_km([r, 0.0, 0.0], [-r, 0.0, 0.0]);
+        assert!((through + EARTH_RADIUS_KM).abs() < 1e-9);
+        let near = min_altitude_km([r, 0.0, 0.0], [r, 100.0, 0.0]);
+        assert!((near - 500.0).abs() < 1e-9);
+        // Chord whose midpoint dips to r·cos(45°).
+        let chord = min_altitude_km([r, 0.0, 0.0], [0.0, r, 0.0]);
+        assert!((chord - (r / 2f64.sqrt() - EARTH_RADIUS_KM)).abs() < 1e-9);
+    }
+
+    /// Two satellites on opposite sides of the same orbit never see each
+    /// other; a satellite always sees itself.
+    #[test]
+    fn opposite_satellites_are_never_visible() {
+        let (iss, opposite) = (parse(ISS_LINE1, ISS_LINE2), parse(OPPOSITE_LINE1, OPPOSITE_LINE2));
+        let (samples, intervals) = visibility_window(
+            &end(&iss),
+            &end(&opposite),
+            &start(),
+            3_600,
+            60,
+            100.0,
+        )
+        .unwrap();
+        assert_eq!(samples.len(), 61);
+        assert!(intervals.is_empty());
+        assert!(samples.iter().all(|s| !s.visible));
+    }
+
+    /// Interval edges sit on the grazing altitude and alternate with
+    /// blocked samples.
+    #[test]
+    fn interval_edges_touch_grazing_altitude() {
+        let (a, b) = (parse(ISS_LINE1, ISS_LINE2), parse(OTHER_LINE1, OTHER_LINE2));
+        let (samples, intervals) =
+            visibility_window(&end(&a), &end(&b), &start(), 86_400, 60, 100.0).unwrap();
+        assert!(samples.iter().any(|s| s.visible) && samples.iter().any(|s| !s.visible));
+        assert!(!intervals.is_empty());
+        for iv in &intervals {
+            assert!(iv.start < iv.end && iv.min_range_km <= iv.max_range_km);
+            for edge in [iv.start, iv.end] {
+                if edge == start() || edge == passes::offset(&start(), 86_400.0) {
+                    continue;
+                }
+                let (ra, _) = propagate::state_at(&a.elements, &a.constants, &edge).unwrap();
+                let (rb, _) = propagate::state_at(&b.elements, &b.constants, &edge).unwrap();
+                assert!((min_altitude_km(ra, rb) - 100.0).abs() < 1.0, "edge {edge}");
+            }
+        }
+        for pair in intervals.windows(2) {
+            assert!(pair[0].end < pair[1].start);
+        }
+    }
+}
@@ -3,9 +3,129 @@
 //! The message schema mirrors the JSON produced by `apps/api` and described in
 //! `docs/architecture.md § Job queue (Redis Streams)`.
 
+use crate::beta::{BetaSample, FullSunPeriod};
+use crate::contact::{ContactPlan, GroundStation};
+use crate::doppler::DopplerPass;
+use crate::earth::Observer;
+use crate::footprint::{FootprintOptions, FootprintSample};
+use crate::intersat::{LinkSample, VisibilityInterval};
+use crate::lifetime::{Atmosphere, LifetimeEstimate};
+use crate::maneuver::ManeuverEvent;
+use crate::montecarlo::{ElementCovariance, MonteCarloRun};
+use crate::propagate::PropagationModel;
+use crate::regime::OrbitClassification;
+use crate::relative::RelativeSample;
+use crate::residuals::ResidualReport;
+use crate::snapshot::Snapshot;
+use crate::timescale::TimeScale;
+use crate::visual::{Twilight, VisiblePass};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// The fields common to every `stream:propagate` message.
+///
+/// The worker reads this envelope first, so a message with an unknown `kind`
+/// can still be answered on `result:{job_id}`, then parses the whole message
+/// as a [`Job`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct JobHeader {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+    /// Job kind, e.g. `"propagate_window"` or `"tle_residuals"`.
+    pub kind: String,
+}
+
+/// The `job_id` of a message alone, read when its [`JobHeader`] is malformed
+/// (`kind` missing or not a string) so the error can still be answered on
+/// `result:{job_id}`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JobId {
+    pub job_id: String,
+}
+
+/// A `stream:propagate` message, tagged by its `kind` field, with the
+/// kind-specific payload.
+///
+/// A new job type is a new variant here plus its handler in
+/// [`crate::worker`]; the worker's dispatch is an exhaustive match, so the
+/// two cannot drift apart.
+// One `Job` lives per message and is moved straight into its handler, so
+// boxing the large payloads would buy nothing.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Job {
+    PropagateWindow(JobPayload),
+    PropagateEpochs(PropagateEpochsPayload),
+    PropagateBatch(PropagateBatchPayload),
+    Snapshot(SnapshotPayload),
+    TleResiduals(TleResidualsPayload),
+    DetectManeuvers(DetectManeuversPayload),
+    ManeuverEvents(ManeuverEventsPayload),
+    Lifetime(LifetimePayload),
+    ClassifyOrbit(ClassifyOrbitPayload),
+    BetaAngle(BetaAnglePayload),
+    Doppler(DopplerPayload),
+    Footprint(FootprintPayload),
+    VisiblePasses(VisiblePassesPayload),
+    ContactPlan(ContactPlanPayload),
+    RelativeMotion(RelativeMotionPayload),
+    MonteCarlo(MonteCarloPayload),
+    IntersatVisibility(IntersatVisibilityPayload),
+}
+
+impl Job {
+    /// Every `kind` the worker accepts, in variant order.  The
+    /// `job_kinds_match_tags` test checks this against the enum's serde tags.
+    pub const KINDS: [&'static str; 17] = [
+        "propagate_window",
+        "propagate_epochs",
+        "propagate_batch",
+        "snapshot",
+        "tle_residuals",
+        "detect_maneuvers",
+        "maneuver_events",
+        "lifetime",
+        "classify_orbit",
+        "beta_angle",
+        "doppler",
+        "footprint",
+        "visible_passes",
+        "contact_plan",
+        "relative_motion",
+        "monte_carlo",
+        "intersat_visibility",
+    ];
+
+    /// Whether `kind` names a job this worker can run.
+    pub fn is_supported(kind: &str) -> bool {
+        Self::KINDS.contains(&kind)
+    }
+
+    /// The wire `kind` of this job.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::PropagateWindow(_) => "propagate_window",
+            Self::PropagateEpochs(_) => "propagate_epochs",
+            Self::PropagateBatch(_) => "propagate_batch",
+            Self::Snapshot(_) => "snapshot",
+            Self::TleResiduals(_) => "tle_residuals",
+            Self::DetectManeuvers(_) => "detect_maneuvers",
+            Self::ManeuverEvents(_) => "maneuver_events",
+            Self::Lifetime(_) => "lifetime",
+            Self::ClassifyOrbit(_) => "classify_orbit",
+            Self::BetaAngle(_) => "beta_angle",
+            Self::Doppler(_) => "doppler",
+            Self::Footprint(_) => "footprint",
+            Self::VisiblePasses(_) => "visible_passes",
+            Self::ContactPlan(_) => "contact_plan",
+            Self::RelativeMotion(_) => "relative_motion",
+            Self::MonteCarlo(_) => "monte_carlo",
+            Self::IntersatVisibility(_) => "intersat_visibility",
+        }
+    }
+}
+
 /// The TLE lines attached to every propagation job so the worker can propagate
 /// without a round-trip back to Postgres.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -18,7 +138,8 @@ pub struct TleData {
     pub line2: String,
 }
 
-/// Payload stored in the `payload` field of every `stream:propagate` message.
+/// Payload of a `"propagate_window"` job ([`Job::PropagateWindow`]), the
+/// `payload` field of its `stream:propagate` message.
 ///
 /// Field names and types must stay in sync with the Python `JobMessage` dataclass
 /// in `apps/api` (M4).  If you rename a field here, rename it there too and
@@ -29,44 +150,1037 @@ pub struct JobPayload {
     /// `result:{job_id}`.
     pub job_id: String,
 
-    /// Always `"propagate_window"` for v1.
-    pub kind: String,
+    /// Primary-key of the `tles` row.  Written directly into `propagated_windows`
+    /// so the worker never needs to re-query Postgres for the TLE.
+    pub tle_id: i64,
+
+    /// The TLE data to propagate.
+    pub tle: TleData,
+
+    /// TLE epoch (parsed from Line 1).  Carried so the worker can verify it
+    /// matches the TLE it parses without hitting the database.
+    pub epoch: DateTime<Utc>,
+
+    /// Propagation window start time (UTC): the sample at `t = 0`.  Exactly
+    /// one of `start_at` and `center_at` must be set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_at: Option<DateTime<Utc>>,
+
+    /// Centre of a symmetric window (UTC), sampled at `t = 0` with
+    /// `duration_s / 2` either side.  Whole-second steps only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub center_at: Option<DateTime<Utc>>,
+
+    /// Window duration in seconds.  Must be in `[60, 86400]`; with `start_at`
+    /// it may instead be in `[-86400, -60]` to sample backward from
+    /// `start_at`, and its magnitude may reach `MAX_CHUNKED_DURATION_S`, in
+    /// which case the window is computed and delivered in day-long chunks.
+    pub duration_s: i64,
+
+    /// Sampling interval in seconds.  Must be in `[1, 600]`.  May be omitted
+    /// when `step_ms` is set.
+    #[serde(default)]
+    pub step_s: i64,
+
+    /// Sampling interval in milliseconds, for sub-second steps.  Supersedes
+    /// `step_s` and selects result format 2.  Must be in
+    /// `[MIN_STEP_MS, MAX_STEP_S * 1000]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_ms: Option<i64>,
+
+    /// Offset of the first sample after `start_at` in milliseconds, in
+    /// `[0, 1000)`.  Selects result format 2; whole seconds belong in
+    /// `start_at`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_offset_ms: Option<i64>,
+
+    /// Coordinate frame.  Always `"eci_j2000"` for v1.
+    ///
+    /// Note: the `sgp4` crate outputs **TEME** (True Equator, Mean Equinox),
+    /// which is the native SGP4 output frame.  For v1's 1 km accuracy
+    /// requirement the TEME/J2000 difference is negligible; positions are
+    /// labelled `eci_j2000` throughout the API for consistency with the
+    /// architecture doc.
+    pub frame: String,
+
+    /// Whether to include velocity vectors in the response.
+    pub include_velocity: bool,
+
+    /// `propagator` (`"sgp4"`, `"two_body"` or `"j2_secular"`), `gravity`
+    /// (`"wgs72"` / `"wgs84"`) and SGP4 `opsmode` (`"afspc"` / `"improved"`),
+    /// flat in the payload; SGP4 with WGS84 and the improved mode when
+    /// omitted.
+    #[serde(flatten)]
+    pub model: PropagationModel,
+
+    /// Pre-computed cache hash (`sha256:…`).  The worker trusts this value; it
+    /// does not recompute the hash.  Millisecond windows use
+    /// [`crate::hash::compute_ms`]; a non-default `model` is part of the key.
+    pub hash: String,
+}
+
+/// Result format with `t` in whole seconds.
+pub const RESULT_FORMAT_SECONDS: u8 = 1;
+
+/// Result format with `t` in milliseconds (`step_ms` / `start_offset_ms`
+/// windows).
+pub const RESULT_FORMAT_MILLIS: u8 = 2;
+
+fn default_result_format() -> u8 {
+    RESULT_FORMAT_SECONDS
+}
+
+impl JobPayload {
+    /// Shortest window: one minute.
+    pub const MIN_DURATION_S: i64 = 60;
+    /// Longest window: one day.
+    pub const MAX_DURATION_S: i64 = 86_400;
+    /// Coarsest step: ten minutes.
+    pub const MAX_STEP_S: i64 = 600;
+    /// Finest millisecond step: 100 Hz.
+    pub const MIN_STEP_MS: i64 = 10;
+    /// Most samples in one window (a day at 1 s).
+    pub const MAX_SAMPLES: i64 = 86_401;
+    /// Longest chunked window: 31 days.
+    pub const MAX_CHUNKED_DURATION_S: i64 = 31 * 86_400;
+    /// Most samples in one chunked window (a week at 1 s).
+    pub const MAX_CHUNKED_SAMPLES: i64 = 604_801;
+
+    /// Whether the window is longer than a day and so is computed, stored
+    /// and published in chunks.
+    pub fn is_chunked(&self) -> bool {
+        self.duration_s.abs() > Self::MAX_DURATION_S
+    }
+
+    /// [`RESULT_FORMAT_MILLIS`] when `step_ms` or `start_offset_ms` is set,
+    /// else [`RESULT_FORMAT_SECONDS`].
+    pub fn result_format(&self) -> u8 {
+        if self.step_ms.is_some() || self.start_offset_ms.is_some() {
+            RESULT_FORMAT_MILLIS
+        } else {
+            RESULT_FORMAT_SECONDS
+        }
+    }
+
+    /// The instant of `t = 0` and whether the window is centred on it.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason unless exactly one of `start_at` and
+    /// `center_at` is set.
+    pub fn anchor(&self) -> Result<(DateTime<Utc>, bool), String> {
+        match (self.start_at, self.center_at) {
+            (Some(start_at), None) => Ok((start_at, false)),
+            (None, Some(center_at)) => Ok((center_at, true)),
+            _ => Err("exactly one of start_at and center_at must be set".to_owned()),
+        }
+    }
+
+    /// Sampling interval in milliseconds: `step_ms`, else `step_s`.
+    pub fn effective_step_ms(&self) -> i64 {
+        self.step_ms.unwrap_or(self.step_s * 1_000)
+    }
+
+    /// Check the window bounds.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when a bound is violated.
+    pub fn validate(&self) -> Result<(), String> {
+        let (_, centered) = self.anchor()?;
+        if centered && self.result_format() == RESULT_FORMAT_MILLIS {
+            return Err("center_at cannot be combined with step_ms or start_offset_ms".to_owned());
+        }
+        if self.duration_s < 0 && centered {
+            return Err(format!("duration_s must be positive with center_at, got {}", self.duration_s));
+        }
+        let max_duration_s = if centered {
+            Self::MAX_DURATION_S
+        } else {
+            Self::MAX_CHUNKED_DURATION_S
+        };
+        if !(Self::MIN_DURATION_S..=max_duration_s).contains(&self.duration_s.abs()) {
+            return Err(format!(
+                "|duration_s| must be in [{}, {max_duration_s}], got {}",
+                Self::MIN_DURATION_S,
+                self.duration_s
+            ));
+        }
+        match self.step_ms {
+            Some(step_ms) => {
+                if !(Self::MIN_STEP_MS..=Self::MAX_STEP_S * 1_000).contains(&step_ms) {
+                    return Err(format!(
+                        "step_ms must be in [{}, {}], got {step_ms}",
+                        Self::MIN_STEP_MS,
+                        Self::MAX_STEP_S * 1_000
+                    ));
+                }
+            }
+            None => {
+                if !(1..=Self::MAX_STEP_S).contains(&self.step_s) {
+                    return Err(format!(
+                        "step_s must be in [1, {}], got {}",
+                        Self::MAX_STEP_S,
+                        self.step_s
+                    ));
+                }
+            }
+        }
+        if let Some(offset) = self.start_offset_ms {
+            if !(0..1_000).contains(&offset) {
+                return Err(format!("start_offset_ms must be in [0, 1000), got {offset}"));
+            }
+        }
+        let samples = self.duration_s.abs() * 1_000 / self.effective_step_ms() + 1;
+        let max_samples = if self.is_chunked() {
+            Self::MAX_CHUNKED_SAMPLES
+        } else {
+            Self::MAX_SAMPLES
+        };
+        if samples > max_samples {
+            return Err(format!("window has {samples} samples, at most {max_samples} allowed"));
+        }
+        Ok(())
+    }
+
+    /// Check a forward window of at most a day sampled in whole seconds:
+    /// `step_s` in `[min_step_s, MAX_STEP_S]` and `duration_s` in
+    /// `[max(MIN_DURATION_S, step_s), MAX_DURATION_S]`.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when a bound is violated.
+    pub fn validate_day_window(duration_s: i64, step_s: i64, min_step_s: i64) -> Result<(), String> {
+        if !(min_step_s..=Self::MAX_STEP_S).contains(&step_s) {
+            return Err(format!(
+                "step_s must be in [{min_step_s}, {}], got {step_s}",
+                Self::MAX_STEP_S
+            ));
+        }
+        if !(Self::MIN_DURATION_S..=Self::MAX_DURATION_S).contains(&duration_s) || duration_s < step_s {
+            return Err(format!(
+                "duration_s must be in [max({}, step_s), {}], got {duration_s}",
+                Self::MIN_DURATION_S,
+                Self::MAX_DURATION_S
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Payload of a `"tle_residuals"` job: audit the stored TLE history of one
+/// satellite by propagating each element set to the epoch of the next.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TleResidualsPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// Satellite whose `tles` rows are analysed.
+    pub norad_id: i64,
+
+    /// Number of most-recent TLE rows to analyse.  Defaults to
+    /// [`DEFAULT_HISTORY_LIMIT`]; clamped to `[2, MAX_HISTORY_LIMIT]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+
+    /// Fixed residual threshold in km above which a pair is flagged.  When
+    /// absent the threshold is derived from the history itself (see
+    /// [`crate::residuals`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flag_km: Option<f64>,
+}
+
+impl TleResidualsPayload {
+    /// `limit` with the default applied and clamped to `[2, MAX_HISTORY_LIMIT]`.
+    pub fn effective_limit(&self) -> i64 {
+        history_limit(self.limit)
+    }
+}
+
+/// Payload of a `"detect_maneuvers"` job: find discrete element changes in the
+/// stored TLE history of one satellite and persist them to `maneuver_events`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DetectManeuversPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// Satellite whose `tles` rows are analysed.
+    pub norad_id: i64,
+
+    /// Number of most-recent TLE rows to analyse.  Defaults to
+    /// [`DEFAULT_HISTORY_LIMIT`]; clamped to `[2, MAX_HISTORY_LIMIT]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+}
+
+impl DetectManeuversPayload {
+    /// `limit` with the default applied and clamped to `[2, MAX_HISTORY_LIMIT]`.
+    pub fn effective_limit(&self) -> i64 {
+        history_limit(self.limit)
+    }
+}
+
+/// Payload of a `"maneuver_events"` job: read back the maneuvers stored in
+/// `maneuver_events` for one satellite.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManeuverEventsPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// Satellite whose stored maneuvers are returned.
+    pub norad_id: i64,
+}
+
+/// Payload of a `"classify_orbit"` job: label the orbit regime of one TLE.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClassifyOrbitPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// Primary-key of the `tles` row, echoed in the result.
+    pub tle_id: i64,
+
+    /// The TLE to classify.
+    pub tle: TleData,
+}
+
+/// Payload of a `"beta_angle"` job: solar beta angle over a long window.
+///
+/// Sampling follows the `"propagate_window"` contract, with wider bounds so
+/// a window can span days to months at coarse steps.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BetaAnglePayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// Primary-key of the `tles` row, echoed in the result.
+    pub tle_id: i64,
+
+    /// The TLE to propagate.
+    pub tle: TleData,
+
+    /// Window start time (UTC).
+    pub start_at: DateTime<Utc>,
+
+    /// Window duration in seconds.  Must be in `[step_s, MAX_DURATION_S]`.
+    pub duration_s: i64,
+
+    /// Sampling interval in seconds.  Must be in `[MIN_STEP_S, MAX_STEP_S]`.
+    pub step_s: i64,
+}
+
+impl BetaAnglePayload {
+    /// Longest window: one year.
+    pub const MAX_DURATION_S: i64 = 366 * 86_400;
+    /// Finest step: one minute.
+    pub const MIN_STEP_S: i64 = 60;
+    /// Coarsest step: one day.
+    pub const MAX_STEP_S: i64 = 86_400;
+
+    /// Check the window bounds.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when a bound is violated.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(Self::MIN_STEP_S..=Self::MAX_STEP_S).contains(&self.step_s) {
+            return Err(format!(
+                "step_s must be in [{}, {}], got {}",
+                Self::MIN_STEP_S,
+                Self::MAX_STEP_S,
+                self.step_s
+            ));
+        }
+        if !(self.step_s..=Self::MAX_DURATION_S).contains(&self.duration_s) {
+            return Err(format!(
+                "duration_s must be in [step_s, {}], got {}",
+                Self::MAX_DURATION_S,
+                self.duration_s
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Payload of a `"doppler"` job: downlink Doppler offset and rate over every
+/// pass of one satellite above an observer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DopplerPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// Primary-key of the `tles` row, echoed in the result.
+    pub tle_id: i64,
+
+    /// The TLE to propagate.
+    pub tle: TleData,
+
+    /// Ground station receiving the downlink.
+    pub observer: Observer,
+
+    /// Transmitted carrier frequency in Hz.
+    pub frequency_hz: f64,
+
+    /// Search window start time (UTC).
+    pub start_at: DateTime<Utc>,
+
+    /// Search window duration in seconds.  Must be in `[60, MAX_DURATION_S]`.
+    pub duration_s: i64,
+
+    /// Sampling interval within each pass in seconds.  Must be in
+    /// `[1, MAX_STEP_S]`.
+    pub step_s: i64,
+
+    /// Elevation mask in degrees.  Defaults to `0`.
+    #[serde(default)]
+    pub min_elevation_deg: f64,
+}
+
+impl DopplerPayload {
+    /// Longest search window: one week.
+    pub const MAX_DURATION_S: i64 = 7 * 86_400;
+    /// Coarsest in-pass step: one minute.
+    pub const MAX_STEP_S: i64 = 60;
+
+    /// Check the window, frequency and observer bounds.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when a bound is violated.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=Self::MAX_STEP_S).contains(&self.step_s) {
+            return Err(format!(
+                "step_s must be in [1, {}], got {}",
+                Self::MAX_STEP_S,
+                self.step_s
+            ));
+        }
+        if !(JobPayload::MIN_DURATION_S..=Self::MAX_DURATION_S).contains(&self.duration_s) {
+            return Err(format!(
+                "duration_s must be in [{}, {}], got {}",
+                JobPayload::MIN_DURATION_S,
+                Self::MAX_DURATION_S,
+                self.duration_s
+            ));
+        }
+        if !(self.frequency_hz.is_finite() && self.frequency_hz > 0.0) {
+            return Err(format!("frequency_hz must be positive, got {}", self.frequency_hz));
+        }
+        if !(-90.0..=90.0).contains(&self.observer.lat_deg) {
+            return Err(format!(
+                "observer.lat_deg must be in [-90, 90], got {}",
+                self.observer.lat_deg
+            ));
+        }
+        if !(-90.0..90.0).contains(&self.min_elevation_deg) {
+            return Err(format!(
+                "min_elevation_deg must be in [-90, 90), got {}",
+                self.min_elevation_deg
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Payload of a `"footprint"` job: sub-satellite point and ground footprint
+/// polygon at every sample of a window.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FootprintPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// Primary-key of the `tles` row, echoed in the result.
+    pub tle_id: i64,
+
+    /// The TLE to propagate.
+    pub tle: TleData,
+
+    /// Window start time (UTC).
+    pub start_at: DateTime<Utc>,
+
+    /// Window duration in seconds.  Must be in
+    /// `[max(JobPayload::MIN_DURATION_S, step_s), JobPayload::MAX_DURATION_S]`.
+    pub duration_s: i64,
+
+    /// Sampling interval in seconds.  Must be in
+    /// `[MIN_STEP_S, JobPayload::MAX_STEP_S]`.
+    pub step_s: i64,
+
+    /// Footprint edge (elevation mask or sensor cone) and resolution.
+    pub footprint: FootprintOptions,
+}
+
+impl FootprintPayload {
+    /// Finest step.  Each sample carries a whole polygon, so this is coarser
+    /// than `"propagate_window"` allows.
+    pub const MIN_STEP_S: i64 = 10;
+
+    /// Check the window bounds and footprint options.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when a bound is violated.
+    pub fn validate(&self) -> Result<(), String> {
+        JobPayload::validate_day_window(self.duration_s, self.step_s, Self::MIN_STEP_S)?;
+        self.footprint.validate()
+    }
+}
+
+/// One satellite of a multi-satellite job.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SatelliteTle {
+    /// Primary-key of the `tles` row, echoed in the result.
+    pub tle_id: i64,
+    pub tle: TleData,
+}
+
+/// Payload of a `"contact_plan"` job: merged contact schedule for a network
+/// of ground stations and one or more satellites.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContactPlanPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// Ground stations, each with its own elevation mask.  Names must be
+    /// unique.
+    pub stations: Vec<GroundStation>,
+
+    /// Satellites to schedule.
+    pub satellites: Vec<SatelliteTle>,
+
+    /// Plan window start time (UTC).
+    pub start_at: DateTime<Utc>,
+
+    /// Plan window duration in seconds.  Must be in `[60, MAX_DURATION_S]`.
+    pub duration_s: i64,
+}
+
+impl ContactPlanPayload {
+    /// Longest plan window: one week.
+    pub const MAX_DURATION_S: i64 = 7 * 86_400;
+    /// Most stations per plan.
+    pub const MAX_STATIONS: usize = 50;
+    /// Most satellites per plan.
+    pub const MAX_SATELLITES: usize = 100;
+
+    /// Check the window, station and satellite bounds.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when a bound is violated.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(JobPayload::MIN_DURATION_S..=Self::MAX_DURATION_S).contains(&self.duration_s) {
+            return Err(format!(
+                "duration_s must be in [{}, {}], got {}",
+                JobPayload::MIN_DURATION_S,
+                Self::MAX_DURATION_S,
+                self.duration_s
+            ));
+        }
+        if !(1..=Self::MAX_STATIONS).contains(&self.stations.len()) {
+            return Err(format!(
+                "stations must have 1 to {} entries, got {}",
+                Self::MAX_STATIONS,
+                self.stations.len()
+            ));
+        }
+        if !(1..=Self::MAX_SATELLITES).contains(&self.satellites.len()) {
+            return Err(format!(
+                "satellites must have 1 to {} entries, got {}",
+                Self::MAX_SATELLITES,
+                self.satellites.len()
+            ));
+        }
+        let mut names = std::collections::HashSet::new();
+        for station in &self.stations {
+            if !names.insert(station.name.as_str()) {
+                return Err(format!("duplicate station name '{}'", station.name));
+            }
+            if !(-90.0..=90.0).contains(&station.observer.lat_deg) {
+                return Err(format!(
+                    "station '{}': lat_deg must be in [-90, 90], got {}",
+                    station.name, station.observer.lat_deg
+                ));
+            }
+            if !(-90.0..90.0).contains(&station.min_elevation_deg) {
+                return Err(format!(
+                    "station '{}': min_elevation_deg must be in [-90, 90), got {}",
+                    station.name, station.min_elevation_deg
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Payload of an `"intersat_visibility"` job: line-of-sight windows between
+/// two satellites.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IntersatVisibilityPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// First end of the link; range rate is measured from here.
+    pub primary: SatelliteTle,
+
+    /// Second end of the link.
+    pub secondary: SatelliteTle,
+
+    /// Window start time (UTC).
+    pub start_at: DateTime<Utc>,
+
+    /// Window duration in seconds.  Must be in
+    /// `[max(JobPayload::MIN_DURATION_S, step_s), JobPayload::MAX_DURATION_S]`.
+    pub duration_s: i64,
+
+    /// Sampling interval in seconds.  Must be in `[1, JobPayload::MAX_STEP_S]`.
+    pub step_s: i64,
+
+    /// Lowest altitude in km the line of sight may graze.  Defaults to
+    /// [`DEFAULT_GRAZING_ALT_KM`](Self::DEFAULT_GRAZING_ALT_KM).
+    #[serde(default = "default_grazing_alt_km")]
+    pub grazing_alt_km: f64,
+}
+
+fn default_grazing_alt_km() -> f64 {
+    IntersatVisibilityPayload::DEFAULT_GRAZING_ALT_KM
+}
+
+impl IntersatVisibilityPayload {
+    /// Default grazing altitude: the top of the dense atmosphere.
+    pub const DEFAULT_GRAZING_ALT_KM: f64 = 100.0;
+
+    /// Check the window bounds and grazing altitude.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when a bound is violated.
+    pub fn validate(&self) -> Result<(), String> {
+        JobPayload::validate_day_window(self.duration_s, self.step_s, 1)?;
+        if !(0.0..=1_000.0).contains(&self.grazing_alt_km) {
+            return Err(format!(
+                "grazing_alt_km must be in [0, 1000], got {}",
+                self.grazing_alt_km
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Payload of a `"visible_passes"` job: passes an observer can see with the
+/// naked eye.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VisiblePassesPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// Primary-key of the `tles` row, echoed in the result.
+    pub tle_id: i64,
+
+    /// The TLE to propagate.
+    pub tle: TleData,
+
+    /// Where the observer stands.
+    pub observer: Observer,
+
+    /// Search window start time (UTC).
+    pub start_at: DateTime<Utc>,
+
+    /// Search window duration in seconds.  Must be in `[60, MAX_DURATION_S]`.
+    pub duration_s: i64,
+
+    /// Elevation mask in degrees.  Defaults to
+    /// [`DEFAULT_MIN_ELEVATION_DEG`](Self::DEFAULT_MIN_ELEVATION_DEG).
+    #[serde(default = "default_visual_min_elevation_deg")]
+    pub min_elevation_deg: f64,
+
+    /// Required sky darkness.  Defaults to nautical twilight.
+    #[serde(default)]
+    pub twilight: Twilight,
+
+    /// Standard magnitude (1 000 km, 90° phase).  When absent no magnitude
+    /// is estimated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub standard_magnitude: Option<f64>,
+}
+
+fn default_visual_min_elevation_deg() -> f64 {
+    VisiblePassesPayload::DEFAULT_MIN_ELEVATION_DEG
+}
+
+impl VisiblePassesPayload {
+    /// Longest search window: ten days.
+    pub const MAX_DURATION_S: i64 = 10 * 86_400;
+    /// Default mask: low passes are lost in horizon haze.
+    pub const DEFAULT_MIN_ELEVATION_DEG: f64 = 10.0;
+
+    /// Check the window and observer bounds.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when a bound is violated.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(JobPayload::MIN_DURATION_S..=Self::MAX_DURATION_S).contains(&self.duration_s) {
+            return Err(format!(
+                "duration_s must be in [{}, {}], got {}",
+                JobPayload::MIN_DURATION_S,
+                Self::MAX_DURATION_S,
+                self.duration_s
+            ));
+        }
+        if !(-90.0..=90.0).contains(&self.observer.lat_deg) {
+            return Err(format!(
+                "observer.lat_deg must be in [-90, 90], got {}",
+                self.observer.lat_deg
+            ));
+        }
+        if !(0.0..90.0).contains(&self.min_elevation_deg) {
+            return Err(format!(
+                "min_elevation_deg must be in [0, 90), got {}",
+                self.min_elevation_deg
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Payload of a `"relative_motion"` job: one satellite's state relative to
+/// another in the reference's RIC frame.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RelativeMotionPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// The satellite whose RIC frame is used (e.g. the ISS).
+    pub reference: SatelliteTle,
+
+    /// The satellite whose relative motion is returned.
+    pub target: SatelliteTle,
+
+    /// Window start time (UTC).
+    pub start_at: DateTime<Utc>,
+
+    /// Window duration in seconds.  Must be in
+    /// `[max(JobPayload::MIN_DURATION_S, step_s), JobPayload::MAX_DURATION_S]`.
+    pub duration_s: i64,
+
+    /// Sampling interval in seconds.  Must be in `[1, JobPayload::MAX_STEP_S]`.
+    pub step_s: i64,
+}
+
+impl RelativeMotionPayload {
+    /// Check the window bounds.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when a bound is violated.
+    pub fn validate(&self) -> Result<(), String> {
+        JobPayload::validate_day_window(self.duration_s, self.step_s, 1)
+    }
+}
+
+/// Payload of a `"monte_carlo"` job: position uncertainty envelope from
+/// perturbed clones of one TLE.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MonteCarloPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// Primary-key of the `tles` row, echoed in the result.
+    pub tle_id: i64,
+
+    /// The nominal TLE.
+    pub tle: TleData,
+
+    /// Window start time (UTC).
+    pub start_at: DateTime<Utc>,
+
+    /// Window duration in seconds.  Must be in `[60, MAX_DURATION_S]`.
+    pub duration_s: i64,
+
+    /// Sampling interval in seconds.  Must be in `[MIN_STEP_S, MAX_STEP_S]`.
+    pub step_s: i64,
+
+    /// Number of perturbed clones.  Defaults to `DEFAULT_CLONES`.
+    #[serde(default = "default_monte_carlo_clones")]
+    pub clones: usize,
+
+    /// Covariance of `[n, e, i, Ω, ω, M]` in rev/day, unitless and degrees.
+    /// When omitted, [`crate::montecarlo::default_covariance`] is used with
+    /// the TLE age at `start_at`.
+    #[serde(default)]
+    pub covariance: Option<ElementCovariance>,
+
+    /// Random seed.  The same seed reproduces the same result.  Defaults to `0`.
+    #[serde(default)]
+    pub seed: u64,
+}
+
+fn default_monte_carlo_clones() -> usize {
+    MonteCarloPayload::DEFAULT_CLONES
+}
+
+impl MonteCarloPayload {
+    /// Clones propagated when `clones` is omitted.
+    pub const DEFAULT_CLONES: usize = 200;
+    /// Largest accepted `clones`.
+    pub const MAX_CLONES: usize = 2_000;
+    /// Longest window: one week.
+    pub const MAX_DURATION_S: i64 = 7 * 86_400;
+    /// Finest step.
+    pub const MIN_STEP_S: i64 = 10;
+    /// Coarsest step: one hour.
+    pub const MAX_STEP_S: i64 = 3_600;
+    /// Upper bound on `clones × samples`, so one job cannot monopolise the
+    /// worker.
+    pub const MAX_PROPAGATIONS: usize = 2_000_000;
+
+    /// Check the window, clone count and covariance shape.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when a bound is violated.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(Self::MIN_STEP_S..=Self::MAX_STEP_S).contains(&self.step_s) {
+            return Err(format!(
+                "step_s must be in [{}, {}], got {}",
+                Self::MIN_STEP_S,
+                Self::MAX_STEP_S,
+                self.step_s
+            ));
+        }
+        if !(JobPayload::MIN_DURATION_S..=Self::MAX_DURATION_S).contains(&self.duration_s)
+            || self.duration_s < self.step_s
+        {
+            return Err(format!(
+                "duration_s must be in [max({}, step_s), {}], got {}",
+                JobPayload::MIN_DURATION_S,
+                Self::MAX_DURATION_S,
+                self.duration_s
+            ));
+        }
+        if !(2..=Self::MAX_CLONES).contains(&self.clones) {
+            return Err(format!(
+                "clones must be in [2, {}], got {}",
+                Self::MAX_CLONES,
+                self.clones
+            ));
+        }
+        let samples = (self.duration_s / self.step_s + 1) as usize;
+        if self.clones * samples > Self::MAX_PROPAGATIONS {
+            return Err(format!(
+                "clones × samples must be at most {}, got {}",
+                Self::MAX_PROPAGATIONS,
+                self.clones * samples
+            ));
+        }
+        if let Some(cov) = &self.covariance {
+            if cov.iter().flatten().any(|c| !c.is_finite()) {
+                return Err("covariance must be finite".to_owned());
+            }
+            if (0..6).any(|k| cov[k][k] < 0.0) {
+                return Err("covariance diagonal must be non-negative".to_owned());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Payload of a `"lifetime"` job: predicted reentry epoch of one TLE,
+/// persisted to `reentry_predictions`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LifetimePayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// Satellite the prediction is stored under.
+    pub norad_id: i64,
+
+    /// Primary-key of the `tles` row, stored with the prediction.
+    pub tle_id: i64,
+
+    /// The TLE whose epoch state and B* seed the integration.
+    pub tle: TleData,
+
+    /// Density model.  Defaults to Harris-Priester.
+    #[serde(default)]
+    pub atmosphere: Atmosphere,
+
+    /// Days after the TLE epoch to integrate before giving up.  Defaults to
+    /// `DEFAULT_HORIZON_DAYS`; must be in `[1, MAX_HORIZON_DAYS]`.
+    #[serde(default = "default_lifetime_horizon_days")]
+    pub horizon_days: i64,
+}
+
+fn default_lifetime_horizon_days() -> i64 {
+    LifetimePayload::DEFAULT_HORIZON_DAYS
+}
+
+impl LifetimePayload {
+    /// Horizon when `horizon_days` is omitted: one year.
+    pub const DEFAULT_HORIZON_DAYS: i64 = 365;
+    /// Longest horizon: five years.
+    pub const MAX_HORIZON_DAYS: i64 = 5 * 365;
+
+    /// Check the horizon bound.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when a bound is violated.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=Self::MAX_HORIZON_DAYS).contains(&self.horizon_days) {
+            return Err(format!(
+                "horizon_days must be in [1, {}], got {}",
+                Self::MAX_HORIZON_DAYS,
+                self.horizon_days
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Payload of a `"propagate_epochs"` job: the state of one satellite at each
+/// of an arbitrary list of instants (e.g. observation times from a log).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PropagateEpochsPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
 
-    /// Primary-key of the `tles` row.  Written directly into `propagated_windows`
-    /// so the worker never needs to re-query Postgres for the TLE.
+    /// Primary-key of the `tles` row, echoed in the result.
     pub tle_id: i64,
 
-    /// The TLE data to propagate.
+    /// The TLE to propagate.
     pub tle: TleData,
 
-    /// TLE epoch (parsed from Line 1).  Carried so the worker can verify it
-    /// matches the TLE it parses without hitting the database.
-    pub epoch: DateTime<Utc>,
+    /// Instants to propagate to (UTC), in any order; duplicates allowed.
+    /// Results come back in the same order.
+    pub epochs: Vec<DateTime<Utc>>,
+
+    /// Coordinate frame.  Always `"eci_j2000"` (TEME, see [`JobPayload`]).
+    pub frame: String,
+
+    /// Whether to include velocity vectors in the response.
+    pub include_velocity: bool,
+
+    /// Propagator, gravity constants and operation mode (see [`JobPayload`]).
+    #[serde(flatten)]
+    pub model: PropagationModel,
+}
+
+impl PropagateEpochsPayload {
+    /// Most epochs in one job (as many samples as the longest window).
+    pub const MAX_EPOCHS: usize = 86_401;
+
+    /// Check the list size.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when the list is empty or too long.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=Self::MAX_EPOCHS).contains(&self.epochs.len()) {
+            return Err(format!(
+                "epochs must hold between 1 and {} instants, got {}",
+                Self::MAX_EPOCHS,
+                self.epochs.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Payload of a `"propagate_batch"` job: one whole-second window (the same
+/// bounds as a day-long `"propagate_window"`) for many satellites at once.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PropagateBatchPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
 
-    /// Propagation window start time (UTC).
+    /// Satellites to propagate; `tle_id`s must be distinct.
+    pub satellites: Vec<SatelliteTle>,
+
+    /// Window start (UTC).
     pub start_at: DateTime<Utc>,
 
-    /// Window duration in seconds.  Must be in `[60, 86400]`.
+    /// Window length in seconds, negative for backward windows.
     pub duration_s: i64,
 
-    /// Sampling interval in seconds.  Must be in `[1, 600]`.
+    /// Sampling interval in seconds.
     pub step_s: i64,
 
-    /// Coordinate frame.  Always `"eci_j2000"` for v1.
-    ///
-    /// Note: the `sgp4` crate outputs **TEME** (True Equator, Mean Equinox),
-    /// which is the native SGP4 output frame.  For v1's 1 km accuracy
-    /// requirement the TEME/J2000 difference is negligible; positions are
-    /// labelled `eci_j2000` throughout the API for consistency with the
-    /// architecture doc.
+    /// Coordinate frame.  Always `"eci_j2000"` (TEME, see [`JobPayload`]).
     pub frame: String,
 
     /// Whether to include velocity vectors in the response.
     pub include_velocity: bool,
 
-    /// Pre-computed cache hash (`sha256:…`).  The worker trusts this value; it
-    /// does not recompute the hash.
-    pub hash: String,
+    /// Propagator, gravity constants and operation mode (see [`JobPayload`]).
+    #[serde(flatten)]
+    pub model: PropagationModel,
+}
+
+impl PropagateBatchPayload {
+    /// Most satellites in one job.
+    pub const MAX_SATELLITES: usize = 500;
+    /// Upper bound on satellites × samples.
+    pub const MAX_TOTAL_SAMPLES: usize = 2_000_000;
+
+    /// Check the satellite list and window bounds.
+    ///
+    /// # Errors
+    /// Returns a human-readable reason when a bound is violated.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=Self::MAX_SATELLITES).contains(&self.satellites.len()) {
+            return Err(format!(
+                "satellites must hold between 1 and {} entries, got {}",
+                Self::MAX_SATELLITES,
+                self.satellites.len()
+            ));
+        }
+        let mut ids: Vec<i64> = self.satellites.iter().map(|s| s.tle_id).collect();
+        ids.sort_unstable();
+        if let Some(pair) = ids.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(format!("duplicate tle_id {}", pair[0]));
+        }
+        if !(JobPayload::MIN_DURATION_S..=JobPayload::MAX_DURATION_S).contains(&self.duration_s.abs()) {
+            return Err(format!(
+                "|duration_s| must be in [{}, {}], got {}",
+                JobPayload::MIN_DURATION_S,
+                JobPayload::MAX_DURATION_S,
+                self.duration_s
+            ));
+        }
+        if !(1..=JobPayload::MAX_STEP_S).contains(&self.step_s) {
+            return Err(format!(
+                "step_s must be in [1, {}], got {}",
+                JobPayload::MAX_STEP_S,
+                self.step_s
+            ));
+        }
+        let total = (self.duration_s.abs() / self.step_s + 1) as usize * self.satellites.len();
+        if total > Self::MAX_TOTAL_SAMPLES {
+            return Err(format!(
+                "batch has {total} samples in all, at most {} allowed",
+                Self::MAX_TOTAL_SAMPLES
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Payload of a `"snapshot"` job: the latest TLE of every satellite in
+/// `tles`, propagated to one epoch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotPayload {
+    /// UUIDv7 job identifier.
+    pub job_id: String,
+
+    /// Instant to propagate to (UTC).  Defaults to the current snapshot
+    /// epoch (see [`crate::snapshot::aligned_epoch`]).
+    #[serde(default)]
+    pub epoch: Option<DateTime<Utc>>,
+
+    /// Whether to include WGS84 geodetic coordinates.
+    #[serde(default)]
+    pub geodetic: bool,
+}
+
+/// TLE rows analysed by history jobs when `limit` is omitted.
+pub const DEFAULT_HISTORY_LIMIT: i64 = 50;
+
+/// Upper bound on `limit` for history jobs.
+pub const MAX_HISTORY_LIMIT: i64 = 500;
+
+fn history_limit(limit: Option<i64>) -> i64 {
+    limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .clamp(2, MAX_HISTORY_LIMIT)
 }
 
 /// Result published to `result:{job_id}` after a successful propagation.
@@ -78,6 +1192,39 @@ pub struct JobPayload {
 pub enum JobResult {
     /// Successful propagation result.
     Ok(Box<PropagationResult>),
+    /// Successful TLE-history residual analysis.
+    Residuals(Box<TleResidualsResult>),
+    /// Successful maneuver detection or stored-maneuver read.
+    Maneuvers(Box<ManeuverResult>),
+    /// Successful orbit classification.
+    Classification(Box<ClassificationResult>),
+    /// Successful beta-angle series.
+    BetaAngle(Box<BetaAngleResult>),
+    /// Successful Doppler table.
+    Doppler(Box<DopplerResult>),
+    /// Successful footprint series.
+    Footprint(Box<FootprintResult>),
+    /// Successful contact plan.
+    ContactPlan(Box<ContactPlanResult>),
+    /// Successful inter-satellite visibility search.
+    IntersatVisibility(Box<IntersatVisibilityResult>),
+    /// Successful naked-eye pass search.
+    VisiblePasses(Box<VisiblePassesResult>),
+    /// Successful relative-motion series.
+    RelativeMotion(Box<RelativeMotionResult>),
+    /// Successful Monte Carlo uncertainty envelope.
+    MonteCarlo(Box<MonteCarloResult>),
+    /// Successful reentry prediction.
+    Lifetime(Box<LifetimeResult>),
+    /// Successful propagation to an explicit epoch list.
+    Epochs(Box<PropagateEpochsResult>),
+    /// Successful multi-satellite window propagation.
+    Batch(Box<PropagateBatchResult>),
+    /// Successful catalog snapshot.
+    Snapshot(Box<SnapshotResult>),
+    /// One chunk of a window longer than a day, published before the final
+    /// [`JobResult::Ok`].
+    Chunk(Box<WindowChunk>),
     /// Worker-side error.
     Err(PropagationError),
 }
@@ -89,11 +1236,288 @@ pub struct PropagationResult {
     pub tle_id: i64,
     pub hash: String,
     pub frame: String,
+    /// The instant of `t = 0`: the payload's `start_at`, or its `center_at`
+    /// when `centered`.
+    pub start_at: DateTime<Utc>,
+    /// Negative for backward windows.
+    pub duration_s: i64,
+    pub step_s: i64,
+    pub include_velocity: bool,
+    /// Whether the window is centred on `start_at` (`center_at` payloads).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub centered: bool,
+    /// `propagator` / `gravity` / `opsmode` the window was propagated with.
+    #[serde(flatten)]
+    pub model: PropagationModel,
+    /// Scale of `start_at`; `t` offsets are SI time.  Always `"utc"`.
+    #[serde(default)]
+    pub time_scale: TimeScale,
+    /// [`RESULT_FORMAT_SECONDS`] (`t` in seconds) or
+    /// [`RESULT_FORMAT_MILLIS`] (`t` in milliseconds).
+    #[serde(default = "default_result_format")]
+    pub result_format: u8,
+    /// Echoed from the payload for format 2 windows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_ms: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_offset_ms: Option<i64>,
+    /// Number of [`WindowChunk`]s already published for a window longer than
+    /// a day; `samples` is then empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_count: Option<usize>,
+    pub samples: Vec<Sample>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// One chunk of a long window published on `result:{job_id}`.
+///
+/// Chunks arrive in `chunk_index` order, each stored in
+/// `propagated_window_chunks` before it is published; a [`PropagationResult`]
+/// with `chunk_count` set closes the window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowChunk {
+    pub job_id: String,
+    /// The window's cache key.
+    pub hash: String,
+    pub chunk_index: usize,
+    pub chunk_count: usize,
+    /// Samples of this chunk, `t` still relative to the window's `start_at`.
+    pub samples: Vec<Sample>,
+}
+
+/// Successful `"propagate_epochs"` result published on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropagateEpochsResult {
+    pub job_id: String,
+    pub tle_id: i64,
+    /// Cache key from [`crate::hash::compute_epochs`].
+    pub hash: String,
+    pub frame: String,
+    pub include_velocity: bool,
+    /// `propagator` / `gravity` / `opsmode` the epochs were propagated with.
+    #[serde(flatten)]
+    pub model: PropagationModel,
+    /// Scale of every `epoch`.  Always `"utc"`.
+    pub time_scale: TimeScale,
+    /// One state per requested epoch, in request order.
+    pub samples: Vec<EpochSample>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Successful `"propagate_batch"` result published on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropagateBatchResult {
+    pub job_id: String,
+    pub frame: String,
     pub start_at: DateTime<Utc>,
+    /// Negative for backward windows.
     pub duration_s: i64,
     pub step_s: i64,
     pub include_velocity: bool,
+    /// `propagator` / `gravity` / `opsmode` every window was propagated with.
+    #[serde(flatten)]
+    pub model: PropagationModel,
+    /// Scale of `start_at`; `t` offsets are SI seconds.  Always `"utc"`.
+    pub time_scale: TimeScale,
+    /// One window per satellite that propagated, in request order.
+    pub windows: Vec<BatchWindow>,
+    /// Satellites whose TLE failed to parse or diverged.
+    pub failures: Vec<BatchFailure>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// One satellite's window in a [`PropagateBatchResult`], stored in
+/// `propagated_windows` exactly as the equivalent `"propagate_window"` job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchWindow {
+    pub tle_id: i64,
+    /// Cache key from [`crate::hash::compute`].
+    pub hash: String,
     pub samples: Vec<Sample>,
+}
+
+/// A satellite of a batch that could not be propagated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchFailure {
+    pub tle_id: i64,
+    pub detail: String,
+}
+
+/// Successful `"snapshot"` result published on `result:{job_id}` and kept
+/// in the hot cache under `hash`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotResult {
+    pub job_id: String,
+    /// Cache key from [`crate::hash::compute_snapshot`].
+    pub hash: String,
+    pub epoch: DateTime<Utc>,
+    /// Always `"eci_j2000"` (TEME, see [`JobPayload`]).
+    pub frame: String,
+    /// Scale of `epoch`.  Always `"utc"`.
+    pub time_scale: TimeScale,
+    #[serde(flatten)]
+    pub snapshot: Snapshot,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Successful `"tle_residuals"` result published on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TleResidualsResult {
+    pub job_id: String,
+    pub norad_id: i64,
+    /// Always `"ric"`: residuals are radial / in-track / cross-track.
+    pub frame: String,
+    #[serde(flatten)]
+    pub report: ResidualReport,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Successful `"detect_maneuvers"` or `"maneuver_events"` result published
+/// on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManeuverResult {
+    pub job_id: String,
+    pub norad_id: i64,
+    /// Events detected in the analysed history (`"detect_maneuvers"`) or
+    /// stored for the satellite (`"maneuver_events"`), oldest first.
+    pub events: Vec<ManeuverEvent>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Successful `"classify_orbit"` result published on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassificationResult {
+    pub job_id: String,
+    pub tle_id: i64,
+    #[serde(flatten)]
+    pub classification: OrbitClassification,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Successful `"beta_angle"` result published on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BetaAngleResult {
+    pub job_id: String,
+    pub tle_id: i64,
+    pub start_at: DateTime<Utc>,
+    pub duration_s: i64,
+    pub step_s: i64,
+    pub samples: Vec<BetaSample>,
+    /// Runs of consecutive samples with no eclipse.
+    pub full_sun_periods: Vec<FullSunPeriod>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Successful `"doppler"` result published on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DopplerResult {
+    pub job_id: String,
+    pub tle_id: i64,
+    pub observer: Observer,
+    pub frequency_hz: f64,
+    pub min_elevation_deg: f64,
+    /// Passes in the window, oldest first.
+    pub passes: Vec<DopplerPass>,
+    /// The same samples as a CSV table (see [`crate::doppler::to_csv`]).
+    pub csv: String,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Successful `"footprint"` result published on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FootprintResult {
+    pub job_id: String,
+    pub tle_id: i64,
+    pub start_at: DateTime<Utc>,
+    pub duration_s: i64,
+    pub step_s: i64,
+    pub footprint: FootprintOptions,
+    pub samples: Vec<FootprintSample>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Successful `"contact_plan"` result published on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactPlanResult {
+    pub job_id: String,
+    pub start_at: DateTime<Utc>,
+    pub duration_s: i64,
+    #[serde(flatten)]
+    pub plan: ContactPlan,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Successful `"intersat_visibility"` result published on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntersatVisibilityResult {
+    pub job_id: String,
+    pub primary_tle_id: i64,
+    pub secondary_tle_id: i64,
+    pub start_at: DateTime<Utc>,
+    pub duration_s: i64,
+    pub step_s: i64,
+    pub grazing_alt_km: f64,
+    pub samples: Vec<LinkSample>,
+    /// Clear line-of-sight intervals, oldest first.
+    pub intervals: Vec<VisibilityInterval>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Successful `"visible_passes"` result published on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VisiblePassesResult {
+    pub job_id: String,
+    pub tle_id: i64,
+    pub observer: Observer,
+    pub min_elevation_deg: f64,
+    pub twilight: Twilight,
+    /// Passes with a visible portion, oldest first.
+    pub passes: Vec<VisiblePass>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Successful `"relative_motion"` result published on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelativeMotionResult {
+    pub job_id: String,
+    pub reference_tle_id: i64,
+    pub target_tle_id: i64,
+    /// Always `"ric"`: rotating radial / in-track / cross-track frame of the
+    /// reference.
+    pub frame: String,
+    pub start_at: DateTime<Utc>,
+    pub duration_s: i64,
+    pub step_s: i64,
+    pub samples: Vec<RelativeSample>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Successful `"monte_carlo"` result published on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloResult {
+    pub job_id: String,
+    pub tle_id: i64,
+    pub start_at: DateTime<Utc>,
+    pub duration_s: i64,
+    pub step_s: i64,
+    pub seed: u64,
+    /// `"supplied"` or `"age_default"`.
+    pub covariance_source: String,
+    /// The element covariance the clones were drawn from.
+    pub covariance: ElementCovariance,
+    #[serde(flatten)]
+    pub run: MonteCarloRun,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Successful `"lifetime"` result published on `result:{job_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifetimeResult {
+    pub job_id: String,
+    pub norad_id: i64,
+    pub tle_id: i64,
+    #[serde(flatten)]
+    pub estimate: LifetimeEstimate,
     pub computed_at: DateTime<Utc>,
 }
 
@@ -108,7 +1532,8 @@ pub struct PropagationError {
 /// A single sampled position (and optionally velocity) at time offset `t`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Sample {
-    /// Seconds since `start_at`.  Always a multiple of `step_s`.
+    /// Offset since `start_at`: seconds (a multiple of `step_s`) in result
+    /// format 1, milliseconds (`start_offset_ms + k * step_ms`) in format 2.
     pub t: i64,
     /// ECI/TEME position in km: `[x, y, z]`.
     pub r_km: [f64; 3],
@@ -118,6 +1543,18 @@ pub struct Sample {
     pub v_km_s: Option<[f64; 3]>,
 }
 
+/// A single propagated state at an absolute instant.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EpochSample {
+    /// The requested instant (UTC).
+    pub epoch: DateTime<Utc>,
+    /// ECI/TEME position in km: `[x, y, z]`.
+    pub r_km: [f64; 3],
+    /// ECI/TEME velocity in km/s.  `None` when `include_velocity = false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub v_km_s: Option<[f64; 3]>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,7 +1563,6 @@ mod tests {
     fn fixture_payload() -> JobPayload {
         JobPayload {
             job_id: "01900000-0000-7000-8000-000000000001".to_owned(),
-            kind: "propagate_window".to_owned(),
             tle_id: 42,
             tle: TleData {
                 name: "ISS (ZARYA)".to_owned(),
@@ -136,11 +1572,15 @@ mod tests {
                     .to_owned(),
             },
             epoch: Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap(),
-            start_at: Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap(),
+            start_at: Some(Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap()),
+            center_at: None,
             duration_s: 3600,
             step_s: 10,
+            step_ms: None,
+            start_offset_ms: None,
             frame: "eci_j2000".to_owned(),
             include_velocity: true,
+            model: PropagationModel::default(),
             hash: "sha256:abc123".to_owned(),
         }
     }
@@ -157,8 +1597,8 @@ mod tests {
     /// All expected JSON field names must be present.
     #[test]
     fn job_payload_field_names() {
-        let payload = fixture_payload();
-        let json = serde_json::to_value(&payload).expect("to_value");
+        let job = Job::PropagateWindow(fixture_payload());
+        let json = serde_json::to_value(&job).expect("to_value");
         for field in &[
             "job_id",
             "kind",
@@ -170,6 +1610,9 @@ mod tests {
             "step_s",
             "frame",
             "include_velocity",
+            "propagator",
+            "gravity",
+            "opsmode",
             "hash",
         ] {
             assert!(
@@ -187,6 +1630,455 @@ mod tests {
         }
     }
 
+    /// `propagator`, `gravity` and `opsmode` are optional, flat, lowercase
+    /// fields.
+    #[test]
+    fn job_payload_propagation_model() {
+        use crate::propagate::{Gravity, OpsMode, PropagatorKind};
+        let mut json = serde_json::to_value(fixture_payload()).expect("to_value");
+        let obj = json.as_object_mut().unwrap();
+        obj.remove("propagator");
+        obj.remove("gravity");
+        obj.remove("opsmode");
+        let payload: JobPayload = serde_json::from_value(json.clone()).expect("deserialise");
+        assert_eq!(payload.model, PropagationModel::default());
+
+        json["gravity"] = "wgs72".into();
+        json["opsmode"] = "afspc".into();
+        let payload: JobPayload = serde_json::from_value(json.clone()).expect("deserialise");
+        assert_eq!(
+            payload.model,
+            PropagationModel {
+                gravity: Gravity::Wgs72,
+                opsmode: OpsMode::Afspc,
+                ..PropagationModel::default()
+            }
+        );
+        json["propagator"] = "j2_secular".into();
+        let payload: JobPayload = serde_json::from_value(json.clone()).expect("deserialise");
+        assert_eq!(payload.model.propagator, PropagatorKind::J2Secular);
+        json["gravity"] = "egm96".into();
+        assert!(serde_json::from_value::<JobPayload>(json).is_err());
+    }
+
+    /// Whole-second payloads stay on format 1 and omit the millisecond fields.
+    #[test]
+    fn job_payload_result_format() {
+        let payload = fixture_payload();
+        assert_eq!(payload.result_format(), RESULT_FORMAT_SECONDS);
+        assert_eq!(payload.effective_step_ms(), 10_000);
+        assert!(payload.validate().is_ok());
+        let json = serde_json::to_value(&payload).expect("to_value");
+        assert!(json.get("step_ms").is_none() && json.get("start_offset_ms").is_none());
+
+        let fine = JobPayload { step_s: 0, step_ms: Some(100), start_offset_ms: Some(250), ..payload };
+        assert_eq!(fine.result_format(), RESULT_FORMAT_MILLIS);
+        assert_eq!(fine.effective_step_ms(), 100);
+        assert!(fine.validate().is_ok());
+        assert!(JobPayload { step_ms: Some(5), ..fine.clone() }.validate().is_err());
+        assert!(JobPayload { start_offset_ms: Some(1_000), ..fine.clone() }.validate().is_err());
+        // 86 400 s at 100 ms is 864 001 samples.
+        assert!(JobPayload { duration_s: 86_400, ..fine }.validate().is_err());
+    }
+
+    /// Backward and centred windows: exactly one anchor, signed durations
+    /// only from `start_at`.
+    #[test]
+    fn job_payload_backward_and_centered() {
+        let payload = fixture_payload();
+        let start_at = payload.start_at.unwrap();
+        let backward = JobPayload { duration_s: -2_700, ..payload.clone() };
+        assert!(backward.validate().is_ok());
+        assert_eq!(backward.anchor(), Ok((start_at, false)));
+
+        let centered = JobPayload { start_at: None, center_at: Some(start_at), ..payload.clone() };
+        assert!(centered.validate().is_ok());
+        assert_eq!(centered.anchor(), Ok((start_at, true)));
+        assert!(JobPayload { duration_s: -2_700, ..centered.clone() }.validate().is_err());
+        assert!(JobPayload { step_ms: Some(500), ..centered.clone() }.validate().is_err());
+        assert!(JobPayload { start_at: Some(start_at), ..centered }.validate().is_err());
+        assert!(JobPayload { start_at: None, ..payload }.validate().is_err());
+    }
+
+    /// Windows past a day are chunked up to 31 days, within the chunked
+    /// sample budget; centred windows stay within a day.
+    #[test]
+    fn job_payload_chunked_bounds() {
+        let payload = fixture_payload();
+        assert!(!payload.is_chunked());
+        let week = JobPayload { duration_s: 7 * 86_400, step_s: 60, ..payload.clone() };
+        assert!(week.is_chunked());
+        assert!(week.validate().is_ok());
+        assert!(JobPayload { duration_s: -7 * 86_400, ..week.clone() }.validate().is_ok());
+        assert!(JobPayload { step_s: 1, duration_s: 8 * 86_400, ..week.clone() }.validate().is_err());
+        assert!(JobPayload { duration_s: 32 * 86_400, ..week.clone() }.validate().is_err());
+        let centered = JobPayload { start_at: None, center_at: payload.start_at, ..week };
+        assert!(centered.validate().is_err());
+    }
+
+    /// The header and the tagged [`Job`] both read a serialised window job.
+    #[test]
+    fn job_header_reads_window_payload() {
+        let job = Job::PropagateWindow(fixture_payload());
+        let json = serde_json::to_string(&job).expect("serialise");
+        let header: JobHeader = serde_json::from_str(&json).expect("deserialise header");
+        assert_eq!(header.kind, job.kind());
+        assert_eq!(header.job_id, "01900000-0000-7000-8000-000000000001");
+        match serde_json::from_str(&json).expect("deserialise job") {
+            Job::PropagateWindow(payload) => assert_eq!(payload, fixture_payload()),
+            other => panic!("parsed as {}", other.kind()),
+        }
+    }
+
+    /// A message with a `job_id` but no usable `kind` fails the header, yet
+    /// still yields its `job_id`.
+    #[test]
+    fn job_id_survives_malformed_kind() {
+        for json in [r#"{"job_id": "j1"}"#, r#"{"job_id": "j1", "kind": 5}"#] {
+            assert!(serde_json::from_str::<JobHeader>(json).is_err(), "{json}");
+            let JobId { job_id } = serde_json::from_str(json).expect("deserialise job_id");
+            assert_eq!(job_id, "j1");
+        }
+        assert!(serde_json::from_str::<JobId>(r#"{"kind": "snapshot"}"#).is_err());
+    }
+
+    /// `Job::KINDS` lists exactly the serde tags of [`Job`]: each listed kind
+    /// gets past the tag (failing, if at all, on its own fields), and
+    /// anything else is an unknown variant.
+    #[test]
+    fn job_kinds_match_tags() {
+        for kind in Job::KINDS {
+            assert!(Job::is_supported(kind));
+            match serde_json::from_value::<Job>(serde_json::json!({"job_id": "j", "kind": kind})) {
+                Ok(job) => assert_eq!(job.kind(), kind),
+                Err(e) => assert!(!e.to_string().contains("unknown variant"), "{kind}: {e}"),
+            }
+        }
+        assert!(!Job::is_supported("propagate_windows"));
+        let err = serde_json::from_str::<Job>(r#"{"job_id":"j","kind":"propagate_windows"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown variant"), "{err}");
+
+        // serde lists every tag it accepts, so a variant missing from KINDS
+        // fails here.
+        let expected = err.split_once("expected one of").expect("variant list").1;
+        let tags: Vec<&str> = expected.split('`').skip(1).step_by(2).collect();
+        assert_eq!(tags, Job::KINDS);
+    }
+
+    /// Optional residual fields default to `None`; `limit` is clamped.
+    #[test]
+    fn tle_residuals_payload_defaults() {
+        let payload: TleResidualsPayload = serde_json::from_str(
+            r#"{"job_id":"j","kind":"tle_residuals","norad_id":25544}"#,
+        )
+        .expect("deserialise");
+        assert_eq!(payload.limit, None);
+        assert_eq!(payload.flag_km, None);
+        assert_eq!(payload.effective_limit(), DEFAULT_HISTORY_LIMIT);
+
+        let tiny = TleResidualsPayload { limit: Some(1), ..payload.clone() };
+        assert_eq!(tiny.effective_limit(), 2);
+        let huge = TleResidualsPayload { limit: Some(1_000_000), ..payload };
+        assert_eq!(huge.effective_limit(), MAX_HISTORY_LIMIT);
+    }
+
+    /// The payload check a job's handler runs, for kinds that have one.
+    fn validate(job: &Job) -> Result<(), String> {
+        match job {
+            Job::PropagateWindow(p) => p.validate(),
+            Job::PropagateEpochs(p) => p.validate(),
+            Job::PropagateBatch(p) => p.validate(),
+            Job::Lifetime(p) => p.validate(),
+            Job::BetaAngle(p) => p.validate(),
+            Job::Doppler(p) => p.validate(),
+            Job::Footprint(p) => p.validate(),
+            Job::VisiblePasses(p) => p.validate(),
+            Job::ContactPlan(p) => p.validate(),
+            Job::RelativeMotion(p) => p.validate(),
+            Job::MonteCarlo(p) => p.validate(),
+            Job::IntersatVisibility(p) => p.validate(),
+            Job::Snapshot(_)
+            | Job::TleResiduals(_)
+            | Job::DetectManeuvers(_)
+            | Job::ManeuverEvents(_)
+            | Job::ClassifyOrbit(_) => Ok(()),
+        }
+    }
+
+    /// One row of [`payload_bounds`].
+    struct Case {
+        /// A valid message of one kind, without `job_id`.
+        json: serde_json::Value,
+        /// Fields (JSON pointers) the parsed payload fills with these
+        /// defaults; `null` for an omitted `Option`.
+        defaults: Vec<(&'static str, serde_json::Value)>,
+        /// Overrides, each of which alone must fail validation.
+        rejected: Vec<(&'static str, serde_json::Value)>,
+    }
+
+    /// Each row's message parses with its defaults and validates, and is
+    /// rejected with any one of its overrides applied.
+    #[test]
+    fn payload_bounds() {
+        let base = fixture_payload();
+        let satellite = serde_json::json!({"tle_id": base.tle_id, "tle": base.tle});
+        let window = |kind: &str, duration_s: i64, step_s: i64| {
+            serde_json::json!({
+                "kind": kind,
+                "tle_id": base.tle_id,
+                "tle": base.tle,
+                "start_at": base.start_at,
+                "duration_s": duration_s,
+                "step_s": step_s,
+            })
+        };
+        let with = |mut json: serde_json::Value, fields: serde_json::Value| {
+            for (key, value) in fields.as_object().unwrap() {
+                json[key] = value.clone();
+            }
+            json
+        };
+        let cases = vec![
+            Case {
+                json: window("beta_angle", 90 * 86_400, 3_600),
+                defaults: vec![],
+                rejected: vec![
+                    ("/step_s", (BetaAnglePayload::MIN_STEP_S - 1).into()),
+                    ("/duration_s", (BetaAnglePayload::MAX_DURATION_S + 1).into()),
+                    ("/duration_s", 60.into()),
+                ],
+            },
+            Case {
+                json: with(
+                    window("doppler", 86_400, 5),
+                    serde_json::json!({
+                        "observer": {"lat_deg": 51.5, "lon_deg": -0.13},
+                        "frequency_hz": 145.8e6,
+                    }),
+                ),
+                defaults: vec![("/min_elevation_deg", 0.0.into()), ("/observer/alt_m", 0.0.into())],
+                rejected: vec![
+                    ("/step_s", (DopplerPayload::MAX_STEP_S + 1).into()),
+                    ("/duration_s", (JobPayload::MIN_DURATION_S - 1).into()),
+                    ("/duration_s", (DopplerPayload::MAX_DURATION_S + 1).into()),
+                    ("/frequency_hz", (-1.0).into()),
+                    ("/observer/lat_deg", 91.0.into()),
+                ],
+            },
+            Case {
+                json: serde_json::json!({
+                    "kind": "contact_plan",
+                    "stations": [
+                        {"name": "svalbard", "lat_deg": 78.23, "lon_deg": 15.39, "min_elevation_deg": 5.0},
+                        {"name": "mcmurdo", "lat_deg": -77.85, "lon_deg": 166.67, "alt_m": 10.0},
+                    ],
+                    "satellites": [satellite],
+                    "start_at": base.start_at,
+                    "duration_s": 86_400,
+                }),
+                defaults: vec![("/stations/1/min_elevation_deg", 0.0.into())],
+                rejected: vec![
+                    ("/duration_s", (JobPayload::MIN_DURATION_S - 1).into()),
+                    ("/duration_s", (ContactPlanPayload::MAX_DURATION_S + 1).into()),
+                    ("/stations/1/name", "svalbard".into()),
+                    ("/satellites", serde_json::json!([])),
+                ],
+            },
+            Case {
+                json: serde_json::json!({
+                    "kind": "visible_passes",
+                    "tle_id": base.tle_id,
+                    "tle": base.tle,
+                    "observer": {"lat_deg": 51.5, "lon_deg": -0.13},
+                    "start_at": base.start_at,
+                    "duration_s": 86_400,
+                }),
+                defaults: vec![
+                    ("/min_elevation_deg", VisiblePassesPayload::DEFAULT_MIN_ELEVATION_DEG.into()),
+                    ("/twilight", "nautical".into()),
+                    ("/standard_magnitude", serde_json::Value::Null),
+                ],
+                rejected: vec![
+                    ("/duration_s", (JobPayload::MIN_DURATION_S - 1).into()),
+                    ("/duration_s", (VisiblePassesPayload::MAX_DURATION_S + 1).into()),
+                    ("/min_elevation_deg", (-1.0).into()),
+                ],
+            },
+            Case {
+                json: window("monte_carlo", 86_400, 60),
+                defaults: vec![
+                    ("/clones", MonteCarloPayload::DEFAULT_CLONES.into()),
+                    ("/covariance", serde_json::Value::Null),
+                    ("/seed", 0.into()),
+                ],
+                rejected: vec![
+                    ("/step_s", (MonteCarloPayload::MIN_STEP_S - 1).into()),
+                    ("/step_s", (MonteCarloPayload::MAX_STEP_S + 1).into()),
+                    ("/duration_s", (MonteCarloPayload::MAX_DURATION_S + 1).into()),
+                    ("/clones", (MonteCarloPayload::MAX_CLONES + 1).into()),
+                    // 1 400 clones × 1 441 samples > MAX_PROPAGATIONS.
+                    ("/clones", 1_400.into()),
+                    ("/covariance", serde_json::to_value([[-1.0; 6]; 6]).unwrap()),
+                ],
+            },
+            Case {
+                json: serde_json::json!({
+                    "kind": "lifetime",
+                    "norad_id": 25544,
+                    "tle_id": base.tle_id,
+                    "tle": base.tle,
+                }),
+                defaults: vec![
+                    ("/atmosphere", "harris_priester".into()),
+                    ("/horizon_days", LifetimePayload::DEFAULT_HORIZON_DAYS.into()),
+                ],
+                rejected: vec![
+                    ("/horizon_days", 0.into()),
+                    ("/horizon_days", (LifetimePayload::MAX_HORIZON_DAYS + 1).into()),
+                ],
+            },
+            Case {
+                json: serde_json::json!({
+                    "kind": "propagate_batch",
+                    "satellites": [
+                        {"tle_id": 1, "tle": base.tle},
+                        {"tle_id": 2, "tle": base.tle},
+                    ],
+                    "start_at": base.start_at,
+                    "duration_s": -JobPayload::MAX_DURATION_S,
+                    "step_s": 1,
+                    "frame": "eci_j2000",
+                    "include_velocity": true,
+                }),
+                defaults: vec![],
+                rejected: vec![
+                    ("/satellites/1/tle_id", 1.into()),
+                    ("/satellites", serde_json::json!([])),
+                    ("/step_s", 0.into()),
+                    ("/duration_s", (JobPayload::MAX_DURATION_S + 1).into()),
+                    // 100 satellites × 86 401 samples > MAX_TOTAL_SAMPLES.
+                    (
+                        "/satellites",
+                        (0..100)
+                            .map(|tle_id| serde_json::json!({"tle_id": tle_id, "tle": base.tle}))
+                            .collect(),
+                    ),
+                ],
+            },
+            Case {
+                json: with(
+                    window("footprint", JobPayload::MAX_DURATION_S, FootprintPayload::MIN_STEP_S),
+                    serde_json::json!({
+                        "footprint": {"type": "min_elevation", "min_elevation_deg": 10.0},
+                    }),
+                ),
+                defaults: vec![("/footprint/vertices", crate::footprint::DEFAULT_VERTICES.into())],
+                rejected: vec![
+                    ("/step_s", (FootprintPayload::MIN_STEP_S - 1).into()),
+                    ("/step_s", (JobPayload::MAX_STEP_S + 1).into()),
+                    ("/duration_s", (JobPayload::MIN_DURATION_S - 1).into()),
+                    ("/duration_s", (JobPayload::MAX_DURATION_S + 1).into()),
+                    ("/footprint/vertices", 4.into()),
+                    ("/footprint/min_elevation_deg", 90.0.into()),
+                ],
+            },
+            Case {
+                json: serde_json::json!({
+                    "kind": "intersat_visibility",
+                    "primary": satellite,
+                    "secondary": satellite,
+                    "start_at": base.start_at,
+                    "duration_s": JobPayload::MIN_DURATION_S,
+                    "step_s": JobPayload::MIN_DURATION_S,
+                }),
+                defaults: vec![(
+                    "/grazing_alt_km",
+                    IntersatVisibilityPayload::DEFAULT_GRAZING_ALT_KM.into(),
+                )],
+                rejected: vec![
+                    ("/step_s", 0.into()),
+                    ("/step_s", (JobPayload::MIN_DURATION_S + 1).into()),
+                    ("/duration_s", (JobPayload::MIN_DURATION_S - 1).into()),
+                    ("/duration_s", (JobPayload::MAX_DURATION_S + 1).into()),
+                    ("/grazing_alt_km", (-1.0).into()),
+                ],
+            },
+            Case {
+                json: serde_json::json!({
+                    "kind": "relative_motion",
+                    "reference": satellite,
+                    "target": satellite,
+                    "start_at": base.start_at,
+                    "duration_s": JobPayload::MAX_DURATION_S,
+                    "step_s": JobPayload::MAX_STEP_S,
+                }),
+                defaults: vec![],
+                rejected: vec![
+                    ("/step_s", 0.into()),
+                    ("/step_s", (JobPayload::MAX_STEP_S + 1).into()),
+                    ("/duration_s", (JobPayload::MIN_DURATION_S - 1).into()),
+                    ("/duration_s", (JobPayload::MAX_DURATION_S + 1).into()),
+                ],
+            },
+        ];
+        for Case { mut json, defaults, rejected } in cases {
+            json["job_id"] = base.job_id.clone().into();
+            let job: Job = serde_json::from_value(json.clone()).expect("deserialise");
+            let kind = job.kind();
+            assert_eq!(validate(&job), Ok(()), "{kind}");
+            let parsed = serde_json::to_value(&job).expect("to_value");
+            for (pointer, value) in defaults {
+                let field = parsed.pointer(pointer).unwrap_or(&serde_json::Value::Null);
+                assert_eq!(field, &value, "{kind} default {pointer}");
+            }
+            for (pointer, value) in rejected {
+                let mut bad = json.clone();
+                match bad.pointer_mut(pointer) {
+                    Some(field) => *field = value.clone(),
+                    None => {
+                        let (parent, key) = pointer.rsplit_once('/').unwrap();
+                        bad.pointer_mut(parent).unwrap()[key] = value.clone();
+                    }
+                }
+                let job: Job = serde_json::from_value(bad).expect("deserialise");
+                assert!(validate(&job).is_err(), "{kind} with {pointer} = {value}");
+            }
+        }
+    }
+
+    /// Epoch lists must be non-empty and bounded.
+    #[test]
+    fn propagate_epochs_payload_bounds() {
+        let payload: PropagateEpochsPayload = serde_json::from_str(
+            r#"{"job_id":"j","kind":"propagate_epochs","tle_id":1,
+                "tle":{"name":"ISS","line1":"1","line2":"2"},
+                "epochs":["2026-04-26T12:00:07.25Z","2026-04-26T12:00:00Z"],
+                "frame":"eci_j2000","include_velocity":false}"#,
+        )
+        .expect("deserialise");
+        assert_eq!(payload.epochs.len(), 2);
+        assert_eq!(payload.model, PropagationModel::default());
+        assert!(payload.validate().is_ok());
+        let empty = PropagateEpochsPayload { epochs: Vec::new(), ..payload };
+        assert!(empty.validate().is_err());
+    }
+
+    /// Snapshot jobs default to the current epoch without geodetic
+    /// coordinates.
+    #[test]
+    fn snapshot_payload_defaults() {
+        let payload: SnapshotPayload =
+            serde_json::from_str(r#"{"job_id":"j","kind":"snapshot"}"#).expect("deserialise");
+        assert_eq!((payload.epoch, payload.geodetic), (None, false));
+        let pinned: SnapshotPayload = serde_json::from_str(
+            r#"{"job_id":"j","kind":"snapshot","epoch":"2026-04-26T12:00:00Z","geodetic":true}"#,
+        )
+        .expect("deserialise");
+        assert!(pinned.epoch.is_some() && pinned.geodetic);
+    }
+
     /// `v_km_s` must be omitted when `include_velocity = false`.
     #[test]
     fn sample_skips_velocity_when_none() {
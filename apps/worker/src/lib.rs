@@ -3,9 +3,32 @@
 //! Exposes the internal modules so that integration tests and other crates can
 //! import and call worker logic directly without spawning a subprocess.
 
+pub mod analytic;
+pub mod beta;
 pub mod config;
+pub mod contact;
 pub mod db;
+pub mod doppler;
+pub mod earth;
+pub mod elements;
+pub mod eop;
+pub mod footprint;
+pub mod frames;
 pub mod hash;
+pub mod intersat;
 pub mod job;
+pub mod lifetime;
+pub mod maneuver;
+pub mod montecarlo;
+pub mod near_earth;
+pub mod passes;
 pub mod propagate;
+pub mod regime;
+pub mod relative;
+pub mod residuals;
+pub mod snapshot;
+pub mod sun;
+pub mod timescale;
+pub mod tle_cache;
+pub mod visual;
 pub mod worker;
@@ -0,0 +1,525 @@
+//! Orbital lifetime and reentry epoch from numerical integration with drag.
+//!
+//! # Force model
+//!
+//! The SGP4 state at the TLE epoch is integrated by Nyx's adaptive RK89
+//! propagator (steps of at most [`MAX_STEP_S`]) under point-mass gravity, J2
+//! and atmospheric drag:
+//!
+//! ```text
+//! a_drag = −½ ρ(h) · B · |v_rel| v_rel,   v_rel = v − ω⊕ × r
+//! ```
+//!
+//! The ballistic coefficient `B = C_D A / m` comes from the TLE's B* via
+//! `B* = ρ₀ B / 2` with the SGP4 reference density `ρ₀` ([`BSTAR_RHO0`]).
+//! The density `ρ(h)` is one of two static models ([`Atmosphere`]); `h` is
+//! the height above the WGS84 ellipsoid.
+//!
+//! Nyx supplies the central gravity and the integrator; J2 and drag are a
+//! Nyx [`AccelModel`] defined here.  Nyx 1.1.2's own `Drag` is not used: its
+//! exponential density mixes metres and kilometres, and it applies the
+//! atmosphere's velocity rather than the velocity relative to it, which slows
+//! decay by orders of magnitude.  The TEME state is integrated as if it were
+//! EME2000; the difference is far below the density model's uncertainty.
+//!
+//! # Reentry and uncertainty
+//!
+//! Reentry is the first instant the height drops below [`REENTRY_ALT_KM`],
+//! linearly interpolated within the step.  The uncertainty band reruns the
+//! integration with `B` scaled by `1 ± BALLISTIC_UNCERTAINTY`, covering the
+//! combined error of B* and of a static density model.
+
+use crate::earth::{EARTH_ROTATION_RAD_S, WGS84_F};
+use crate::elements::{EARTH_RADIUS_KM, J2, MU_EARTH_KM3_S2, SECONDS_PER_DAY};
+use crate::frames;
+use crate::propagate;
+use crate::sun;
+use crate::timescale;
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use nyx_space::cosmic::{Cosm, Frame, Orbit};
+use nyx_space::dynamics::{AccelModel, OrbitalDynamics};
+use nyx_space::linalg::{Matrix3, Vector3};
+use nyx_space::propagators::{PropOpts, Propagator, RSSCartesianStep, RK89};
+use nyx_space::time::Epoch;
+use nyx_space::NyxError;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+/// Height at which the object is considered to have reentered, in km.
+pub const REENTRY_ALT_KM: f64 = 120.0;
+
+/// Longest integration step in seconds, which also bounds the
+/// interpolation of the reentry crossing.
+pub const MAX_STEP_S: f64 = 60.0;
+
+/// Relative error tolerance of the adaptive step.
+const TOLERANCE: f64 = 1e-10;
+
+/// Relative 1σ-like spread applied to the ballistic coefficient for the
+/// earliest / latest reentry epochs.
+pub const BALLISTIC_UNCERTAINTY: f64 = 0.3;
+
+/// SGP4 reference density `ρ₀` in kg/m² per Earth radius.
+pub const BSTAR_RHO0: f64 = 0.156_966_15;
+
+/// Static atmosphere density model.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Atmosphere {
+    /// Piecewise exponential (Vallado, Table 8-4).  No diurnal variation.
+    Exponential,
+    /// Harris-Priester for mean solar activity (Montenbruck & Gill,
+    /// Table 3.8), with the diurnal bulge lagging the Sun by 30°.
+    #[default]
+    HarrisPriester,
+}
+
+/// Reentry prediction for one TLE.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LifetimeEstimate {
+    pub tle_epoch: DateTime<Utc>,
+    pub atmosphere: Atmosphere,
+    /// `C_D A / m` derived from B*, in m²/kg.
+    pub ballistic_coefficient_m2_kg: f64,
+    /// End of the integration; no reentry before it means `None` below.
+    pub horizon_end: DateTime<Utc>,
+    /// Predicted reentry with the nominal ballistic coefficient.
+    pub reentry_epoch: Option<DateTime<Utc>>,
+    /// Reentry with the ballistic coefficient raised by
+    /// [`BALLISTIC_UNCERTAINTY`].
+    pub earliest_epoch: Option<DateTime<Utc>>,
+    /// Reentry with the ballistic coefficient lowered by
+    /// [`BALLISTIC_UNCERTAINTY`].
+    pub latest_epoch: Option<DateTime<Utc>>,
+}
+
+/// Exponential model rows `(base height km, base density kg/m³, scale height km)`.
+const EXPONENTIAL: [(f64, f64, f64); 28] = [
+    (0.0, 1.225, 7.249),
+    (25.0, 3.899e-2, 6.349),
+    (30.0, 1.774e-2, 6.682),
+    (40.0, 3.972e-3, 7.554),
+    (50.0, 1.057e-3, 8.382),
+    (60.0, 3.206e-4, 7.714),
+    (70.0, 8.770e-5, 6.549),
+    (80.0, 1.905e-5, 5.799),
+    (90.0, 3.396e-6, 5.382),
+    (100.0, 5.297e-7, 5.877),
+    (110.0, 9.661e-8, 7.263),
+    (120.0, 2.438e-8, 9.473),
+    (130.0, 8.484e-9, 12.636),
+    (140.0, 3.845e-9, 16.149),
+    (150.0, 2.070e-9, 22.523),
+    (180.0, 5.464e-10, 29.740),
+    (200.0, 2.789e-10, 37.105),
+    (250.0, 7.248e-11, 45.546),
+    (300.0, 2.418e-11, 53.628),
+    (350.0, 9.518e-12, 53.298),
+    (400.0, 3.725e-12, 58.515),
+    (450.0, 1.585e-12, 60.828),
+    (500.0, 6.967e-13, 63.822),
+    (600.0, 1.454e-13, 71.835),
+    (700.0, 3.614e-14, 88.667),
+    (800.0, 1.170e-14, 124.64),
+    (900.0, 5.245e-15, 181.05),
+    (1000.0, 3.019e-15, 268.00),
+];
+
+/// Harris-Priester rows `(height km, minimum, maximum)`, densities in g/km³.
+const HARRIS_PRIESTER: [(f64, f64, f64); 50] = [
+    (100.0, 497_400.0, 497_400.0),
+    (120.0, 24_900.0, 24_900.0),
+    (130.0, 8_377.0, 8_710.0),
+    (140.0, 3_899.0, 4_059.0),
+    (150.0, 2_122.0, 2_215.0),
+    (160.0, 1_263.0, 1_344.0),
+    (170.0, 800.8, 875.8),
+    (180.0, 528.3, 601.0),
+    (190.0, 361.7, 429.7),
+    (200.0, 255.7, 316.2),
+    (210.0, 183.9, 239.6),
+    (220.0, 134.1, 185.3),
+    (230.0, 99.49, 145.5),
+    (240.0, 74.88, 115.7),
+    (250.0, 57.09, 93.08),
+    (260.0, 44.03, 75.55),
+    (270.0, 34.30, 61.82),
+    (280.0, 26.97, 50.95),
+    (290.0, 21.39, 42.26),
+    (300.0, 17.08, 35.26),
+    (320.0, 10.99, 25.05),
+    (340.0, 7.214, 17.97),
+    (360.0, 4.824, 13.01),
+    (380.0, 3.274, 9.507),
+    (400.0, 2.249, 7.010),
+    (420.0, 1.558, 5.206),
+    (440.0, 1.091, 3.899),
+    (460.0, 0.7701, 2.943),
+    (480.0, 0.5474, 2.236),
+    (500.0, 0.3916, 1.710),
+    (520.0, 0.2819, 1.317),
+    (540.0, 0.2042, 1.021),
+    (560.0, 0.1488, 0.7962),
+    (580.0, 0.1092, 0.6244),
+    (600.0, 0.08070, 0.4924),
+    (620.0, 0.06012, 0.3906),
+    (640.0, 0.04519, 0.3114),
+    (660.0, 0.03430, 0.2496),
+    (680.0, 0.02632, 0.2012),
+    (700.0, 0.02043, 0.1630),
+    (720.0, 0.01607, 0.1328),
+    (740.0, 0.01281, 0.1087),
+    (760.0, 0.01036, 0.08936),
+    (780.0, 0.008496, 0.07273),
+    (800.0, 0.007069, 0.06081),
+    (840.0, 0.004680, 0.04255),
+    (880.0, 0.003200, 0.03012),
+    (920.0, 0.002210, 0.02158),
+    (960.0, 0.001560, 0.01560),
+    (1000.0, 0.001150, 0.01150),
+];
+
+/// Harris-Priester bulge lag behind the Sun, in degrees of right ascension.
+const HP_LAG_DEG: f64 = 30.0;
+
+impl Atmosphere {
+    /// The serialised name, as stored in `reentry_predictions.atmosphere`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Exponential => "exponential",
+            Self::HarrisPriester => "harris_priester",
+        }
+    }
+
+    /// Density in kg/m³ at height `h_km` for position `r_km`.
+    ///
+    /// `sun_dir` is only used by Harris-Priester; `hp_exponent` is its
+    /// cosine power (2 for low-inclination, 6 for polar orbits).
+    pub fn density_kg_m3(
+        self,
+        h_km: f64,
+        r_km: [f64; 3],
+        sun_dir: [f64; 3],
+        hp_exponent: f64,
+    ) -> f64 {
+        match self {
+            Self::Exponential => {
+                let row = EXPONENTIAL
+                    .iter()
+                    .rev()
+                    .find(|(h0, _, _)| *h0 <= h_km)
+                    .unwrap_or(&EXPONENTIAL[0]);
+                row.1 * (-(h_km - row.0) / row.2).exp()
+            }
+            Self::HarrisPriester => harris_priester(h_km, r_km, sun_dir, hp_exponent),
+        }
+    }
+}
+
+fn harris_priester(h_km: f64, r_km: [f64; 3], sun_dir: [f64; 3], n: f64) -> f64 {
+    let last = HARRIS_PRIESTER.len() - 1;
+    if h_km >= HARRIS_PRIESTER[last].0 {
+        return 0.0;
+    }
+    let h = h_km.max(HARRIS_PRIESTER[0].0);
+    let i = HARRIS_PRIESTER
+        .iter()
+        .rposition(|row| row.0 <= h)
+        .unwrap_or(0)
+        .min(last - 1);
+    let (lo, hi) = (HARRIS_PRIESTER[i], HARRIS_PRIESTER[i + 1]);
+    let interp = |a: f64, b: f64| a * ((h - lo.0) / (hi.0 - lo.0) * (b / a).ln()).exp();
+    let rho_min = interp(lo.1, hi.1);
+    let rho_max = interp(lo.2, hi.2);
+
+    let (s, c) = HP_LAG_DEG.to_radians().sin_cos();
+    let apex = [
+        c * sun_dir[0] - s * sun_dir[1],
+        s * sun_dir[0] + c * sun_dir[1],
+        sun_dir[2],
+    ];
+    let cos_psi = frames::dot(frames::unit(r_km), apex);
+    let weight = (0.5 * (1.0 + cos_psi)).max(0.0).powf(0.5 * n);
+    // g/km³ → kg/m³.
+    (rho_min + (rho_max - rho_min) * weight) * 1e-12
+}
+
+/// Harris-Priester cosine exponent for an orbit inclination.
+pub fn hp_exponent(inclination_deg: f64) -> f64 {
+    let i = inclination_deg.min(180.0 - inclination_deg);
+    if i >= 60.0 {
+        6.0
+    } else {
+        2.0
+    }
+}
+
+/// `C_D A / m` in m²/kg from B* in 1/Earth radii.
+pub fn ballistic_coefficient_m2_kg(bstar: f64) -> f64 {
+    2.0 * bstar / BSTAR_RHO0
+}
+
+/// Height above the WGS84 ellipsoid in km, to first order in the flattening.
+fn height_km(r_km: [f64; 3]) -> f64 {
+    let r = frames::norm(r_km);
+    let sin_lat = r_km[2] / r;
+    r - EARTH_RADIUS_KM * (1.0 - WGS84_F * sin_lat * sin_lat)
+}
+
+/// J2 and drag accelerations, held fixed over one integration.
+struct DragModel {
+    atmosphere: Atmosphere,
+    b_m2_kg: f64,
+    hp_exponent: f64,
+    /// TLE epoch, for the Sun direction.
+    epoch: DateTime<Utc>,
+    /// The same instant on Nyx's time scale.
+    start: Epoch,
+}
+
+impl DragModel {
+    fn acceleration(&self, r: [f64; 3], v: [f64; 3], sun_dir: [f64; 3]) -> [f64; 3] {
+        let rn = frames::norm(r);
+        let z2 = 5.0 * r[2] * r[2] / (rn * rn);
+        let k = -1.5 * J2 * MU_EARTH_KM3_S2 * EARTH_RADIUS_KM * EARTH_RADIUS_KM / rn.powi(5);
+        let j2 = [
+            k * r[0] * (1.0 - z2),
+            k * r[1] * (1.0 - z2),
+            k * r[2] * (3.0 - z2),
+        ];
+
+        let v_rel = [
+            v[0] + EARTH_ROTATION_RAD_S * r[1],
+            v[1] - EARTH_ROTATION_RAD_S * r[0],
+            v[2],
+        ];
+        let rho = self
+            .atmosphere
+            .density_kg_m3(height_km(r), r, sun_dir, self.hp_exponent);
+        // ρ [kg/m³] · B [m²/kg] · |v|v [km²/s²] = 1e3 km/s² per unit.
+        let drag = frames::scale(v_rel, -0.5 * rho * self.b_m2_kg * frames::norm(v_rel) * 1e3);
+        frames::add(j2, drag)
+    }
+
+    /// Seconds from the TLE epoch until the height drops below
+    /// [`REENTRY_ALT_KM`], or `None` if it stays above for `horizon_s`.
+    fn time_to_reentry(self, r0: [f64; 3], v0: [f64; 3], horizon_s: f64) -> Result<Option<f64>> {
+        let mut h_prev = height_km(r0);
+        if h_prev < REENTRY_ALT_KM {
+            return Ok(Some(0.0));
+        }
+        let start = self.start;
+        let opts = PropOpts::with_adaptive_step_s(1e-3, MAX_STEP_S, TOLERANCE, RSSCartesianStep {});
+        let prop = Propagator::new::<RK89>(OrbitalDynamics::from_model(Arc::new(self)), opts);
+        let orbit = Orbit::cartesian(r0[0], r0[1], r0[2], v0[0], v0[1], v0[2], start, eme2000());
+        let mut instance = prop.with(orbit);
+        let mut t_prev = 0.0;
+        while t_prev < horizon_s {
+            instance
+                .single_step()
+                .map_err(|e| anyhow!("integration failed {t_prev} s after the TLE epoch: {e}"))?;
+            let state = &instance.state;
+            let r = [state.x, state.y, state.z];
+            if !r
+                .iter()
+                .chain(&[state.vx, state.vy, state.vz])
+                .all(|x| x.is_finite())
+            {
+                bail!("integration diverged {t_prev} s after the TLE epoch");
+            }
+            let t = (state.dt - start).in_seconds();
+            let h = height_km(r);
+            if h < REENTRY_ALT_KM {
+                return Ok(Some(
+                    t_prev + (t - t_prev) * (h_prev - REENTRY_ALT_KM) / (h_prev - h),
+                ));
+            }
+            (t_prev, h_prev) = (t, h);
+        }
+        Ok(None)
+    }
+}
+
+impl fmt::Display for DragModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "J2 and {} drag, B = {} m²/kg",
+            self.atmosphere.as_str(),
+            self.b_m2_kg
+        )
+    }
+}
+
+impl AccelModel for DragModel {
+    fn eom(&self, osc: &Orbit) -> Result<Vector3<f64>, NyxError> {
+        let t = (osc.dt - self.start).in_seconds();
+        let sun_dir = sun::direction(&timescale::utc_after_si(&self.epoch, t));
+        let r = [osc.x, osc.y, osc.z];
+        Ok(Vector3::from(self.acceleration(
+            r,
+            [osc.vx, osc.vy, osc.vz],
+            sun_dir,
+        )))
+    }
+
+    fn dual_eom(&self, _osc: &Orbit) -> Result<(Vector3<f64>, Matrix3<f64>), NyxError> {
+        Err(NyxError::PartialsUndefined)
+    }
+}
+
+/// Nyx's EME2000 frame, whose μ drives the central gravity.  The embedded
+/// DE438 subset is parsed once per process.
+fn eme2000() -> Frame {
+    static COSM: OnceLock<Arc<Cosm>> = OnceLock::new();
+    COSM.get_or_init(Cosm::de438).frame("EME2000")
+}
+
+/// Estimate the reentry epoch and uncertainty band of a parsed TLE.
+///
+/// The nominal, earliest and latest runs are integrated in parallel.
+///
+/// # Errors
+/// Returns an error if the TLE's B* is not positive or the integration
+/// diverges.
+pub fn estimate(
+    propagator: &propagate::Propagator,
+    atmosphere: Atmosphere,
+    horizon_days: f64,
+) -> Result<LifetimeEstimate> {
+    let (elements, constants) = (&propagator.elements, &propagator.constants);
+    if elements.drag_term <= 0.0 {
+        bail!(
+            "B* must be positive to estimate decay, got {}",
+            elements.drag_term
+        );
+    }
+    let epoch = DateTime::from_naive_utc_and_offset(elements.datetime, Utc);
+    let (r0, v0) = propagate::state_at(elements, constants, &epoch)?;
+    let b = ballistic_coefficient_m2_kg(elements.drag_term);
+    let horizon_s = horizon_days * SECONDS_PER_DAY;
+    let hp = hp_exponent(elements.inclination);
+
+    let start = Epoch::from_unix_seconds(epoch.timestamp_micros() as f64 * 1e-6);
+
+    let runs: Vec<Option<DateTime<Utc>>> = [
+        1.0,
+        1.0 + BALLISTIC_UNCERTAINTY,
+        1.0 - BALLISTIC_UNCERTAINTY,
+    ]
+    .par_iter()
+    .map(|scale| {
+        let model = DragModel {
+            atmosphere,
+            b_m2_kg: b * scale,
+            hp_exponent: hp,
+            epoch,
+            start,
+        };
+        let t = model.time_to_reentry(r0, v0, horizon_s)?;
+        Ok(t.map(|t| timescale::utc_after_si(&epoch, t)))
+    })
+    .collect::<Result<_>>()?;
+
+    Ok(LifetimeEstimate {
+        tle_epoch: epoch,
+        atmosphere,
+        ballistic_coefficient_m2_kg: b,
+        horizon_end: timescale::utc_after_si(&epoch, horizon_s),
+        reentry_epoch: runs[0],
+        earliest_epoch: runs[1],
+        latest_epoch: runs[2],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::propagate::PropagationModel;
+    use chrono::Duration;
+
+    // ~170 km circular with a large B*: decays within a day or two.
+    const LOW_LINE1: &str = "1 99990U 26001A   26116.50000000  .00500000  00000-0  50000-3 0  9999";
+    const LOW_LINE2: &str = "2 99990  51.6000 127.0000 0005000  20.0000 340.0000 16.30000000000015";
+    // ~850 km sun-synchronous with a small B*: decades.
+    const HIGH_LINE1: &str =
+        "1 99991U 26001B   26116.50000000  .00000100  00000-0  10000-4 0  9993";
+    const HIGH_LINE2: &str =
+        "2 99991  98.6000  20.0000 0010000  90.0000   0.0000 14.20000000000012";
+    const NO_DRAG_LINE1: &str =
+        "1 99992U 26001C   26116.50000000  .00000000  00000-0  00000-0 0  9998";
+    const NO_DRAG_LINE2: &str =
+        "2 99992  98.6000  20.0000 0010000  90.0000   0.0000 14.20000000000013";
+
+    /// Both models fall with height and agree to within the diurnal range of
+    /// Harris-Priester at 400 km.
+    #[test]
+    fn density_models_are_consistent() {
+        let r = [EARTH_RADIUS_KM + 400.0, 0.0, 0.0];
+        let sun = [1.0, 0.0, 0.0];
+        let night = [-1.0, 0.0, 0.0];
+        let exp400 = Atmosphere::Exponential.density_kg_m3(400.0, r, sun, 2.0);
+        assert!((exp400 - 3.725e-12).abs() < 1e-16);
+        let hp_day = Atmosphere::HarrisPriester.density_kg_m3(400.0, r, sun, 2.0);
+        let hp_night = Atmosphere::HarrisPriester.density_kg_m3(400.0, r, night, 2.0);
+        assert!(hp_night < hp_day);
+        assert!((2.249e-12..=7.010e-12).contains(&hp_day));
+        assert!(
+            (hp_night..=hp_day * 1.5).contains(&exp400),
+            "{hp_night} {exp400} {hp_day}"
+        );
+        for model in [Atmosphere::Exponential, Atmosphere::HarrisPriester] {
+            let mut prev = f64::INFINITY;
+            for h in (120..1_000).step_by(7) {
+                let rho = model.density_kg_m3(f64::from(h), r, sun, 6.0);
+                assert!(rho > 0.0 && rho < prev, "{model:?} at {h} km");
+                prev = rho;
+            }
+        }
+    }
+
+    #[test]
+    fn ballistic_coefficient_from_bstar() {
+        // ISS-class B* of 3e-4 /ER is a few 1e-3 m²/kg.
+        let b = ballistic_coefficient_m2_kg(3e-4);
+        assert!((b - 3.822e-3).abs() < 1e-6, "{b}");
+        assert_eq!(hp_exponent(51.6), 2.0);
+        assert_eq!(hp_exponent(97.0), 6.0);
+        let name = serde_json::to_value(Atmosphere::HarrisPriester).unwrap();
+        assert_eq!(name, Atmosphere::HarrisPriester.as_str());
+    }
+
+    fn parse(line1: &str, line2: &str) -> propagate::Propagator {
+        propagate::Propagator::new("", line1, line2, PropagationModel::default()).unwrap()
+    }
+
+    /// A very low object reenters within the horizon, with the band ordered
+    /// earliest ≤ nominal ≤ latest.
+    #[test]
+    fn low_orbit_reenters() {
+        for atmosphere in [Atmosphere::Exponential, Atmosphere::HarrisPriester] {
+            let est = estimate(&parse(LOW_LINE1, LOW_LINE2), atmosphere, 60.0).unwrap();
+            let nominal = est.reentry_epoch.expect("should reenter within 60 days");
+            let earliest = est.earliest_epoch.unwrap();
+            let latest = est.latest_epoch.unwrap();
+            assert!(
+                earliest < nominal && nominal < latest,
+                "{earliest} {nominal} {latest}"
+            );
+            assert!(est.tle_epoch < earliest);
+        }
+    }
+
+    /// A high orbit survives a short horizon; zero B* is rejected.
+    #[test]
+    fn high_orbit_and_no_drag() {
+        let est = estimate(&parse(HIGH_LINE1, HIGH_LINE2), Atmosphere::default(), 2.0).unwrap();
+        assert_eq!(est.reentry_epoch, None);
+        assert_eq!(est.earliest_epoch, None);
+        assert_eq!(est.horizon_end, est.tle_epoch + Duration::days(2));
+        assert!(estimate(&parse(NO_DRAG_LINE1, NO_DRAG_LINE2), Atmosphere::default(), 5.0).is_err());
+    }
+}
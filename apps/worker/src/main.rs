@@ -3,17 +3,22 @@
 //! Entry point for the Rust SGP4 worker process.
 //!
 //! # Startup sequence
-//! 1. Read configuration from environment variables ([`worker_lib::config::Config`]).
+//! 1. Read configuration from environment variables ([`worker_lib::config::Config`])
+//!    and install the Earth orientation ([`worker_lib::eop`]) and time-scale
+//!    ([`worker_lib::timescale`]) tables and the parsed-TLE cache
+//!    ([`worker_lib::tle_cache`]).
 //! 2. Connect to Postgres via [`sqlx`] connection pool.
-//! 3. Connect to Redis (multiplexed async connection).
+//! 3. Connect to Redis (multiplexed async connections; the consumer loop opens
+//!    its own).
 //! 4. Ensure the `stream:propagate` consumer group exists (idempotent).
-//! 5. Run the consumer loop indefinitely ([`worker_lib::worker::run`]).
+//! 5. Run the consumer loop indefinitely ([`worker_lib::worker::run`]), up to
+//!    `WORKER_CONCURRENCY` jobs at a time.
 
 use anyhow::{Context, Result};
 use redis::AsyncCommands;
 use sqlx::postgres::PgPoolOptions;
 use tracing::info;
-use worker_lib::{config, worker};
+use worker_lib::{config, eop, timescale, tle_cache, worker};
 
 const STREAM_KEY: &str = "stream:propagate";
 const GROUP_NAME: &str = "workers";
@@ -31,9 +36,21 @@ async fn main() -> Result<()> {
     let cfg = config::Config::from_env().context("failed to load configuration")?;
     info!(worker_name = %cfg.worker_name, "starting Galactic Guide propagation worker");
 
+    let eop = eop::Eop::load(cfg.eop_path.as_deref()).context("failed to load EOP table")?;
+    let mut scales =
+        timescale::TimeScales::load(cfg.leap_seconds_path.as_deref(), cfg.ut1_utc_path.as_deref())
+            .context("failed to load time-scale tables")?;
+    if scales.ut1_utc.is_none() {
+        scales.ut1_utc = Some(eop.ut1_utc());
+    }
+    timescale::install(scales);
+    eop::install(eop);
+    tle_cache::install(tle_cache::TleCache::new(cfg.tle_cache_capacity));
+
     // ── Postgres ──────────────────────────────────────────────────────────────
+    // One connection per concurrent job, and never fewer than before.
     let pool = PgPoolOptions::new()
-        .max_connections(5)
+        .max_connections(cfg.concurrency.max(5) as u32)
         .connect(&cfg.database_url)
         .await
         .context("failed to connect to Postgres")?;
@@ -63,6 +80,13 @@ async fn main() -> Result<()> {
     }
 
     // ── Consumer loop ─────────────────────────────────────────────────────────
-    worker::run(pool, redis_conn, &cfg.worker_name).await
+    worker::run(
+        pool,
+        &redis_client,
+        &cfg.worker_name,
+        cfg.concurrency,
+        cfg.snapshot_cadence_s,
+    )
+    .await
 }
 
@@ -0,0 +1,333 @@
+//! Maneuver detection from the stored TLE history.
+//!
+//! Consecutive TLEs are compared on the elements a burn changes and that
+//! natural perturbations leave (nearly) alone over a few days:
+//!
+//! - **Semi-major axis** — compared against the drag trend implied by the
+//!   older TLE's `mean_motion_dot` (see
+//!   [`MeanElements::drag_decay_km_day`]).  J2 has no secular effect on `a`.
+//! - **Inclination** and **eccentricity** — no secular J2 or drag trend is
+//!   modelled; the jump is compared directly.
+//!
+//! A pair is a maneuver candidate when any jump exceeds that element's fixed
+//! TLE noise floor.  The floors do not depend on the rest of the history, so
+//! a two-TLE history can report its one burn and a history that is mostly
+//! burns (Starlink orbit raising) reports every one of them.
+//!
+//! # Epoch and Δv estimate
+//!
+//! For an impulsive burn the pre- and post-maneuver trajectories share the
+//! same position at the burn instant.  The estimated epoch is therefore the
+//! time between the two TLE epochs at which the positions predicted by both
+//! TLEs are closest, and the Δv is the velocity difference there.
+
+use crate::db::TleRow;
+use crate::elements::{MeanElements, SECONDS_PER_DAY};
+use crate::frames;
+use crate::propagate::{self, Propagator};
+use crate::residuals;
+use crate::tle_cache::{self, Parsed};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Drag-corrected semi-major-axis jump, in km, above which a pair is a
+/// maneuver candidate.  Well above TLE fit noise (tens of metres in LEO) and
+/// below a typical ISS reboost (~1–2 km).
+pub const DELTA_A_THRESHOLD_KM: f64 = 0.3;
+
+/// Inclination jump threshold in degrees.
+pub const DELTA_I_THRESHOLD_DEG: f64 = 0.01;
+
+/// Eccentricity jump threshold.
+pub const DELTA_E_THRESHOLD: f64 = 1e-4;
+
+/// Coarse scan step for the burn-epoch search, in seconds.
+const SCAN_STEP_S: i64 = 60;
+
+/// A detected maneuver between two consecutive TLEs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, sqlx::FromRow)]
+pub struct ManeuverEvent {
+    pub norad_id: i32,
+    /// `tles.id` of the last pre-maneuver element set.
+    pub from_tle_id: i64,
+    /// `tles.id` of the first post-maneuver element set.
+    pub to_tle_id: i64,
+    /// Estimated burn epoch, between the two TLE epochs.
+    pub epoch: DateTime<Utc>,
+    /// Estimated Δv magnitude in m/s.
+    pub delta_v_m_s: f64,
+    /// Semi-major-axis change beyond the drag trend, in km.
+    pub delta_a_km: f64,
+    pub delta_i_deg: f64,
+    pub delta_e: f64,
+    /// RIC prediction residual of the pair in km (see [`crate::residuals`]).
+    pub residual_km: f64,
+}
+
+/// Element jumps between two consecutive TLEs.
+#[derive(Debug, Clone, Copy)]
+struct Jump {
+    delta_a_km: f64,
+    delta_i_deg: f64,
+    delta_e: f64,
+}
+
+/// Detect maneuvers in a TLE history sorted oldest first.
+///
+/// Unparseable rows are skipped (they are reported by the
+/// `"tle_residuals"` job instead).
+pub fn detect(history: &[TleRow]) -> Vec<ManeuverEvent> {
+    let parsed: Vec<(&TleRow, Parsed, MeanElements)> = history
+        .iter()
+        .filter_map(|row| {
+            let tle = tle_cache::init(row.id, "", &row.line1, &row.line2).ok()?;
+            let mean = MeanElements::from_elements(&tle.elements);
+            Some((row, tle, mean))
+        })
+        .collect();
+    if parsed.len() < 2 {
+        return Vec::new();
+    }
+
+    parsed
+        .windows(2)
+        .map(|pair| (pair, element_jump(&pair[0].2, &pair[1].2)))
+        .filter(|(_, j)| j.exceeds_noise())
+        .filter_map(|(pair, jump)| {
+            let (older, older_tle, _) = &pair[0];
+            let (newer, newer_tle, _) = &pair[1];
+            let (epoch, delta_v_m_s) =
+                estimate_burn(older_tle, newer_tle, older.epoch, newer.epoch).ok()?;
+            let residual_km =
+                frames::norm(residuals::pair_residual(older_tle, newer_tle, &newer.epoch).ok()?);
+            Some(ManeuverEvent {
+                norad_id: newer.norad_id,
+                from_tle_id: older.id,
+                to_tle_id: newer.id,
+                epoch,
+                delta_v_m_s,
+                delta_a_km: jump.delta_a_km,
+                delta_i_deg: jump.delta_i_deg,
+                delta_e: jump.delta_e,
+                residual_km,
+            })
+        })
+        .collect()
+}
+
+impl Jump {
+    /// Whether any element jumped by more than its noise floor.
+    fn exceeds_noise(&self) -> bool {
+        self.delta_a_km.abs() > DELTA_A_THRESHOLD_KM
+            || self.delta_i_deg.abs() > DELTA_I_THRESHOLD_DEG
+            || self.delta_e.abs() > DELTA_E_THRESHOLD
+    }
+}
+
+/// Jumps in `a`, `i`, `e` from `older` to `newer`, net of the drag trend.
+fn element_jump(older: &MeanElements, newer: &MeanElements) -> Jump {
+    let dt_days = (newer.epoch - older.epoch).num_seconds() as f64 / SECONDS_PER_DAY;
+    let expected_a = older.semi_major_axis_km + older.drag_decay_km_day() * dt_days;
+    Jump {
+        delta_a_km: newer.semi_major_axis_km - expected_a,
+        delta_i_deg: newer.inclination_deg - older.inclination_deg,
+        delta_e: newer.eccentricity - older.eccentricity,
+    }
+}
+
+/// Burn epoch (closest approach of the two predicted trajectories) and Δv in
+/// m/s at that epoch.
+fn estimate_burn(
+    older: &Propagator,
+    newer: &Propagator,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<(DateTime<Utc>, f64)> {
+    let separation = |at: &DateTime<Utc>| -> Result<f64> {
+        let (r_old, _) = propagate::state_at(&older.elements, &older.constants, at)?;
+        let (r_new, _) = propagate::state_at(&newer.elements, &newer.constants, at)?;
+        Ok(frames::norm(frames::sub(r_new, r_old)))
+    };
+
+    // Coarse scan.
+    let span_s = (to - from).num_seconds().max(0);
+    let mut best_t = 0;
+    let mut best_d = f64::INFINITY;
+    let mut t = 0;
+    while t <= span_s {
+        let d = separation(&(from + Duration::seconds(t)))?;
+        if d < best_d {
+            best_d = d;
+            best_t = t;
+        }
+        t += SCAN_STEP_S;
+    }
+
+    // Refine to 1 s around the coarse minimum.
+    let lo = (best_t - SCAN_STEP_S).max(0);
+    let hi = (best_t + SCAN_STEP_S).min(span_s);
+    for t in lo..=hi {
+        let d = separation(&(from + Duration::seconds(t)))?;
+        if d < best_d {
+            best_d = d;
+            best_t = t;
+        }
+    }
+
+    let epoch = from + Duration::seconds(best_t);
+    let (_, v_old) = propagate::state_at(&older.elements, &older.constants, &epoch)?;
+    let (_, v_new) = propagate::state_at(&newer.elements, &newer.constants, &epoch)?;
+    Ok((epoch, frames::norm(frames::sub(v_new, v_old)) * 1_000.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const LINE1_DAY116: &str =
+        "1 25544U 98067A   26116.50000000  .00000000  00000-0  00000-0 0  9991";
+    const LINE1_DAY117: &str =
+        "1 25544U 98067A   26117.50000000  .00000000  00000-0  00000-0 0  9992";
+    const LINE1_DAY118: &str =
+        "1 25544U 98067A   26118.50000000  .00000000  00000-0  00000-0 0  9993";
+    const LINE1_DAY119: &str =
+        "1 25544U 98067A   26119.50000000  .00000000  00000-0  00000-0 0  9994";
+    const LINE2_LOW: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000013";
+    // Reboost: mean motion 15.50 → 15.46 rev/day (a +~12 km).
+    const LINE2_HIGH: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.46000000000018";
+
+    /// Append the TLE checksum digit to the first 68 columns of a line.
+    fn with_checksum(line: String) -> String {
+        let sum: u32 = line
+            .chars()
+            .map(|c| match c {
+                '-' => 1,
+                c => c.to_digit(10).unwrap_or(0),
+            })
+            .sum();
+        format!("{line}{}", sum % 10)
+    }
+
+    /// Line 1 at noon of April `day` 2026 with no drag terms.
+    fn line1(day: u32) -> String {
+        let day_of_year = 90 + day;
+        with_checksum(format!(
+            "1 25544U 98067A   26{day_of_year:03}.50000000  .00000000  00000-0  00000-0 0  999"
+        ))
+    }
+
+    /// Line 2 with the given mean motion in rev/day.
+    fn line2(mean_motion: f64) -> String {
+        with_checksum(format!(
+            "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 {mean_motion:11.8}00001"
+        ))
+    }
+
+    /// A history with one TLE per day from April 26 (at most five), with
+    /// these mean motions.
+    fn history(mean_motions: &[f64]) -> Vec<TleRow> {
+        mean_motions
+            .iter()
+            .zip(26..)
+            .map(|(&n, day)| row(day as i64 - 25, &line1(day), &line2(n), day))
+            .collect()
+    }
+
+    fn row(id: i64, line1: &str, line2: &str, day: u32) -> TleRow {
+        TleRow {
+            id,
+            norad_id: 25544,
+            line1: line1.to_owned(),
+            line2: line2.to_owned(),
+            epoch: Utc.with_ymd_and_hms(2026, 4, day, 12, 0, 0).unwrap(),
+        }
+    }
+
+    /// A history with no element changes yields no events.
+    #[test]
+    fn quiet_history_has_no_events() {
+        let history = vec![
+            row(1, LINE1_DAY116, LINE2_LOW, 26),
+            row(2, LINE1_DAY117, LINE2_LOW, 27),
+            row(3, LINE1_DAY118, LINE2_LOW, 28),
+        ];
+        assert!(detect(&history).is_empty());
+    }
+
+    /// A step in mean motion is reported once, between the right TLEs, with
+    /// an epoch inside the interval and a positive Δv.
+    #[test]
+    fn reboost_is_detected() {
+        let history = vec![
+            row(1, LINE1_DAY116, LINE2_LOW, 26),
+            row(2, LINE1_DAY117, LINE2_LOW, 27),
+            row(3, LINE1_DAY118, LINE2_HIGH, 28),
+            row(4, LINE1_DAY119, LINE2_HIGH, 29),
+        ];
+        let events = detect(&history);
+        assert_eq!(events.len(), 1, "events: {events:?}");
+        let e = &events[0];
+        assert_eq!((e.from_tle_id, e.to_tle_id), (2, 3));
+        assert!(e.delta_a_km > 10.0, "delta_a = {}", e.delta_a_km);
+        assert!(e.epoch >= history[1].epoch && e.epoch <= history[2].epoch);
+        assert!(e.delta_v_m_s > 0.0);
+    }
+
+    /// A two-TLE history has one pair; a burn between them is reported.
+    #[test]
+    fn two_tle_history_reports_its_burn() {
+        let events = detect(&history(&[15.50, 15.46]));
+        assert_eq!(events.len(), 1, "events: {events:?}");
+        assert_eq!((events[0].from_tle_id, events[0].to_tle_id), (1, 2));
+    }
+
+    /// A three-TLE history with one burn reports exactly that burn.
+    #[test]
+    fn three_tle_history_reports_its_burn() {
+        let events = detect(&history(&[15.50, 15.50, 15.46]));
+        assert_eq!(events.len(), 1, "events: {events:?}");
+        assert_eq!((events[0].from_tle_id, events[0].to_tle_id), (2, 3));
+    }
+
+    /// Orbit raising where most pairs are burns: none is masked by the others.
+    #[test]
+    fn mostly_burns_history_reports_every_burn() {
+        let events = detect(&history(&[15.80, 15.72, 15.72, 15.64, 15.56]));
+        let pairs: Vec<(i64, i64)> = events.iter().map(|e| (e.from_tle_id, e.to_tle_id)).collect();
+        assert_eq!(pairs, vec![(1, 2), (3, 4), (4, 5)]);
+        assert!(events.iter().all(|e| e.delta_a_km > 0.0));
+    }
+
+    /// The line helpers reproduce the hand-written fixtures.
+    #[test]
+    fn line_helpers_match_fixtures() {
+        assert_eq!(line1(26), LINE1_DAY116);
+        assert_eq!(line2(15.50), LINE2_LOW);
+    }
+
+    /// The drag trend is removed before thresholding.
+    #[test]
+    fn drag_trend_is_not_a_jump() {
+        let older = MeanElements {
+            epoch: Utc.with_ymd_and_hms(2026, 4, 26, 0, 0, 0).unwrap(),
+            semi_major_axis_km: 6_790.0,
+            eccentricity: 0.0004,
+            inclination_deg: 51.64,
+            raan_deg: 0.0,
+            arg_perigee_deg: 0.0,
+            mean_anomaly_deg: 0.0,
+            mean_motion_rev_day: 15.5,
+            mean_motion_dot: 0.0002,
+            bstar: 0.0,
+        };
+        let mut newer = older.clone();
+        newer.epoch = older.epoch + Duration::days(1);
+        newer.semi_major_axis_km += older.drag_decay_km_day();
+        let jump = element_jump(&older, &newer);
+        assert!(jump.delta_a_km.abs() < 1e-9);
+    }
+}
@@ -0,0 +1,303 @@
+//! Monte Carlo uncertainty propagation from perturbed TLE mean elements.
+//!
+//! # Method
+//!
+//! 1. Draw `clones` perturbations `δ = L z` of the six mean elements, where
+//!    `z ~ N(0, I)` and `L` is the Cholesky factor of the element covariance
+//!    (supplied, or [`default_covariance`] from the TLE age).
+//! 2. Propagate every clone with SGP4 over the window, in parallel across
+//!    cores (rayon).  Clones SGP4 rejects are dropped and counted.
+//! 3. At each sample, reduce the clone positions to a mean, a 3×3 TEME
+//!    covariance, 1σ extents along the nominal RIC axes, and percentiles of
+//!    the distance from the mean.
+//!
+//! Draws come from a single seeded generator before the parallel step, so a
+//! given `seed` reproduces the same result regardless of thread count.
+//!
+//! # Element order and units
+//!
+//! Covariances are over `[n, e, i, Ω, ω, M]` in rev/day, unitless and
+//! degrees, matching the TLE fields.
+
+use crate::frames;
+use crate::propagate::{self, Propagator};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, StandardNormal};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use sgp4::{Constants, Elements};
+
+/// Covariance of `[n, e, i, Ω, ω, M]` (see the module docs for units).
+pub type ElementCovariance = [[f64; 6]; 6];
+
+/// Statistics over the clone cloud at time offset `t`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MonteCarloSample {
+    /// Seconds since `start_at`.  Always a multiple of `step_s`.
+    pub t: i64,
+    /// Unperturbed TLE position in km (TEME).
+    pub nominal_r_km: [f64; 3],
+    /// Mean clone position in km (TEME).
+    pub mean_r_km: [f64; 3],
+    /// Sample covariance of the clone positions in km² (TEME).
+    pub cov_r_km2: [[f64; 3]; 3],
+    /// 1σ radial / in-track / cross-track extent in km, along the RIC axes
+    /// of the nominal state.
+    pub sigma_ric_km: [f64; 3],
+    /// Distance from the mean containing 50 / 95 / 99 % of clones, in km.
+    pub p50_km: f64,
+    pub p95_km: f64,
+    pub p99_km: f64,
+}
+
+/// Result of a Monte Carlo run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MonteCarloRun {
+    pub samples: Vec<MonteCarloSample>,
+    /// Clones that propagated over the whole window.
+    pub clones_used: usize,
+    /// Clones SGP4 rejected (e.g. decayed or hyperbolic).
+    pub clones_failed: usize,
+}
+
+/// Age-based default element covariance (diagonal).
+///
+/// A heuristic tuned to typical LEO TLE accuracy: about 1 km in-track at
+/// epoch growing by a few km per day of age, with sub-kilometre radial and
+/// cross-track terms.  `age_days` is the gap between the TLE epoch and the
+/// window start.
+pub fn default_covariance(age_days: f64) -> ElementCovariance {
+    let age = age_days.abs();
+    let sigma = [
+        5e-5,                // n, rev/day
+        1e-5,                // e
+        1e-3,                // i, deg
+        1e-3 + 1e-3 * age,   // Ω, deg
+        1e-2,                // ω, deg
+        1e-2 + 2e-2 * age,   // M, deg
+    ];
+    let mut cov = [[0.0; 6]; 6];
+    for (k, s) in sigma.iter().enumerate() {
+        cov[k][k] = s * s;
+    }
+    cov
+}
+
+/// Lower-triangular Cholesky factor of a symmetric positive semi-definite
+/// matrix.
+///
+/// # Errors
+/// Returns an error if the matrix is not symmetric or has a negative pivot.
+pub fn cholesky(c: &ElementCovariance) -> Result<ElementCovariance> {
+    let mut l = [[0.0; 6]; 6];
+    for i in 0..6 {
+        for j in 0..=i {
+            let scale = c[i][i].abs().max(c[j][j].abs()).max(f64::MIN_POSITIVE);
+            if (c[i][j] - c[j][i]).abs() > 1e-9 * scale {
+                bail!("covariance is not symmetric at ({i}, {j})");
+            }
+            let sum: f64 = (0..j).map(|k| l[i][k] * l[j][k]).sum();
+            if i == j {
+                let d = c[i][i] - sum;
+                if d < -1e-12 * scale {
+                    bail!("covariance is not positive semi-definite (pivot {i} = {d})");
+                }
+                l[i][i] = d.max(0.0).sqrt();
+            } else if l[j][j] > 0.0 {
+                l[i][j] = (c[i][j] - sum) / l[j][j];
+            }
+        }
+    }
+    Ok(l)
+}
+
+/// Run the Monte Carlo propagation around a parsed TLE, sampled at the
+/// offsets of [`propagate::propagate_window`].
+///
+/// # Errors
+/// Returns an error if the nominal TLE diverges, the covariance is invalid,
+/// or every clone fails.
+pub fn run(
+    propagator: &Propagator,
+    start_at: &DateTime<Utc>,
+    duration_s: i64,
+    step_s: i64,
+    clones: usize,
+    covariance: &ElementCovariance,
+    seed: u64,
+) -> Result<MonteCarloRun> {
+    let offsets = propagate::window_offsets_ms(0, duration_s * 1_000, step_s * 1_000);
+    let nominal = propagate::sample_offsets_ms(propagator, start_at, &offsets, true)?;
+    let elements = &propagator.elements;
+    let l = cholesky(covariance)?;
+    let offsets_s: Vec<f64> = offsets.iter().map(|&t| t as f64 / 1_000.0).collect();
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let draws: Vec<Elements> = (0..clones)
+        .map(|_| {
+            let z: [f64; 6] = std::array::from_fn(|_| StandardNormal.sample(&mut rng));
+            let delta: [f64; 6] =
+                std::array::from_fn(|i| (0..=i).map(|k| l[i][k] * z[k]).sum());
+            perturb(elements, &delta)
+        })
+        .collect();
+
+    let trajectories: Vec<Vec<[f64; 3]>> = draws
+        .par_iter()
+        .filter_map(|clone| {
+            let constants = Constants::from_elements(clone).ok()?;
+            offsets_s
+                .iter()
+                .map(|&t| {
+                    propagate::state_after_si(clone, &constants, start_at, t)
+                        .ok()
+                        .map(|(r, _)| r)
+                })
+                .collect()
+        })
+        .collect();
+    if trajectories.is_empty() {
+        bail!("all {clones} clones failed to propagate");
+    }
+
+    let samples = nominal
+        .iter()
+        .enumerate()
+        .map(|(k, s)| {
+            let cloud: Vec<[f64; 3]> = trajectories.iter().map(|tr| tr[k]).collect();
+            statistics(s.t / 1_000, s.r_km, s.v_km_s.unwrap_or_default(), &cloud)
+        })
+        .collect();
+
+    Ok(MonteCarloRun {
+        samples,
+        clones_used: trajectories.len(),
+        clones_failed: clones - trajectories.len(),
+    })
+}
+
+/// Apply an element perturbation, keeping angles in `[0, 360)` and the
+/// eccentricity non-negative.
+fn perturb(base: &Elements, delta: &[f64; 6]) -> Elements {
+    let mut e = base.clone();
+    e.mean_motion += delta[0];
+    e.eccentricity += delta[1];
+    e.inclination = (e.inclination + delta[2]).clamp(0.0, 180.0);
+    e.right_ascension += delta[3];
+    e.argument_of_perigee += delta[4];
+    e.mean_anomaly += delta[5];
+    if e.eccentricity < 0.0 {
+        // (−e, ω, M) is the same orbit as (e, ω + 180°, M − 180°).
+        e.eccentricity = -e.eccentricity;
+        e.argument_of_perigee += 180.0;
+        e.mean_anomaly -= 180.0;
+    }
+    e.right_ascension = e.right_ascension.rem_euclid(360.0);
+    e.argument_of_perigee = e.argument_of_perigee.rem_euclid(360.0);
+    e.mean_anomaly = e.mean_anomaly.rem_euclid(360.0);
+    e
+}
+
+fn statistics(t: i64, nominal_r: [f64; 3], nominal_v: [f64; 3], cloud: &[[f64; 3]]) -> MonteCarloSample {
+    let n = cloud.len() as f64;
+    let mean: [f64; 3] = std::array::from_fn(|i| cloud.iter().map(|p| p[i]).sum::<f64>() / n);
+    let mut cov = [[0.0; 3]; 3];
+    if cloud.len() > 1 {
+        for p in cloud {
+            let d = frames::sub(*p, mean);
+            for i in 0..3 {
+                for j in 0..3 {
+                    cov[i][j] += d[i] * d[j] / (n - 1.0);
+                }
+            }
+        }
+    }
+    let basis = frames::ric_basis(nominal_r, nominal_v);
+    let sigma_ric_km = basis.map(|axis| {
+        let c_axis: [f64; 3] = std::array::from_fn(|i| frames::dot(cov[i], axis));
+        frames::dot(axis, c_axis).max(0.0).sqrt()
+    });
+
+    let mut distances: Vec<f64> = cloud.iter().map(|p| frames::norm(frames::sub(*p, mean))).collect();
+    distances.sort_by(f64::total_cmp);
+    let percentile = |q: f64| {
+        let rank = ((q * n).ceil() as usize).clamp(1, distances.len());
+        distances[rank - 1]
+    };
+
+    MonteCarloSample {
+        t,
+        nominal_r_km: nominal_r,
+        mean_r_km: mean,
+        cov_r_km2: cov,
+        sigma_ric_km,
+        p50_km: percentile(0.50),
+        p95_km: percentile(0.95),
+        p99_km: percentile(0.99),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::propagate::PropagationModel;
+    use chrono::TimeZone;
+
+    const ISS_LINE1: &str =
+        "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9999";
+    const ISS_LINE2: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000013";
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap()
+    }
+
+    fn iss() -> Propagator {
+        Propagator::new("ISS", ISS_LINE1, ISS_LINE2, PropagationModel::default()).unwrap()
+    }
+
+    #[test]
+    fn cholesky_reproduces_matrix() {
+        let mut c = default_covariance(2.0);
+        c[0][5] = 0.5 * (c[0][0] * c[5][5]).sqrt();
+        c[5][0] = c[0][5];
+        let l = cholesky(&c).unwrap();
+        for i in 0..6 {
+            for j in 0..6 {
+                let llt: f64 = (0..6).map(|k| l[i][k] * l[j][k]).sum();
+                assert!((llt - c[i][j]).abs() < 1e-15, "({i},{j}) {llt} vs {}", c[i][j]);
+            }
+        }
+        c[1][1] = -1.0;
+        assert!(cholesky(&c).is_err());
+    }
+
+    /// With zero covariance every clone is the nominal trajectory.
+    #[test]
+    fn zero_covariance_collapses_to_nominal() {
+        let run = run(&iss(), &start(), 600, 60, 16, &[[0.0; 6]; 6], 1).unwrap();
+        assert_eq!(run.samples.len(), 11);
+        assert_eq!(run.clones_used, 16);
+        for s in &run.samples {
+            assert!(frames::norm(frames::sub(s.mean_r_km, s.nominal_r_km)) < 1e-6);
+            assert!(s.p99_km < 1e-6);
+        }
+    }
+
+    /// The default cloud is dominated by in-track spread, grows with time,
+    /// and is reproducible for a fixed seed.
+    #[test]
+    fn default_cloud_grows_in_track() {
+        let cov = default_covariance(1.0);
+        let a = run(&iss(), &start(), 86_400, 3_600, 200, &cov, 7).unwrap();
+        let b = run(&iss(), &start(), 86_400, 3_600, 200, &cov, 7).unwrap();
+        assert_eq!(a, b);
+        let (first, last) = (&a.samples[0], &a.samples[a.samples.len() - 1]);
+        assert!(last.p95_km > first.p95_km, "{} <= {}", last.p95_km, first.p95_km);
+        assert!(last.sigma_ric_km[1] > last.sigma_ric_km[0]);
+        assert!(first.p50_km <= first.p95_km && first.p95_km <= first.p99_km);
+    }
+}
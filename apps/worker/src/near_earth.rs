@@ -0,0 +1,446 @@
+//! Block-wise near-Earth SGP4 for [`crate::propagate::propagate_batch`].
+//!
+//! A batch samples every satellite on the same grid, so its inner loop is one
+//! satellite over many instants.  [`NearEarth`] runs that loop [`LANES`]
+//! instants at a time: each stage of the near-Earth step (secular update,
+//! Kepler solve, short-period terms) is a loop over fixed-size `[f64; LANES]`
+//! arrays, so the polynomial and short-period arithmetic compiles to packed
+//! instructions.  Terms that depend only on the satellite (`cos I₀`, `sin I₀`,
+//! `aₑ kₑ / 60`) are computed once rather than per sample.  `sin`, `cos`,
+//! `atan2` and `powf` are still one libm call per lane.
+//!
+//! The formulas and their evaluation order are those of the `sgp4` crate
+//! (`Constants::propagate_from_state` and `near_earth_orbital_elements`, 2.4),
+//! so results match [`sgp4::Constants::propagate`] to rounding.  The crate
+//! keeps its initialised constants private; [`NearEarth::from_constants`]
+//! reads them through the crate's `serde` representation instead of
+//! re-deriving them.
+//!
+//! Deep-space orbits (period ≥ 225 min) carry resonance state and lunar-solar
+//! terms and stay on the scalar path.  The operation mode only changes
+//! initialisation and the deep-space branch, so one evaluator serves both
+//! `afspc` and `improved`.
+
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use sgp4::{Constants, Geopotential, Orbit};
+
+/// Instants evaluated per block.
+pub const LANES: usize = 8;
+
+/// `[f64; LANES]`, one value per instant of a block.
+type Lanes = [f64; LANES];
+
+/// Near-Earth SGP4 constants of one satellite, unpacked for block evaluation.
+#[derive(Debug, Clone)]
+pub struct NearEarth {
+    geopotential: Geopotential,
+    orbit_0: Orbit,
+    right_ascension_dot: f64,
+    argument_of_perigee_dot: f64,
+    mean_anomaly_dot: f64,
+    c1: f64,
+    c4: f64,
+    k0: f64,
+    k1: f64,
+    a0: f64,
+    k2: f64,
+    k3: f64,
+    k4: f64,
+    k5: f64,
+    k6: f64,
+    high_altitude: Option<HighAltitude>,
+    cos_i0: f64,
+    sin_i0: f64,
+    /// ¹/₂ J₂.
+    half_j2: f64,
+    /// aₑ kₑ / 60, from Earth radii per minute to km/s.
+    velocity_scale: f64,
+}
+
+/// Extra drag terms of perigees above 220 km.
+#[derive(Debug, Clone, Deserialize)]
+struct HighAltitude {
+    c5: f64,
+    d2: f64,
+    d3: f64,
+    d4: f64,
+    eta: f64,
+    k7: f64,
+    k8: f64,
+    k9: f64,
+    k10: f64,
+    elliptic: Elliptic,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+enum Elliptic {
+    No {},
+    Yes { k11: f64, k12: f64, k13: f64 },
+}
+
+/// The fields of `sgp4::Constants` this module reads, as serialised.
+#[derive(Deserialize)]
+struct SerdeConstants {
+    geopotential: Geopotential,
+    right_ascension_dot: f64,
+    argument_of_perigee_dot: f64,
+    mean_anomaly_dot: f64,
+    c1: f64,
+    c4: f64,
+    k0: f64,
+    k1: f64,
+    method: SerdeMethod,
+    orbit_0: Orbit,
+}
+
+#[derive(Deserialize)]
+enum SerdeMethod {
+    NearEarth {
+        a0: f64,
+        k2: f64,
+        k3: f64,
+        k4: f64,
+        k5: f64,
+        k6: f64,
+        high_altitude: SerdeHighAltitude,
+    },
+    DeepSpace(IgnoredAny),
+}
+
+#[derive(Deserialize)]
+enum SerdeHighAltitude {
+    No {},
+    Yes(HighAltitude),
+}
+
+/// Positions and velocities of one block, TEME, one lane per instant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub position: [Lanes; 3],
+    pub velocity: [Lanes; 3],
+    /// `false` where SGP4 fails (eccentricity out of range or a negative
+    /// semi-latus rectum); that lane's state is meaningless.
+    pub valid: [bool; LANES],
+}
+
+impl NearEarth {
+    /// Unpack `constants`, or `None` for a deep-space orbit.
+    pub fn from_constants(constants: &Constants) -> Option<Self> {
+        let value = serde_json::to_value(constants).ok()?;
+        let c: SerdeConstants = serde_json::from_value(value).ok()?;
+        let SerdeMethod::NearEarth {
+            a0,
+            k2,
+            k3,
+            k4,
+            k5,
+            k6,
+            high_altitude,
+        } = c.method
+        else {
+            return None;
+        };
+        Some(Self {
+            geopotential: c.geopotential,
+            cos_i0: c.orbit_0.inclination.cos(),
+            sin_i0: c.orbit_0.inclination.sin(),
+            half_j2: 0.5 * c.geopotential.j2,
+            velocity_scale: c.geopotential.ae * c.geopotential.ke / 60.0,
+            orbit_0: c.orbit_0,
+            right_ascension_dot: c.right_ascension_dot,
+            argument_of_perigee_dot: c.argument_of_perigee_dot,
+            mean_anomaly_dot: c.mean_anomaly_dot,
+            c1: c.c1,
+            c4: c.c4,
+            k0: c.k0,
+            k1: c.k1,
+            a0,
+            k2,
+            k3,
+            k4,
+            k5,
+            k6,
+            high_altitude: match high_altitude {
+                SerdeHighAltitude::No {} => None,
+                SerdeHighAltitude::Yes(h) => Some(h),
+            },
+        })
+    }
+
+    /// Propagate to `t`, SI minutes since the TLE epoch, one instant per lane.
+    #[allow(clippy::needless_range_loop)]
+    pub fn propagate_block(&self, t: &Lanes) -> Block {
+        let o = &self.orbit_0;
+
+        // Secular update: p₂₂ = Ω₀ + Ω̇ t + k₀ t², p₂₃ = ω₀ + ω̇ t, p₂₄ = M₀ + Ṁ t.
+        let p22 = lanes(|i| {
+            o.right_ascension + self.right_ascension_dot * t[i] + self.k0 * (t[i] * t[i])
+        });
+        let p23 = lanes(|i| o.argument_of_perigee + self.argument_of_perigee_dot * t[i]);
+        let p24 = lanes(|i| o.mean_anomaly + self.mean_anomaly_dot * t[i]);
+
+        let mut omega = [0.0; LANES];
+        let mut mean_anomaly = [0.0; LANES];
+        let mut a = [0.0; LANES];
+        let mut p27 = [0.0; LANES];
+        match &self.high_altitude {
+            None => {
+                for i in 0..LANES {
+                    let t = t[i];
+                    omega[i] = p23[i];
+                    mean_anomaly[i] = p24[i] + o.mean_motion * self.k1 * (t * t);
+                    let d = 1.0 - self.c1 * t;
+                    a[i] = self.a0 * (d * d);
+                    p27[i] = o.eccentricity - self.c4 * t;
+                }
+            }
+            Some(h) => {
+                // p₂₅ = k₁₃ ((1 + η cos p₂₄)³ - k₁₁) + k₁₂ t, zero when e₀ ≤ 10⁻⁴.
+                let p25 = match h.elliptic {
+                    Elliptic::Yes { k11, k12, k13 } => lanes(|i| {
+                        let x = 1.0 + h.eta * p24[i].cos();
+                        k13 * (x * x * x - k11) + k12 * t[i]
+                    }),
+                    Elliptic::No {} => [0.0; LANES],
+                };
+                let p26 = lanes(|i| p24[i] + p25[i]);
+                let sin_p26 = lanes(|i| p26[i].sin());
+                for i in 0..LANES {
+                    let t = t[i];
+                    let (t2, t3, t4) = (t * t, t * t * t, (t * t) * (t * t));
+                    omega[i] = p23[i] - p25[i];
+                    mean_anomaly[i] = p26[i]
+                        + o.mean_motion * (self.k1 * t2 + h.k8 * t3 + t4 * (h.k9 + t * h.k10));
+                    let d = 1.0 - self.c1 * t - h.d2 * t2 - h.d3 * t3 - h.d4 * t4;
+                    a[i] = self.a0 * (d * d);
+                    p27[i] = o.eccentricity - (self.c4 * t + h.c5 * (sin_p26[i] - h.k7));
+                }
+            }
+        }
+
+        let mut valid: [bool; LANES] = std::array::from_fn(|i| (-0.001..1.0).contains(&p27[i]));
+        let e = lanes(|i| p27[i].max(1.0e-6));
+        let n = lanes(|i| self.geopotential.ke / a[i].powf(1.5));
+
+        // Long-period terms.
+        let p37 = lanes(|i| 1.0 / (a[i] * (1.0 - e[i] * e[i])));
+        let axn = lanes(|i| e[i] * omega[i].cos());
+        let ayn = lanes(|i| e[i] * omega[i].sin() + p37[i] * self.k2);
+        let p38 = lanes(|i| {
+            (mean_anomaly[i] + omega[i] + p37[i] * self.k5 * axn[i])
+                % (2.0 * std::f64::consts::PI)
+        });
+
+        // Kepler's equation for E + ω, each lane stopping where the scalar
+        // iteration would.
+        let mut ew = p38;
+        let mut sin_ew = lanes(|i| ew[i].sin());
+        let mut cos_ew = lanes(|i| ew[i].cos());
+        let mut done = [false; LANES];
+        for _ in 0..10 {
+            for i in 0..LANES {
+                let delta = (p38[i] - ayn[i] * cos_ew[i] + axn[i] * sin_ew[i] - ew[i])
+                    / (1.0 - cos_ew[i] * axn[i] - sin_ew[i] * ayn[i]);
+                done[i] |= delta.abs() < 1.0e-12;
+                if !done[i] {
+                    ew[i] += delta.clamp(-0.95, 0.95);
+                }
+            }
+            if done.iter().all(|&d| d) {
+                break;
+            }
+            sin_ew = lanes(|i| ew[i].sin());
+            cos_ew = lanes(|i| ew[i].cos());
+        }
+
+        // Short-period terms.
+        let p39 = lanes(|i| axn[i] * axn[i] + ayn[i] * ayn[i]);
+        let pl = lanes(|i| a[i] * (1.0 - p39[i]));
+        for i in 0..LANES {
+            valid[i] &= pl[i] >= 0.0;
+        }
+        let p40 = lanes(|i| axn[i] * sin_ew[i] - ayn[i] * cos_ew[i]);
+        let r = lanes(|i| a[i] * (1.0 - (axn[i] * cos_ew[i] + ayn[i] * sin_ew[i])));
+        let r_dot = lanes(|i| a[i].sqrt() * p40[i] / r[i]);
+        let b = lanes(|i| (1.0 - p39[i]).sqrt());
+        let p41 = lanes(|i| p40[i] / (1.0 + b[i]));
+        let p42 = lanes(|i| a[i] / r[i] * (sin_ew[i] - ayn[i] - axn[i] * p41[i]));
+        let p43 = lanes(|i| a[i] / r[i] * (cos_ew[i] - axn[i] + ayn[i] * p41[i]));
+        let u = lanes(|i| p42[i].atan2(p43[i]));
+        let mut rk = [0.0; LANES];
+        let mut uk = [0.0; LANES];
+        let mut inclination_k = [0.0; LANES];
+        let mut right_ascension_k = [0.0; LANES];
+        let mut rk_dot = [0.0; LANES];
+        let mut rfk_dot = [0.0; LANES];
+        for i in 0..LANES {
+            let p44 = 2.0 * p43[i] * p42[i];
+            let p45 = 1.0 - 2.0 * (p42[i] * p42[i]);
+            let p46 = self.half_j2 / pl[i] / pl[i];
+            let j2_pl = self.half_j2 / pl[i];
+            rk[i] = r[i] * (1.0 - 1.5 * p46 * b[i] * self.k6) + 0.5 * j2_pl * self.k3 * p45;
+            uk[i] = u[i] - 0.25 * p46 * self.k4 * p44;
+            inclination_k[i] = o.inclination + 1.5 * p46 * self.cos_i0 * self.sin_i0 * p45;
+            right_ascension_k[i] = p22[i] + 1.5 * p46 * self.cos_i0 * p44;
+            rk_dot[i] = r_dot[i] - n[i] * j2_pl * self.k3 * p44 / self.geopotential.ke;
+            rfk_dot[i] = pl[i].sqrt() / r[i]
+                + n[i] * j2_pl * (self.k3 * p45 + 1.5 * self.k6) / self.geopotential.ke;
+        }
+        let (sin_uk, cos_uk) = (lanes(|i| uk[i].sin()), lanes(|i| uk[i].cos()));
+        let sin_ik = lanes(|i| inclination_k[i].sin());
+        let cos_ik = lanes(|i| inclination_k[i].cos());
+        let sin_rak = lanes(|i| right_ascension_k[i].sin());
+        let cos_rak = lanes(|i| right_ascension_k[i].cos());
+
+        let mut position = [[0.0; LANES]; 3];
+        let mut velocity = [[0.0; LANES]; 3];
+        let ae = self.geopotential.ae;
+        for i in 0..LANES {
+            let u0 = -sin_rak[i] * cos_ik[i] * sin_uk[i] + cos_rak[i] * cos_uk[i];
+            let u1 = cos_rak[i] * cos_ik[i] * sin_uk[i] + sin_rak[i] * cos_uk[i];
+            let u2 = sin_ik[i] * sin_uk[i];
+            position[0][i] = rk[i] * u0 * ae;
+            position[1][i] = rk[i] * u1 * ae;
+            position[2][i] = rk[i] * u2 * ae;
+            velocity[0][i] = (rk_dot[i] * u0
+                + rfk_dot[i]
+                    * (-sin_rak[i] * cos_ik[i] * cos_uk[i] - cos_rak[i] * sin_uk[i]))
+                * self.velocity_scale;
+            velocity[1][i] = (rk_dot[i] * u1
+                + rfk_dot[i]
+                    * (cos_rak[i] * cos_ik[i] * cos_uk[i] - sin_rak[i] * sin_uk[i]))
+                * self.velocity_scale;
+            velocity[2][i] =
+                (rk_dot[i] * u2 + rfk_dot[i] * (sin_ik[i] * cos_uk[i])) * self.velocity_scale;
+        }
+        Block {
+            position,
+            velocity,
+            valid,
+        }
+    }
+}
+
+/// One value per lane.
+#[inline(always)]
+fn lanes(f: impl Fn(usize) -> f64) -> Lanes {
+    std::array::from_fn(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sgp4::{Elements, MinutesSinceEpoch};
+
+    /// Replace the last digit of a TLE line with its checksum.
+    fn with_checksum(line: &str) -> String {
+        let body = &line[..68];
+        let sum: u32 = body
+            .chars()
+            .map(|c| match c {
+                '-' => 1,
+                c => c.to_digit(10).unwrap_or(0),
+            })
+            .sum();
+        format!("{body}{}", sum % 10)
+    }
+
+    fn constants(line1: &str, line2: &str) -> Constants {
+        let elements = Elements::from_tle(
+            None,
+            with_checksum(line1).as_bytes(),
+            with_checksum(line2).as_bytes(),
+        )
+        .unwrap();
+        Constants::from_elements(&elements).unwrap()
+    }
+
+    /// Each lane equals the crate's own propagation, or fails where it fails.
+    fn assert_matches_scalar(constants: &Constants, times: &[f64]) {
+        let near_earth = NearEarth::from_constants(constants).unwrap();
+        for chunk in times.chunks_exact(LANES) {
+            let t: Lanes = chunk.try_into().unwrap();
+            let block = near_earth.propagate_block(&t);
+            for (i, &t) in t.iter().enumerate() {
+                match constants.propagate(MinutesSinceEpoch(t)) {
+                    Ok(p) => {
+                        assert!(block.valid[i], "lane failed at t={t} min");
+                        for k in 0..3 {
+                            assert!((block.position[k][i] - p.position[k]).abs() < 1e-8, "t={t}");
+                            assert!((block.velocity[k][i] - p.velocity[k]).abs() < 1e-11, "t={t}");
+                        }
+                    }
+                    Err(_) => assert!(!block.valid[i], "lane succeeded at t={t} min"),
+                }
+            }
+        }
+    }
+
+    fn grid(from: f64, to: f64, n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|k| from + (to - from) * k as f64 / (n - 1) as f64)
+            .collect()
+    }
+
+    /// ISS: perigee above 220 km, eccentric enough for the `k₁₁..k₁₃` terms.
+    #[test]
+    fn high_altitude_elliptic_matches_scalar() {
+        let c = constants(
+            "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9990",
+            "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000010",
+        );
+        let h = NearEarth::from_constants(&c).unwrap().high_altitude.unwrap();
+        assert!(matches!(h.elliptic, Elliptic::Yes { .. }));
+        assert_matches_scalar(&c, &grid(-2_880.0, 2_880.0, 8 * 40));
+    }
+
+    /// e₀ ≤ 10⁻⁴ drops the elliptic drag terms.
+    #[test]
+    fn high_altitude_circular_matches_scalar() {
+        let c = constants(
+            "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9990",
+            "2 25544  51.6400 127.0000 0000500  20.0000 340.0000 15.50000000000010",
+        );
+        let h = NearEarth::from_constants(&c).unwrap().high_altitude.unwrap();
+        assert!(matches!(h.elliptic, Elliptic::No {}));
+        assert_matches_scalar(&c, &grid(-2_880.0, 2_880.0, 8 * 40));
+    }
+
+    /// Perigee below 220 km uses the truncated drag polynomial.
+    #[test]
+    fn low_perigee_matches_scalar() {
+        let c = constants(
+            "1 99001U 26001A   26116.50000000  .00100000  00000-0  20000-3 0  9990",
+            "2 99001  97.4000  10.0000 0100000  90.0000 270.0000 16.00000000000010",
+        );
+        assert!(NearEarth::from_constants(&c).unwrap().high_altitude.is_none());
+        assert_matches_scalar(&c, &grid(-1_440.0, 1_440.0, 8 * 40));
+    }
+
+    /// Lanes past re-entry are flagged exactly where SGP4 errors.
+    #[test]
+    fn decayed_lanes_are_invalid() {
+        let c = constants(
+            "1 99002U 26001A   26116.50000000  .01000000  00000-0  50000-1 0  9990",
+            "2 99002  97.4000  10.0000 0100000  90.0000 270.0000 16.00000000000010",
+        );
+        let times = grid(0.0, 43_200.0, 8 * 40);
+        let failures = times
+            .iter()
+            .filter(|&&t| c.propagate(MinutesSinceEpoch(t)).is_err())
+            .count();
+        assert!(failures > 0 && failures < times.len(), "{failures} failures");
+        assert_matches_scalar(&c, &times);
+    }
+
+    /// GPS (12 h period) is deep space: left to the scalar path.
+    #[test]
+    fn deep_space_is_not_unpacked() {
+        let c = constants(
+            "1 36585U 10022A   26116.50000000  .00000000  00000-0  00000-0 0  9990",
+            "2 36585  55.1600 150.0000 0010000  40.0000 320.0000  2.00564740000010",
+        );
+        assert!(NearEarth::from_constants(&c).is_none());
+    }
+}
@@ -0,0 +1,230 @@
+//! Pass prediction for a ground observer.
+//!
+//! Every observer-based feature (Doppler tables, contact plans, visible
+//! passes) finds passes with [`find_passes`] so they agree on AOS/LOS.
+//!
+//! # Root finding
+//!
+//! 1. Elevation minus the mask angle is sampled every [`COARSE_STEP_S`]
+//!    seconds over the window.
+//! 2. Each sign change is bisected to [`TIME_TOLERANCE_S`] to give AOS
+//!    (rising) or LOS (setting).
+//! 3. The time of closest approach (maximum elevation) is found with a
+//!    golden-section search between AOS and LOS.
+//!
+//! A pass already in progress at the window start has `aos = start`; one
+//! still in progress at the window end has `los = end`.  Passes that rise and
+//! set between two coarse samples (shorter than `COARSE_STEP_S`, i.e. grazing
+//! the mask) are not reported.
+
+use crate::earth::{Look, Observer};
+use crate::propagate;
+use crate::timescale;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sgp4::{Constants, Elements};
+
+/// Coarse elevation sampling interval in seconds.
+pub const COARSE_STEP_S: f64 = 30.0;
+
+/// Bisection / golden-section tolerance in seconds.
+pub const TIME_TOLERANCE_S: f64 = 0.01;
+
+/// One pass of a satellite over an observer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Pass {
+    /// Acquisition of signal: elevation rises through the mask.
+    pub aos: DateTime<Utc>,
+    /// Time of maximum elevation.
+    pub tca: DateTime<Utc>,
+    /// Loss of signal: elevation sets through the mask.
+    pub los: DateTime<Utc>,
+    /// Maximum elevation in degrees.
+    pub max_elevation_deg: f64,
+}
+
+/// An initialised TLE seen from one observer.
+pub struct Tracker<'a> {
+    pub elements: &'a Elements,
+    pub constants: &'a Constants,
+    pub observer: Observer,
+}
+
+impl Tracker<'_> {
+    /// Look angles at an absolute instant.
+    ///
+    /// # Errors
+    /// Returns an error if SGP4 diverges.
+    pub fn look_at(&self, at: &DateTime<Utc>) -> Result<Look> {
+        let (r, v) = propagate::state_at(self.elements, self.constants, at)?;
+        Ok(self.observer.look(r, v, at))
+    }
+
+    /// Look angles `seconds` SI seconds after `anchor`.  The SGP4 time is
+    /// offset from `anchor` rather than taken from the UTC label, which
+    /// repeats across an inserted leap second.
+    ///
+    /// # Errors
+    /// Returns an error if SGP4 diverges.
+    pub fn look_after(&self, anchor: &DateTime<Utc>, seconds: f64) -> Result<Look> {
+        let (r, v) = propagate::state_after_si(self.elements, self.constants, anchor, seconds)?;
+        Ok(self.observer.look(r, v, &offset(anchor, seconds)))
+    }
+}
+
+/// Offset `start` by a fractional number of SI seconds (microsecond
+/// resolution; see [`crate::timescale::utc_after_si`]).
+pub fn offset(start: &DateTime<Utc>, seconds: f64) -> DateTime<Utc> {
+    timescale::utc_after_si(start, seconds)
+}
+
+/// Find all passes above `min_elevation_deg` in `[start, end]`.
+///
+/// # Errors
+/// Returns an error if SGP4 diverges anywhere in the window.
+pub fn find_passes(
+    tracker: &Tracker<'_>,
+    min_elevation_deg: f64,
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+) -> Result<Vec<Pass>> {
+    let span_s = (*end - *start).num_milliseconds() as f64 / 1_000.0;
+    let f = |t: f64| -> Result<f64> {
+        Ok(tracker.look_after(start, t)?.elevation_deg - min_elevation_deg)
+    };
+
+    let mut passes = Vec::new();
+    let mut prev_t = 0.0;
+    let mut prev_f = f(0.0)?;
+    let mut aos = (prev_f >= 0.0).then_some(0.0);
+
+    while prev_t < span_s {
+        let t = (prev_t + COARSE_STEP_S).min(span_s);
+        let ft = f(t)?;
+        if prev_f < 0.0 && ft >= 0.0 {
+            aos = Some(bisect(&f, prev_t, t)?);
+        } else if prev_f >= 0.0 && ft < 0.0 {
+            if let Some(rise) = aos.take() {
+                let set = bisect(&f, prev_t, t)?;
+                passes.push(build_pass(tracker, start, rise, set)?);
+            }
+        }
+        prev_t = t;
+        prev_f = ft;
+    }
+    if let Some(rise) = aos {
+        passes.push(build_pass(tracker, start, rise, span_s)?);
+    }
+    Ok(passes)
+}
+
+/// Bisect the sign change of `f` in `[lo, hi]` to [`TIME_TOLERANCE_S`].
+pub(crate) fn bisect(f: &impl Fn(f64) -> Result<f64>, mut lo: f64, mut hi: f64) -> Result<f64> {
+    let lo_sign = f(lo)? >= 0.0;
+    while hi - lo > TIME_TOLERANCE_S {
+        let mid = 0.5 * (lo + hi);
+        if (f(mid)? >= 0.0) == lo_sign {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok(0.5 * (lo + hi))
+}
+
+/// Locate the elevation peak between `rise` and `set` and build the [`Pass`].
+fn build_pass(tracker: &Tracker<'_>, start: &DateTime<Utc>, rise: f64, set: f64) -> Result<Pass> {
+    let elevation = |t: f64| -> Result<f64> { Ok(tracker.look_after(start, t)?.elevation_deg) };
+
+    // Golden-section search for the maximum.
+    let inv_phi = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut a, mut b) = (rise, set);
+    let mut c = b - inv_phi * (b - a);
+    let mut d = a + inv_phi * (b - a);
+    let (mut fc, mut fd) = (elevation(c)?, elevation(d)?);
+    while b - a > TIME_TOLERANCE_S {
+        if fc > fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - inv_phi * (b - a);
+            fc = elevation(c)?;
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + inv_phi * (b - a);
+            fd = elevation(d)?;
+        }
+    }
+    let peak = 0.5 * (a + b);
+
+    Ok(Pass {
+        aos: offset(start, rise),
+        tca: offset(start, peak),
+        los: offset(start, set),
+        max_elevation_deg: elevation(peak)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    const ISS_LINE1: &str =
+        "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9999";
+    const ISS_LINE2: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000013";
+
+    fn iss_epoch() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap()
+    }
+
+    /// A mid-latitude site sees several ISS passes per day, each well formed.
+    #[test]
+    fn iss_passes_are_well_formed() {
+        let (elements, constants) = propagate::init("ISS", ISS_LINE1, ISS_LINE2).unwrap();
+        let tracker = Tracker {
+            elements: &elements,
+            constants: &constants,
+            observer: Observer { lat_deg: 51.5, lon_deg: -0.13, alt_m: 20.0 },
+        };
+        let start = iss_epoch();
+        let end = start + Duration::days(1);
+        let passes = find_passes(&tracker, 10.0, &start, &end).unwrap();
+        assert!(!passes.is_empty(), "expected at least one pass in 24 h");
+        for p in &passes {
+            assert!(p.aos <= p.tca && p.tca <= p.los, "{p:?}");
+            assert!(p.max_elevation_deg >= 10.0, "{p:?}");
+            // LEO passes last at most ~12 minutes above 10°.
+            assert!(p.los - p.aos < Duration::minutes(15), "{p:?}");
+            // Elevation at AOS/LOS is the mask (unless clipped by the window).
+            for edge in [p.aos, p.los] {
+                if edge != start && edge != end {
+                    let el = tracker.look_at(&edge).unwrap().elevation_deg;
+                    assert!((el - 10.0).abs() < 0.05, "edge elevation {el}");
+                }
+            }
+        }
+        for pair in passes.windows(2) {
+            assert!(pair[0].los < pair[1].aos);
+        }
+    }
+
+    /// An observer who never sees the satellite gets no passes: the ISS
+    /// (51.6°) never rises above 10° at the South Pole.
+    #[test]
+    fn no_passes_out_of_reach() {
+        let (elements, constants) = propagate::init("ISS", ISS_LINE1, ISS_LINE2).unwrap();
+        let tracker = Tracker {
+            elements: &elements,
+            constants: &constants,
+            observer: Observer { lat_deg: -90.0, lon_deg: 0.0, alt_m: 0.0 },
+        };
+        let start = iss_epoch();
+        let passes = find_passes(&tracker, 10.0, &start, &(start + Duration::days(1))).unwrap();
+        assert!(passes.is_empty());
+    }
+}
@@ -19,11 +19,266 @@
 //! three.js uses Y-up.  When rendering, **three.js Y ← TEME Z**.
 //! This mapping is applied once in `apps/web/lib/gmst.ts`; it is NOT applied
 //! here.  The worker always returns raw TEME vectors.
+//!
+//! # Gravity model and operation mode
+//!
+//! Window-style jobs may pick the SGP4 gravity constants (`wgs72` or
+//! `wgs84`) and operation mode (`afspc`, the original AFSPC code path, or
+//! `improved`) through [`PropagationModel`].  The default, WGS84 with the
+//! improved mode, is what `Constants::from_elements` has always used.  `wgs72` with
+//! `afspc` reproduces the catalogue-generation setup some tools (STK's
+//! "AFSPC" option) default to; `wgs72` with `improved` matches Vallado's
+//! reference code and Skyfield.
+//!
+//! The same model can swap SGP4 for one of the [`crate::analytic`]
+//! comparison propagators (`two_body`, `j2_secular`); sampling, frames and
+//! result shapes are unchanged, and `gravity` still picks the constants.
 
-use crate::job::Sample;
+use crate::analytic::AnalyticOrbit;
+use crate::job::{EpochSample, Sample, SatelliteTle};
+use crate::near_earth::{self, NearEarth};
+use crate::timescale;
+use crate::tle_cache;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use sgp4::{Constants, Elements, MinutesSinceEpoch};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use sgp4::{Constants, Elements, Geopotential, MinutesSinceEpoch, Orbit, Prediction};
+
+/// Orbit model a job is propagated with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PropagatorKind {
+    #[default]
+    Sgp4,
+    /// Unperturbed Kepler orbit of the TLE's mean elements.
+    TwoBody,
+    /// Kepler orbit with J2 secular drift of the node, perigee and anomaly.
+    J2Secular,
+}
+
+/// SGP4 gravity constants.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Gravity {
+    Wgs72,
+    #[default]
+    Wgs84,
+}
+
+/// SGP4 operation mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OpsMode {
+    /// The original AFSPC code path: AFSPC sidereal time and epoch, and its
+    /// deep-space integrator quirks.
+    Afspc,
+    /// Vallado's improved mode: IAU sidereal time.
+    #[default]
+    Improved,
+}
+
+/// Propagator, gravity constants and operation mode of one propagation,
+/// carried flat in job payloads as `propagator` / `gravity` / `opsmode`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PropagationModel {
+    #[serde(default)]
+    pub propagator: PropagatorKind,
+    #[serde(default)]
+    pub gravity: Gravity,
+    #[serde(default)]
+    pub opsmode: OpsMode,
+}
+
+impl PropagatorKind {
+    /// Wire name, as in payloads.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sgp4 => "sgp4",
+            Self::TwoBody => "two_body",
+            Self::J2Secular => "j2_secular",
+        }
+    }
+}
+
+impl Gravity {
+    /// Wire name, as in payloads and `propagated_windows.gravity`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Wgs72 => "wgs72",
+            Self::Wgs84 => "wgs84",
+        }
+    }
+}
+
+impl OpsMode {
+    /// Wire name, as in payloads and `propagated_windows.opsmode`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Afspc => "afspc",
+            Self::Improved => "improved",
+        }
+    }
+}
+
+impl PropagationModel {
+    /// Canonical label, used in cache keys: `"{gravity}:{opsmode}"` for SGP4
+    /// (e.g. `"wgs72:afspc"`), `"{propagator}:{gravity}"` for the analytic
+    /// propagators, which have no operation mode.
+    pub fn label(&self) -> String {
+        match self.propagator {
+            PropagatorKind::Sgp4 => {
+                format!("{}:{}", self.gravity.as_str(), self.opsmode.as_str())
+            }
+            kind => format!("{}:{}", kind.as_str(), self.gravity.as_str()),
+        }
+    }
+}
+
+/// A parsed TLE with its SGP4 constants, ready to propagate under one
+/// [`PropagationModel`].
+pub struct Propagator {
+    pub elements: Elements,
+    pub constants: Constants,
+    pub model: PropagationModel,
+    /// Set for the analytic propagators, which then replace SGP4 in
+    /// [`Propagator::predict`].
+    analytic: Option<AnalyticOrbit>,
+    /// Set for near-Earth SGP4 orbits; evaluates [`Propagator::predict_grid`]
+    /// in blocks.
+    near_earth: Option<NearEarth>,
+}
+
+impl Propagator {
+    /// Parse a TLE and initialise SGP4 for `model`.
+    ///
+    /// # Errors
+    /// Returns an error if the TLE cannot be parsed or SGP4 initialisation
+    /// fails.
+    pub fn new(name: &str, line1: &str, line2: &str, model: PropagationModel) -> Result<Self> {
+        let elements = Elements::from_tle(
+            Some(name.to_owned()),
+            line1.as_bytes(),
+            line2.as_bytes(),
+        )
+        .context("failed to parse TLE")?;
+        let constants = constants_for(&elements, model).context("failed to initialise SGP4")?;
+        let geopotential = geopotential(model.gravity);
+        let analytic = match model.propagator {
+            PropagatorKind::Sgp4 => None,
+            PropagatorKind::TwoBody => Some(AnalyticOrbit::two_body(&elements, &geopotential)?),
+            PropagatorKind::J2Secular => {
+                Some(AnalyticOrbit::j2_secular(&elements, &geopotential)?)
+            }
+        };
+        let near_earth = match analytic {
+            None => NearEarth::from_constants(&constants),
+            Some(_) => None,
+        };
+        Ok(Self {
+            elements,
+            constants,
+            model,
+            analytic,
+            near_earth,
+        })
+    }
+
+    /// Prediction `minutes` SI minutes after the TLE epoch.
+    ///
+    /// # Errors
+    /// Returns an error if SGP4 diverges.
+    pub fn predict(&self, minutes: f64) -> Result<Prediction> {
+        if let Some(orbit) = &self.analytic {
+            return Ok(orbit.predict(minutes));
+        }
+        let t = MinutesSinceEpoch(minutes);
+        let prediction = match self.model.opsmode {
+            OpsMode::Afspc => self.constants.propagate_afspc_compatibility_mode(t),
+            OpsMode::Improved => self.constants.propagate(t),
+        };
+        prediction.context("SGP4 propagation diverged")
+    }
+
+    /// [`Propagator::predict`] at each of `minutes`, in order.
+    ///
+    /// Near-Earth SGP4 orbits are evaluated [`near_earth::LANES`] instants at
+    /// a time; a lane that fails is re-run through [`Propagator::predict`] so
+    /// its error is the scalar one.
+    pub fn predict_grid(&self, minutes: &[f64]) -> Vec<Result<Prediction>> {
+        let Some(near_earth) = &self.near_earth else {
+            return minutes.iter().map(|&m| self.predict(m)).collect();
+        };
+        let mut predictions = Vec::with_capacity(minutes.len());
+        for chunk in minutes.chunks(near_earth::LANES) {
+            // Pad a short final block with its last instant.
+            let t = std::array::from_fn(|i| chunk[i.min(chunk.len() - 1)]);
+            let block = near_earth.propagate_block(&t);
+            for (i, &m) in chunk.iter().enumerate() {
+                predictions.push(if block.valid[i] {
+                    Ok(Prediction {
+                        position: block.position.map(|axis| axis[i]),
+                        velocity: block.velocity.map(|axis| axis[i]),
+                    })
+                } else {
+                    self.predict(m)
+                });
+            }
+        }
+        predictions
+    }
+
+    /// TEME `(position_km, velocity_km_s)` at an absolute UTC instant.
+    ///
+    /// # Errors
+    /// Returns an error if `at` cannot be expressed relative to the TLE epoch
+    /// or if SGP4 diverges.
+    pub fn state_at(&self, at: &DateTime<Utc>) -> Result<([f64; 3], [f64; 3])> {
+        let prediction = self.predict(minutes_since_epoch(&self.elements, at)?)?;
+        Ok((prediction.position, prediction.velocity))
+    }
+}
+
+/// SGP4 constants for `model`.  The default model is exactly
+/// `Constants::from_elements`.
+fn constants_for(
+    elements: &Elements,
+    model: PropagationModel,
+) -> std::result::Result<Constants, sgp4::ElementsError> {
+    let geopotential = geopotential(model.gravity);
+    let (sidereal, epoch): (fn(f64) -> f64, f64) = match model.opsmode {
+        OpsMode::Afspc => (
+            sgp4::afspc_epoch_to_sidereal_time,
+            elements.epoch_afspc_compatibility_mode(),
+        ),
+        OpsMode::Improved => (sgp4::iau_epoch_to_sidereal_time, elements.epoch()),
+    };
+    Ok(Constants::new(
+        geopotential,
+        sidereal,
+        epoch,
+        elements.drag_term,
+        Orbit::from_kozai_elements(
+            &geopotential,
+            elements.inclination.to_radians(),
+            elements.right_ascension.to_radians(),
+            elements.eccentricity,
+            elements.argument_of_perigee.to_radians(),
+            elements.mean_anomaly.to_radians(),
+            elements.mean_motion * (std::f64::consts::TAU / 1_440.0),
+        )?,
+    )?)
+}
+
+fn geopotential(gravity: Gravity) -> Geopotential {
+    match gravity {
+        Gravity::Wgs72 => sgp4::WGS72,
+        Gravity::Wgs84 => sgp4::WGS84,
+    }
+}
+
+/// Span of one chunk of a window longer than a day, in milliseconds.
+pub const CHUNK_MS: i64 = 86_400_000;
 
 /// Propagate a TLE over a sampled window.
 ///
@@ -37,8 +292,10 @@ use sgp4::{Constants, Elements, MinutesSinceEpoch};
 ///
 /// # Returns
 /// A `Vec<Sample>` with exactly `duration_s / step_s + 1` entries
-/// (inclusive of both endpoints), each at `t = k * step_s` seconds from
-/// `start_at`.
+/// (inclusive of both endpoints), each at `t = k * step_s` SI seconds from
+/// `start_at` (see [`crate::timescale::utc_after_si`]).  A negative
+/// `duration_s` samples backward, `t = -k * step_s`; samples are always
+/// returned earliest first.
 ///
 /// # Errors
 /// Returns an error if the TLE cannot be parsed or if SGP4 diverges for any
@@ -52,6 +309,247 @@ pub fn propagate_window(
     step_s: i64,
     include_velocity: bool,
 ) -> Result<Vec<Sample>> {
+    let samples = propagate_window_ms(
+        name,
+        line1,
+        line2,
+        start_at,
+        0,
+        duration_s * 1_000,
+        step_s * 1_000,
+        include_velocity,
+    )?;
+    Ok(samples
+        .into_iter()
+        .map(|s| Sample { t: s.t / 1_000, ..s })
+        .collect())
+}
+
+/// Propagate a TLE over a window sampled at millisecond resolution (result
+/// format 2).
+///
+/// Samples sit at the [`window_offsets_ms`] after `start_at`, and
+/// [`Sample::t`] is in milliseconds.  [`propagate_window`] is the
+/// whole-second special case.
+///
+/// # Errors
+/// Returns an error if the TLE cannot be parsed or if SGP4 diverges for any
+/// sample.
+#[allow(clippy::too_many_arguments)]
+pub fn propagate_window_ms(
+    name: &str,
+    line1: &str,
+    line2: &str,
+    start_at: &DateTime<Utc>,
+    start_offset_ms: i64,
+    duration_ms: i64,
+    step_ms: i64,
+    include_velocity: bool,
+) -> Result<Vec<Sample>> {
+    let offsets = window_offsets_ms(start_offset_ms, duration_ms, step_ms);
+    propagate_offsets_ms(name, line1, line2, start_at, &offsets, include_velocity)
+}
+
+/// Propagate a TLE over a window centred on `center_at`.
+///
+/// Samples sit at `t = j * step_s` seconds from `center_at` for every integer
+/// `j` with `|j * step_s| ≤ duration_s / 2` (see [`centered_offsets_ms`]),
+/// so the centre is always sampled and the window is symmetric.
+///
+/// # Errors
+/// Returns an error if the TLE cannot be parsed or if SGP4 diverges for any
+/// sample.
+pub fn propagate_window_centered(
+    name: &str,
+    line1: &str,
+    line2: &str,
+    center_at: &DateTime<Utc>,
+    duration_s: i64,
+    step_s: i64,
+    include_velocity: bool,
+) -> Result<Vec<Sample>> {
+    let offsets = centered_offsets_ms(duration_s * 1_000, step_s * 1_000);
+    let samples = propagate_offsets_ms(name, line1, line2, center_at, &offsets, include_velocity)?;
+    Ok(samples
+        .into_iter()
+        .map(|s| Sample { t: s.t / 1_000, ..s })
+        .collect())
+}
+
+/// Offsets in milliseconds of a window anchored at `t = 0`, earliest first.
+///
+/// A forward window (`duration_ms ≥ 0`) runs `start_offset_ms + k * step_ms`;
+/// a backward one runs `start_offset_ms - k * step_ms`.  Either way
+/// `k = 0 ..= |duration_ms| / step_ms`, inclusive of both endpoints.
+pub fn window_offsets_ms(start_offset_ms: i64, duration_ms: i64, step_ms: i64) -> Vec<i64> {
+    let n = duration_ms.abs() / step_ms;
+    if duration_ms >= 0 {
+        (0..=n).map(|k| start_offset_ms + k * step_ms).collect()
+    } else {
+        (0..=n).rev().map(|k| start_offset_ms - k * step_ms).collect()
+    }
+}
+
+/// Offsets in milliseconds of a window centred on `t = 0`: `j * step_ms` for
+/// `|j| ≤ duration_ms / (2 * step_ms)`, earliest first.
+pub fn centered_offsets_ms(duration_ms: i64, step_ms: i64) -> Vec<i64> {
+    let n = duration_ms / (2 * step_ms);
+    (-n..=n).map(|j| j * step_ms).collect()
+}
+
+/// Split a window's [`window_offsets_ms`] into consecutive chunks, each
+/// spanning less than [`CHUNK_MS`] from its first sample (the final chunk
+/// also takes the closing endpoint).  A window of at most one chunk comes
+/// back whole.
+pub fn window_chunks_ms(start_offset_ms: i64, duration_ms: i64, step_ms: i64) -> Vec<Vec<i64>> {
+    let offsets = window_offsets_ms(start_offset_ms, duration_ms, step_ms);
+    let count = ((duration_ms.abs() + CHUNK_MS - 1) / CHUNK_MS).max(1) as usize;
+    let first = offsets[0];
+    let mut chunks = vec![Vec::new(); count];
+    for t in offsets {
+        let index = (((t - first) / CHUNK_MS) as usize).min(count - 1);
+        chunks[index].push(t);
+    }
+    chunks
+}
+
+/// Propagate to `anchor + t` for each offset `t` in milliseconds, returning
+/// [`Sample::t`] in milliseconds.
+pub fn propagate_offsets_ms(
+    name: &str,
+    line1: &str,
+    line2: &str,
+    anchor: &DateTime<Utc>,
+    offsets_ms: &[i64],
+    include_velocity: bool,
+) -> Result<Vec<Sample>> {
+    let propagator = Propagator::new(name, line1, line2, PropagationModel::default())?;
+    sample_offsets_ms(&propagator, anchor, offsets_ms, include_velocity)
+}
+
+/// [`propagate_offsets_ms`] for an already initialised TLE (e.g. from
+/// [`crate::tle_cache`]).
+pub fn sample_offsets_ms(
+    propagator: &Propagator,
+    anchor: &DateTime<Utc>,
+    offsets_ms: &[i64],
+    include_velocity: bool,
+) -> Result<Vec<Sample>> {
+    // Offset the SGP4 time from the anchor, not from each sample's UTC label:
+    // the label of an inserted leap second is the next second's.
+    let base = minutes_since_epoch(&propagator.elements, anchor)?;
+    offsets_ms
+        .iter()
+        .map(|&t_ms| {
+            let prediction = propagator
+                .predict(base + t_ms as f64 / 60_000.0)
+                .with_context(|| format!("propagation failed at t={t_ms}ms"))?;
+            Ok(Sample {
+                t: t_ms,
+                r_km: prediction.position,
+                v_km_s: include_velocity.then_some(prediction.velocity),
+            })
+        })
+        .collect()
+}
+
+/// Propagate a TLE to each instant of an explicit, possibly non-uniform
+/// list, returning one [`EpochSample`] per entry in the same order.
+///
+/// # Errors
+/// Returns an error if the TLE cannot be parsed or if SGP4 diverges at any
+/// epoch.
+pub fn propagate_epochs(
+    name: &str,
+    line1: &str,
+    line2: &str,
+    epochs: &[DateTime<Utc>],
+    include_velocity: bool,
+) -> Result<Vec<EpochSample>> {
+    let propagator = Propagator::new(name, line1, line2, PropagationModel::default())?;
+    sample_epochs(&propagator, epochs, include_velocity)
+}
+
+/// [`propagate_epochs`] for an already initialised TLE.
+pub fn sample_epochs(
+    propagator: &Propagator,
+    epochs: &[DateTime<Utc>],
+    include_velocity: bool,
+) -> Result<Vec<EpochSample>> {
+    epochs
+        .iter()
+        .map(|epoch| {
+            let (position, velocity) = propagator
+                .state_at(epoch)
+                .with_context(|| format!("propagation failed at {epoch}"))?;
+            Ok(EpochSample {
+                epoch: *epoch,
+                r_km: position,
+                v_km_s: include_velocity.then_some(velocity),
+            })
+        })
+        .collect()
+}
+
+/// Propagate many TLEs over the same whole-second window.
+///
+/// The time grid (SI minutes after `start_at`) is built once and shared;
+/// each satellite then needs a single epoch offset, and its inner loop is
+/// [`Propagator::predict_grid`], which evaluates near-Earth orbits in
+/// SIMD-friendly blocks (see [`crate::near_earth`]).  Satellites run in
+/// parallel (rayon), and their SGP4 constants come from [`crate::tle_cache`].
+///
+/// Returns one entry per TLE, in order, each identical in shape to
+/// [`propagate_window`]'s output.  A TLE that fails to parse, or diverges at
+/// any sample, yields an error in its own slot without affecting the rest.
+pub fn propagate_batch(
+    satellites: &[&SatelliteTle],
+    start_at: &DateTime<Utc>,
+    duration_s: i64,
+    step_s: i64,
+    include_velocity: bool,
+    model: PropagationModel,
+) -> Vec<Result<Vec<Sample>>> {
+    let offsets_s: Vec<i64> = window_offsets_ms(0, duration_s * 1_000, step_s * 1_000)
+        .into_iter()
+        .map(|t| t / 1_000)
+        .collect();
+    let grid_min: Vec<f64> = offsets_s.iter().map(|&t| t as f64 / 60.0).collect();
+    satellites
+        .par_iter()
+        .map(|satellite| {
+            let tle = &satellite.tle;
+            let propagator = tle_cache::init_with(
+                satellite.tle_id,
+                &tle.name,
+                &tle.line1,
+                &tle.line2,
+                model,
+            )?;
+            let base = minutes_since_epoch(&propagator.elements, start_at)?;
+            let minutes: Vec<f64> = grid_min.iter().map(|&m| base + m).collect();
+            offsets_s
+                .iter()
+                .zip(propagator.predict_grid(&minutes))
+                .map(|(&t, prediction)| {
+                    let prediction =
+                        prediction.with_context(|| format!("propagation failed at t={t}s"))?;
+                    Ok(Sample {
+                        t,
+                        r_km: prediction.position,
+                        v_km_s: include_velocity.then_some(prediction.velocity),
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Parse a TLE and initialise its SGP4 constants.
+///
+/// # Errors
+/// Returns an error if the TLE cannot be parsed or SGP4 initialisation fails.
+pub fn init(name: &str, line1: &str, line2: &str) -> Result<(Elements, Constants)> {
     // Parse TLE into sgp4 Elements.
     let elements = Elements::from_tle(
         Some(name.to_owned()),
@@ -63,38 +561,69 @@ pub fn propagate_window(
     // Initialise SGP4 constants (Brouwer mean elements).
     let constants = Constants::from_elements(&elements).context("failed to initialise SGP4")?;
 
-    // Number of samples: inclusive on both endpoints.
-    let n_samples = (duration_s / step_s) + 1;
-    let mut samples = Vec::with_capacity(n_samples as usize);
-
-    for k in 0..n_samples {
-        let t_secs = k * step_s;
-        // MinutesSinceEpoch is minutes from the TLE epoch.
-        let sample_time = *start_at + chrono::Duration::seconds(t_secs);
-        let minutes = elements
-            .datetime_to_minutes_since_epoch(&sample_time.naive_utc())
-            .with_context(|| format!("datetime_to_minutes_since_epoch failed at t={t_secs}s"))?;
-        let prediction = constants
-            .propagate(MinutesSinceEpoch(minutes.0))
-            .with_context(|| format!("SGP4 propagation diverged at t={t_secs}s"))?;
-
-        samples.push(Sample {
-            t: t_secs,
-            r_km: prediction.position,
-            v_km_s: if include_velocity {
-                Some(prediction.velocity)
-            } else {
-                None
-            },
-        });
-    }
+    Ok((elements, constants))
+}
+
+/// Propagate an initialised TLE to an absolute UTC instant.
+///
+/// Returns the TEME `(position_km, velocity_km_s)` pair.
+///
+/// # Errors
+/// Returns an error if `at` cannot be expressed relative to the TLE epoch or
+/// if SGP4 diverges.
+pub fn state_at(
+    elements: &Elements,
+    constants: &Constants,
+    at: &DateTime<Utc>,
+) -> Result<([f64; 3], [f64; 3])> {
+    let minutes = minutes_since_epoch(elements, at)?;
+    let prediction = constants
+        .propagate(MinutesSinceEpoch(minutes))
+        .context("SGP4 propagation diverged")?;
+    Ok((prediction.position, prediction.velocity))
+}
+
+/// TEME `(position_km, velocity_km_s)` `seconds` SI seconds after `anchor`.
+///
+/// Use this rather than [`state_at`] on a [`timescale::utc_after_si`] label
+/// for window samples: the label of an inserted leap second is the same as
+/// the next second's, while this counts every SI second once.
+///
+/// # Errors
+/// Returns an error if `anchor` cannot be expressed relative to the TLE epoch
+/// or if SGP4 diverges.
+pub fn state_after_si(
+    elements: &Elements,
+    constants: &Constants,
+    anchor: &DateTime<Utc>,
+    seconds: f64,
+) -> Result<([f64; 3], [f64; 3])> {
+    let minutes = minutes_since_epoch(elements, anchor)? + seconds / 60.0;
+    let prediction = constants
+        .propagate(MinutesSinceEpoch(minutes))
+        .context("SGP4 propagation diverged")?;
+    Ok((prediction.position, prediction.velocity))
+}
 
-    Ok(samples)
+/// SI minutes from the TLE epoch to `at`.
+fn minutes_since_epoch(elements: &Elements, at: &DateTime<Utc>) -> Result<f64> {
+    // MinutesSinceEpoch is minutes from the TLE epoch; chrono ignores leap
+    // seconds, so add any inserted between the epoch and `at`.
+    let minutes = elements
+        .datetime_to_minutes_since_epoch(&at.naive_utc())
+        .context("datetime_to_minutes_since_epoch failed")?;
+    let leap = timescale::scales().leap_seconds.tai_minus_utc(at)
+        - timescale::scales()
+            .leap_seconds
+            .tai_minus_utc(&elements.datetime.and_utc());
+    Ok(minutes.0 + f64::from(leap) / 60.0)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::frames;
+    use crate::job::TleData;
     use chrono::TimeZone;
 
     // ISS TLE from the fallback snapshot (apps/api/data/celestrak-fallback.json).
@@ -142,6 +671,27 @@ mod tests {
         assert_eq!(samples[1].t, 60);
     }
 
+    /// Across the 2016-12-31T23:59:60 leap second every sample is a distinct
+    /// state one SI second after the previous one; the inserted second is
+    /// not collapsed onto the next.
+    #[test]
+    fn window_across_leap_second_has_distinct_states() {
+        let line1 = "1 25544U 98067A   16366.50000000  .00000000  00000-0  00000-0 0  9997";
+        let start = Utc.with_ymd_and_hms(2016, 12, 31, 23, 59, 58).unwrap();
+        let samples = propagate_window("ISS", line1, ISS_LINE2, &start, 4, 1, true).unwrap();
+        assert_eq!(samples.len(), 5);
+        let speed = frames::norm(samples[0].v_km_s.unwrap());
+        for pair in samples.windows(2) {
+            let moved = frames::norm(frames::sub(pair[1].r_km, pair[0].r_km));
+            assert!(
+                (moved - speed).abs() < 0.01 * speed,
+                "t={}→{}: moved {moved} km in one SI second at {speed} km/s",
+                pair[0].t,
+                pair[1].t,
+            );
+        }
+    }
+
     /// `v_km_s` must be `None` for all samples when `include_velocity = false`.
     #[test]
     fn velocity_absent_when_not_requested() {
@@ -172,6 +722,156 @@ mod tests {
             propagate_window("BAD", "not a valid line1", "not a valid line2", &start, 60, 10, true);
         assert!(result.is_err(), "invalid TLE must return Err");
     }
+
+    /// Explicit epochs keep request order and match the window samples at
+    /// the same instants.
+    #[test]
+    fn epochs_match_window_samples() {
+        let start = iss_epoch();
+        let window =
+            propagate_window("ISS", ISS_LINE1, ISS_LINE2, &start, 600, 60, true).unwrap();
+        let epochs = [start + chrono::Duration::seconds(420), start];
+        let states = propagate_epochs("ISS", ISS_LINE1, ISS_LINE2, &epochs, true).unwrap();
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[0].epoch, epochs[0]);
+        assert_eq!(states[0].r_km, window[7].r_km);
+        assert_eq!(states[1].v_km_s, window[0].v_km_s);
+        let no_v = propagate_epochs("ISS", ISS_LINE1, ISS_LINE2, &epochs, false).unwrap();
+        assert!(no_v.iter().all(|s| s.v_km_s.is_none()));
+    }
+
+    /// A backward window mirrors the forward grid, earliest first.
+    #[test]
+    fn backward_window_runs_before_start() {
+        let start = iss_epoch();
+        let back = propagate_window("ISS", ISS_LINE1, ISS_LINE2, &start, -600, 60, true).unwrap();
+        assert_eq!(back.len(), 11);
+        assert_eq!(back[0].t, -600);
+        assert_eq!(back[10].t, 0);
+        let earlier = start - chrono::Duration::seconds(600);
+        let fwd = propagate_window("ISS", ISS_LINE1, ISS_LINE2, &earlier, 600, 60, true).unwrap();
+        for (b, f) in back.iter().zip(&fwd) {
+            assert_eq!(b.r_km, f.r_km);
+        }
+        assert_eq!(window_offsets_ms(0, -150, 60), vec![-120, -60, 0]);
+    }
+
+    /// Chunks partition the window in order, a day apart.
+    #[test]
+    fn chunks_partition_long_windows() {
+        let week = window_chunks_ms(0, 7 * CHUNK_MS, 3_600_000);
+        assert_eq!(week.len(), 7);
+        assert_eq!(week[0].len(), 24);
+        assert_eq!(week[6].len(), 25, "last chunk closes the window");
+        assert_eq!(week[1][0], CHUNK_MS);
+        let flat: Vec<i64> = week.concat();
+        assert_eq!(flat, window_offsets_ms(0, 7 * CHUNK_MS, 3_600_000));
+
+        let back = window_chunks_ms(0, -(CHUNK_MS + 1_800_000), 3_600_000);
+        assert_eq!(back.len(), 2);
+        assert_eq!(back[1].last(), Some(&0));
+        assert_eq!(window_chunks_ms(0, 3_600_000, 60_000).len(), 1);
+    }
+
+    /// A centred window is symmetric about the centre, which it samples.
+    #[test]
+    fn centered_window_is_symmetric() {
+        assert_eq!(centered_offsets_ms(250, 50), vec![-100, -50, 0, 50, 100]);
+        assert_eq!(centered_offsets_ms(200, 50), vec![-100, -50, 0, 50, 100]);
+        let center = iss_epoch();
+        let samples =
+            propagate_window_centered("ISS", ISS_LINE1, ISS_LINE2, &center, 5_400, 10, false)
+                .unwrap();
+        assert_eq!(samples.len(), 541);
+        assert_eq!((samples[0].t, samples[270].t, samples[540].t), (-2_700, 0, 2_700));
+        let at_center = propagate_epochs("ISS", ISS_LINE1, ISS_LINE2, &[center], false).unwrap();
+        assert_eq!(samples[270].r_km, at_center[0].r_km);
+    }
+
+    /// Batch windows match single-window propagation, and a bad TLE only
+    /// fails its own slot.
+    #[test]
+    fn batch_matches_single_windows() {
+        let iss = SatelliteTle {
+            tle_id: 1,
+            tle: TleData {
+                name: "ISS".into(),
+                line1: ISS_LINE1.into(),
+                line2: ISS_LINE2.into(),
+            },
+        };
+        let mut bad = iss.clone();
+        bad.tle_id = 2;
+        bad.tle.line1 = "not a tle".into();
+        let start = iss_epoch();
+        let batch = propagate_batch(&[&iss, &bad, &iss], &start, -1_800, 30, true, PropagationModel::default());
+        assert_eq!(batch.len(), 3);
+        assert!(batch[1].is_err());
+        let single =
+            propagate_window("ISS", ISS_LINE1, ISS_LINE2, &start, -1_800, 30, true).unwrap();
+        for window in [&batch[0], &batch[2]] {
+            let window = window.as_ref().unwrap();
+            assert_eq!(window.len(), single.len());
+            for (b, s) in window.iter().zip(&single) {
+                assert_eq!(b.t, s.t);
+                for k in 0..3 {
+                    assert!((b.r_km[k] - s.r_km[k]).abs() < 1e-6);
+                    assert!((b.v_km_s.unwrap()[k] - s.v_km_s.unwrap()[k]).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    /// The default model is exactly `Constants::from_elements`; the other
+    /// gravity / opsmode combinations stay within a few km of it over an
+    /// orbit.
+    #[test]
+    fn sgp4_models_agree_with_default() {
+        let at = iss_epoch() + chrono::Duration::minutes(90);
+        let (elements, constants) = init("ISS", ISS_LINE1, ISS_LINE2).unwrap();
+        let (r_ref, v_ref) = state_at(&elements, &constants, &at).unwrap();
+        let default = Propagator::new("ISS", ISS_LINE1, ISS_LINE2, PropagationModel::default()).unwrap();
+        assert_eq!(default.state_at(&at).unwrap(), (r_ref, v_ref));
+
+        for gravity in [Gravity::Wgs72, Gravity::Wgs84] {
+            for opsmode in [OpsMode::Afspc, OpsMode::Improved] {
+                let model = PropagationModel {
+                    gravity,
+                    opsmode,
+                    ..PropagationModel::default()
+                };
+                let (r, _) = Propagator::new("ISS", ISS_LINE1, ISS_LINE2, model)
+                    .unwrap()
+                    .state_at(&at)
+                    .unwrap();
+                let miss = (0..3).map(|i| (r[i] - r_ref[i]).powi(2)).sum::<f64>().sqrt();
+                assert!(miss < 5.0, "{} is {miss} km from the default", model.label());
+            }
+        }
+    }
+
+    /// Millisecond windows start at the offset, step by `step_ms`, and agree
+    /// with the whole-second window where their sample times coincide.
+    #[test]
+    fn millisecond_window_matches_second_window() {
+        let start = iss_epoch();
+        let fine =
+            propagate_window_ms("ISS", ISS_LINE1, ISS_LINE2, &start, 0, 2_000, 100, true).unwrap();
+        assert_eq!(fine.len(), 21);
+        assert_eq!(fine[1].t, 100);
+        assert_eq!(fine.last().unwrap().t, 2_000);
+        let coarse =
+            propagate_window("ISS", ISS_LINE1, ISS_LINE2, &start, 2, 1, true).unwrap();
+        assert_eq!(fine[10].r_km, coarse[1].r_km);
+        assert_eq!(fine[20].v_km_s, coarse[2].v_km_s);
+
+        let shifted =
+            propagate_window_ms("ISS", ISS_LINE1, ISS_LINE2, &start, 500, 1_000, 500, false)
+                .unwrap();
+        let ts: Vec<i64> = shifted.iter().map(|s| s.t).collect();
+        assert_eq!(ts, vec![500, 1_000, 1_500]);
+        assert_eq!(shifted[1].r_km, fine[10].r_km);
+    }
 }
 
 // ── Proptest property-based tests ────────────────────────────────────────────
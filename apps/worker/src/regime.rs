@@ -0,0 +1,267 @@
+//! Orbit regime classification from mean elements.
+//!
+//! # Regimes
+//!
+//! Checked in this order; the first match wins:
+//!
+//! | Regime | Rule |
+//! |--------|------|
+//! | `molniya` | `e ≥ 0.5`, period 11–13 h, inclination within 3° of 63.4° |
+//! | `geo` | period within 2 % of a sidereal day and `e < 0.1` (any inclination) |
+//! | `leo` | apogee altitude < 2 000 km |
+//! | `heo` | `e ≥ 0.25`, or perigee above the geostationary altitude |
+//! | `meo` | everything else |
+//!
+//! # Sun-synchronous orbits
+//!
+//! An orbit is sun-synchronous when its J2 nodal precession matches the
+//! Sun's mean motion (`360° / 365.2422 d`) within
+//! [`SSO_RATE_TOLERANCE_DEG_DAY`].  For SSOs the local time of the ascending
+//! node (LTAN) is reported in mean solar hours at the TLE epoch.
+//!
+//! # Repeat ground tracks
+//!
+//! The ground track repeats after `revs` nodal periods and `days` nodal days
+//! when `revs / days` equals the ratio of nodal revolutions per nodal day.
+//! The nodal rate is the Brouwer mean motion (recovered from the TLE's Kozai
+//! mean motion as SGP4 does) plus the J2 secular rates of the argument of
+//! perigee and the mean anomaly.
+//!
+//! The smallest `days ≤ MAX_REPEAT_DAYS` is reported whose equatorial
+//! closure error per cycle is below [`REPEAT_TOLERANCE`] of the spacing
+//! between adjacent tracks of that cycle (`360° / revs`).  The tolerance
+//! therefore tightens with cycle length; a fixed angle would accept a
+//! quarter of arbitrary LEO orbits at some cycle within 30 days.
+
+use crate::elements::{MeanElements, EARTH_RADIUS_KM, J2};
+use crate::sun;
+use serde::{Deserialize, Serialize};
+
+/// Geostationary radius in km.
+pub const GEO_RADIUS_KM: f64 = 42_164.0;
+
+/// Upper bound on apogee altitude for LEO, in km.
+pub const LEO_MAX_APOGEE_ALT_KM: f64 = 2_000.0;
+
+/// Sun's mean motion in degrees per day.
+pub const SUN_MEAN_MOTION_DEG_DAY: f64 = 360.0 / 365.2422;
+
+/// Allowed mismatch between nodal precession and the Sun's motion.
+pub const SSO_RATE_TOLERANCE_DEG_DAY: f64 = 0.05;
+
+/// Longest repeat cycle searched, in days.
+pub const MAX_REPEAT_DAYS: u32 = 30;
+
+/// Maximum equatorial closure error per repeat cycle, as a fraction of the
+/// cycle's track spacing.
+pub const REPEAT_TOLERANCE: f64 = 0.005;
+
+/// Earth rotation rate in degrees per (solar) day.
+const EARTH_ROTATION_DEG_DAY: f64 = 360.985_647_4;
+
+/// Sidereal day in minutes.
+const SIDEREAL_DAY_MIN: f64 = 1_436.068;
+
+/// Coarse orbit family.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Regime {
+    Leo,
+    Meo,
+    Geo,
+    Heo,
+    Molniya,
+}
+
+/// A repeat-ground-track cycle: the track repeats after `revs` orbits in
+/// `days` days.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RepeatGroundTrack {
+    pub revs: u32,
+    pub days: u32,
+}
+
+/// Full classification of one element set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrbitClassification {
+    pub regime: Regime,
+    pub sun_synchronous: bool,
+    /// Local time of the ascending node in mean solar hours `[0, 24)`.
+    /// Present only for sun-synchronous orbits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ltan_hours: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repeat_ground_track: Option<RepeatGroundTrack>,
+    pub period_min: f64,
+    pub perigee_alt_km: f64,
+    pub apogee_alt_km: f64,
+    pub inclination_deg: f64,
+    /// J2 nodal precession in degrees per day.
+    pub raan_rate_deg_day: f64,
+}
+
+/// Classify an element set.
+pub fn classify(m: &MeanElements) -> OrbitClassification {
+    let raan_rate = m.raan_rate_deg_day();
+    let sun_synchronous = (raan_rate - SUN_MEAN_MOTION_DEG_DAY).abs() < SSO_RATE_TOLERANCE_DEG_DAY;
+    let ltan_hours = sun_synchronous.then(|| ltan_hours(m));
+
+    OrbitClassification {
+        regime: regime(m),
+        sun_synchronous,
+        ltan_hours,
+        repeat_ground_track: repeat_ground_track(m),
+        period_min: m.period_min(),
+        perigee_alt_km: m.perigee_alt_km(),
+        apogee_alt_km: m.apogee_alt_km(),
+        inclination_deg: m.inclination_deg,
+        raan_rate_deg_day: raan_rate,
+    }
+}
+
+/// Coarse regime (see the module docs for the rules).
+pub fn regime(m: &MeanElements) -> Regime {
+    let period = m.period_min();
+    if m.eccentricity >= 0.5
+        && (660.0..=780.0).contains(&period)
+        && (m.inclination_deg - 63.4).abs() <= 3.0
+    {
+        Regime::Molniya
+    } else if (period / SIDEREAL_DAY_MIN - 1.0).abs() <= 0.02 && m.eccentricity < 0.1 {
+        Regime::Geo
+    } else if m.apogee_alt_km() < LEO_MAX_APOGEE_ALT_KM {
+        Regime::Leo
+    } else if m.eccentricity >= 0.25 || m.perigee_alt_km() + EARTH_RADIUS_KM > GEO_RADIUS_KM {
+        Regime::Heo
+    } else {
+        Regime::Meo
+    }
+}
+
+/// Local time of the ascending node in mean solar hours at the TLE epoch.
+pub fn ltan_hours(m: &MeanElements) -> f64 {
+    let hour_angle_deg = m.raan_deg - sun::mean_longitude_deg(&m.epoch);
+    (12.0 + hour_angle_deg / 15.0).rem_euclid(24.0)
+}
+
+/// Smallest repeat-ground-track cycle within tolerance, if any.
+pub fn repeat_ground_track(m: &MeanElements) -> Option<RepeatGroundTrack> {
+    let n = brouwer_mean_motion_rev_day(m);
+    let beta = (1.0 - m.eccentricity * m.eccentricity).sqrt();
+    let p = crate::elements::semi_major_axis_km(n) * beta * beta;
+    let cos_i = m.inclination_deg.to_radians().cos();
+    // J2 secular rates in revs per day: ω̇ and Ṁ are added to the mean
+    // motion to get the nodal rate, Ω̇ slows the Earth under the node.
+    let k = 0.75 * n * J2 * (EARTH_RADIUS_KM / p).powi(2);
+    let argp_rate = k * (5.0 * cos_i * cos_i - 1.0);
+    let mean_anomaly_rate = k * beta * (3.0 * cos_i * cos_i - 1.0);
+    let raan_rate = -2.0 * k * cos_i;
+    let nodal_revs_per_day = n + argp_rate + mean_anomaly_rate;
+    let nodal_days_per_day = EARTH_ROTATION_DEG_DAY / 360.0 - raan_rate;
+    let q = nodal_revs_per_day / nodal_days_per_day;
+
+    (1..=MAX_REPEAT_DAYS).find_map(|days| {
+        let revs = (q * f64::from(days)).round();
+        // One rev shifts the track by 360/q degrees; adjacent tracks of the
+        // cycle are 360/revs apart.
+        let closure = (q * f64::from(days) - revs).abs() * revs / q;
+        (revs >= 1.0 && closure < REPEAT_TOLERANCE).then_some(RepeatGroundTrack {
+            revs: revs as u32,
+            days,
+        })
+    })
+}
+
+/// Brouwer mean motion in rev/day from the TLE's Kozai mean motion, as in
+/// SGP4 initialisation.
+fn brouwer_mean_motion_rev_day(m: &MeanElements) -> f64 {
+    let cos_i = m.inclination_deg.to_radians().cos();
+    let beta3 = (1.0 - m.eccentricity * m.eccentricity).powf(1.5);
+    let x = 3.0 * cos_i * cos_i - 1.0;
+    let a1 = crate::elements::semi_major_axis_km(m.mean_motion_rev_day) / EARTH_RADIUS_KM;
+    let d1 = 0.75 * J2 * x / (a1 * a1 * beta3);
+    let a0 = a1 * (1.0 - d1 / 3.0 - d1 * d1 - 134.0 / 81.0 * d1 * d1 * d1);
+    let d0 = 0.75 * J2 * x / (a0 * a0 * beta3);
+    m.mean_motion_rev_day / (1.0 + d0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn elements(mean_motion: f64, e: f64, i: f64) -> MeanElements {
+        MeanElements {
+            epoch: Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap(),
+            semi_major_axis_km: crate::elements::semi_major_axis_km(mean_motion),
+            eccentricity: e,
+            inclination_deg: i,
+            raan_deg: 127.0,
+            arg_perigee_deg: 0.0,
+            mean_anomaly_deg: 0.0,
+            mean_motion_rev_day: mean_motion,
+            mean_motion_dot: 0.0,
+            bstar: 0.0,
+        }
+    }
+
+    /// The curated set: ISS and Starlink are LEO, GPS is MEO.
+    #[test]
+    fn curated_regimes() {
+        assert_eq!(regime(&elements(15.5, 0.0004, 51.64)), Regime::Leo);
+        assert_eq!(regime(&elements(15.06, 0.0001, 53.05)), Regime::Leo);
+        assert_eq!(regime(&elements(2.0056, 0.006, 55.0)), Regime::Meo);
+    }
+
+    #[test]
+    fn geo_molniya_and_heo() {
+        assert_eq!(regime(&elements(1.0027, 0.0002, 0.05)), Regime::Geo);
+        assert_eq!(regime(&elements(2.006, 0.72, 63.4)), Regime::Molniya);
+        // GTO: 10.5 h, e = 0.73, i = 27°.
+        assert_eq!(regime(&elements(2.27, 0.73, 27.0)), Regime::Heo);
+    }
+
+    /// NOAA-19 (~870 km, 99.2°) is sun-synchronous; ISS is not.
+    #[test]
+    fn sun_synchronous_detection() {
+        let noaa19 = classify(&elements(14.125, 0.0014, 99.19));
+        assert!(noaa19.sun_synchronous, "rate {}", noaa19.raan_rate_deg_day);
+        let ltan = noaa19.ltan_hours.expect("SSO must carry LTAN");
+        assert!((0.0..24.0).contains(&ltan));
+
+        let iss = classify(&elements(15.5, 0.0004, 51.64));
+        assert!(!iss.sun_synchronous);
+        assert!(iss.ltan_hours.is_none());
+    }
+
+    /// LTAN moves one hour per 15° of RAAN.
+    #[test]
+    fn ltan_tracks_raan() {
+        let mut m = elements(14.125, 0.0014, 99.19);
+        let base = ltan_hours(&m);
+        m.raan_deg += 15.0;
+        let shifted = ltan_hours(&m);
+        assert!(((shifted - base).rem_euclid(24.0) - 1.0).abs() < 1e-9);
+    }
+
+    /// GPS repeats its ground track every sidereal day (2 revs / 1 day).
+    #[test]
+    fn gps_repeats_daily() {
+        let rgt = repeat_ground_track(&elements(2.005_6, 0.0, 55.0)).expect("GPS must repeat");
+        assert_eq!(rgt, RepeatGroundTrack { revs: 2, days: 1 });
+    }
+
+    /// A Sentinel-2-like orbit phased for 143 revs / 10 days repeats; ISS,
+    /// Starlink and NOAA-19 are not phased for a repeat and must not match
+    /// some cycle by chance.
+    #[test]
+    fn repeat_ground_track_is_selective() {
+        let sentinel2 = repeat_ground_track(&elements(14.308_085_94, 0.000_1, 98.57));
+        assert_eq!(sentinel2, Some(RepeatGroundTrack { revs: 143, days: 10 }));
+        // 0.001 rev/day off the phasing drifts ~10 % of a track spacing per cycle.
+        assert_eq!(repeat_ground_track(&elements(14.309_085_94, 0.000_1, 98.57)), None);
+        assert_eq!(repeat_ground_track(&elements(15.5, 0.0004, 51.64)), None);
+        assert_eq!(repeat_ground_track(&elements(15.06, 0.0001, 53.05)), None);
+        assert_eq!(repeat_ground_track(&elements(14.125, 0.0014, 99.19)), None);
+    }
+}
@@ -0,0 +1,283 @@
+//! TLE consistency audit over the append-only `tles` history.
+//!
+//! For each pair of consecutive element sets `(older, newer)` the older TLE is
+//! propagated forward to the newer TLE's epoch and compared with the newer
+//! TLE evaluated at its own epoch (where SGP4 is most accurate).  The position
+//! difference is expressed in the RIC frame of the newer state (see
+//! [`crate::frames`]).
+//!
+//! # Flagging
+//!
+//! A pair is flagged when its total residual exceeds a threshold.  Callers may
+//! supply a fixed threshold; otherwise it is derived robustly from the
+//! history itself:
+//!
+//! ```text
+//! threshold = max(MIN_FLAG_KM, median + MAD_MULTIPLIER * 1.4826 * MAD)
+//! ```
+//!
+//! A TLE whose every adjacent pair is flagged is reported as a suspect
+//! element set.  TLEs that fail to parse or initialise are reported as
+//! rejected and skipped when forming pairs.
+
+use crate::db::TleRow;
+use crate::frames;
+use crate::propagate::{self, Propagator};
+use crate::tle_cache;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Smallest derived threshold in km, so a very consistent history does not
+/// flag metre-level noise.
+pub const MIN_FLAG_KM: f64 = 1.0;
+
+/// Number of scaled MADs above the median at which a pair is flagged.
+pub const MAD_MULTIPLIER: f64 = 5.0;
+
+/// Prediction residual between two consecutive TLEs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TleResidual {
+    /// `tles.id` of the propagated (older) element set.
+    pub from_tle_id: i64,
+    /// `tles.id` of the reference (newer) element set.
+    pub to_tle_id: i64,
+    pub from_epoch: DateTime<Utc>,
+    pub to_epoch: DateTime<Utc>,
+    /// Seconds between the two epochs.
+    pub dt_s: i64,
+    /// Predicted minus reference position in km: `[radial, in_track, cross_track]`.
+    pub ric_km: [f64; 3],
+    /// Magnitude of `ric_km`.
+    pub total_km: f64,
+    /// `true` when `total_km` exceeds the report threshold.
+    pub flagged: bool,
+}
+
+/// A TLE row that could not be used in the analysis.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RejectedTle {
+    pub tle_id: i64,
+    pub epoch: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// Output of [`analyse`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResidualReport {
+    /// Threshold in km applied to `total_km`.
+    pub threshold_km: f64,
+    /// One entry per consecutive pair of usable TLEs, oldest first.
+    pub residuals: Vec<TleResidual>,
+    /// `tles.id` values whose every adjacent pair is flagged.
+    pub suspect_tle_ids: Vec<i64>,
+    pub rejected: Vec<RejectedTle>,
+}
+
+/// Compute prediction residuals for a TLE history sorted oldest first.
+///
+/// # Arguments
+/// * `history` — TLE rows ordered by ascending epoch (as returned by
+///   [`crate::db::fetch_tle_history`]).
+/// * `flag_km` — fixed flag threshold; `None` derives one from the data.
+pub fn analyse(history: &[TleRow], flag_km: Option<f64>) -> ResidualReport {
+    let mut rejected = Vec::new();
+    let mut usable = Vec::with_capacity(history.len());
+    for row in history {
+        match tle_cache::init(row.id, "", &row.line1, &row.line2) {
+            Ok(parsed) => usable.push((row, parsed)),
+            Err(e) => rejected.push(RejectedTle {
+                tle_id: row.id,
+                epoch: row.epoch,
+                reason: format!("{e:#}"),
+            }),
+        }
+    }
+
+    let mut residuals = Vec::with_capacity(usable.len().saturating_sub(1));
+    for pair in usable.windows(2) {
+        let (older, older_tle) = &pair[0];
+        let (newer, newer_tle) = &pair[1];
+        match pair_residual(older_tle, newer_tle, &newer.epoch) {
+            Ok(ric_km) => residuals.push(TleResidual {
+                from_tle_id: older.id,
+                to_tle_id: newer.id,
+                from_epoch: older.epoch,
+                to_epoch: newer.epoch,
+                dt_s: (newer.epoch - older.epoch).num_seconds(),
+                ric_km,
+                total_km: frames::norm(ric_km),
+                flagged: false,
+            }),
+            Err(e) => rejected.push(RejectedTle {
+                tle_id: older.id,
+                epoch: older.epoch,
+                reason: format!("{e:#}"),
+            }),
+        }
+    }
+
+    let totals: Vec<f64> = residuals.iter().map(|r| r.total_km).collect();
+    let threshold_km = flag_km.unwrap_or_else(|| robust_threshold(&totals, MIN_FLAG_KM));
+    for r in &mut residuals {
+        r.flagged = r.total_km > threshold_km;
+    }
+
+    let suspect_tle_ids = suspects(&residuals);
+    ResidualReport {
+        threshold_km,
+        residuals,
+        suspect_tle_ids,
+        rejected,
+    }
+}
+
+/// RIC residual of the older TLE propagated to `at` against the newer TLE.
+///
+/// Shared with [`crate::maneuver`], which reports it for each event.
+pub(crate) fn pair_residual(older: &Propagator, newer: &Propagator, at: &DateTime<Utc>) -> Result<[f64; 3]> {
+    let (r_pred, v_pred) = propagate::state_at(&older.elements, &older.constants, at)?;
+    let (r_ref, v_ref) = propagate::state_at(&newer.elements, &newer.constants, at)?;
+    Ok(frames::relative_ric(r_ref, v_ref, r_pred, v_pred).0)
+}
+
+/// `max(floor, median + MAD_MULTIPLIER * 1.4826 * MAD)` over `values`.
+///
+/// Shared with [`crate::maneuver`], which applies it to element jumps.
+pub(crate) fn robust_threshold(values: &[f64], floor: f64) -> f64 {
+    if values.is_empty() {
+        return floor;
+    }
+    let med = median(values);
+    let deviations: Vec<f64> = values.iter().map(|t| (t - med).abs()).collect();
+    let mad = median(&deviations);
+    (med + MAD_MULTIPLIER * 1.4826 * mad).max(floor)
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// TLE ids for which every residual pair they take part in is flagged.
+fn suspects(residuals: &[TleResidual]) -> Vec<i64> {
+    let mut ids: Vec<i64> = residuals
+        .iter()
+        .flat_map(|r| [r.from_tle_id, r.to_tle_id])
+        .collect();
+    ids.dedup();
+    ids.into_iter()
+        .filter(|id| {
+            let mut adjacent = residuals
+                .iter()
+                .filter(|r| r.from_tle_id == *id || r.to_tle_id == *id)
+                .peekable();
+            adjacent.peek().is_some() && adjacent.all(|r| r.flagged)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const ISS_LINE1: &str =
+        "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9999";
+    const ISS_LINE2: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000013";
+    // Same orbit one day later, mean anomaly advanced by 15.5 revs (i.e. unchanged).
+    const ISS_LINE1_B: &str =
+        "1 25544U 98067A   26117.50000000  .00016717  00000-0  30442-3 0  9990";
+    const ISS_LINE2_B: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 160.0000 15.50000000000013";
+
+    fn row(id: i64, line1: &str, line2: &str, day: u32) -> TleRow {
+        TleRow {
+            id,
+            norad_id: 25544,
+            line1: line1.to_owned(),
+            line2: line2.to_owned(),
+            epoch: Utc.with_ymd_and_hms(2026, 4, day, 12, 0, 0).unwrap(),
+        }
+    }
+
+    /// N usable TLEs produce N-1 residual pairs in order.
+    #[test]
+    fn one_residual_per_consecutive_pair() {
+        let history = vec![
+            row(1, ISS_LINE1, ISS_LINE2, 26),
+            row(2, ISS_LINE1_B, ISS_LINE2_B, 27),
+        ];
+        let report = analyse(&history, None);
+        assert!(report.rejected.is_empty());
+        assert_eq!(report.residuals.len(), 1);
+        let r = &report.residuals[0];
+        assert_eq!((r.from_tle_id, r.to_tle_id), (1, 2));
+        assert_eq!(r.dt_s, 86_400);
+        assert!((frames::norm(r.ric_km) - r.total_km).abs() < 1e-9);
+    }
+
+    /// Unparseable rows are rejected and skipped when forming pairs.
+    #[test]
+    fn bad_tle_is_rejected() {
+        let history = vec![
+            row(1, ISS_LINE1, ISS_LINE2, 26),
+            row(2, "garbage", "garbage", 27),
+            row(3, ISS_LINE1, ISS_LINE2, 28),
+        ];
+        let report = analyse(&history, None);
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].tle_id, 2);
+        assert_eq!(report.residuals.len(), 1);
+        assert_eq!(report.residuals[0].from_tle_id, 1);
+        assert_eq!(report.residuals[0].to_tle_id, 3);
+    }
+
+    /// A fixed threshold overrides the derived one.
+    #[test]
+    fn fixed_threshold_flags_everything_above_it() {
+        let history = vec![
+            row(1, ISS_LINE1, ISS_LINE2, 26),
+            row(2, ISS_LINE1_B, ISS_LINE2_B, 27),
+        ];
+        let report = analyse(&history, Some(-1.0));
+        assert_eq!(report.threshold_km, -1.0);
+        assert!(report.residuals.iter().all(|r| r.flagged));
+        assert_eq!(report.suspect_tle_ids, vec![1, 2]);
+    }
+
+    /// The derived threshold is robust to a single outlier and never below
+    /// `MIN_FLAG_KM`.
+    #[test]
+    fn derived_threshold_is_robust() {
+        assert_eq!(robust_threshold(&[], MIN_FLAG_KM), MIN_FLAG_KM);
+        assert_eq!(robust_threshold(&[0.1, 0.1, 0.1], MIN_FLAG_KM), MIN_FLAG_KM);
+        let t = robust_threshold(&[2.0, 2.5, 3.0, 2.2, 250.0], MIN_FLAG_KM);
+        assert!(t > 3.0 && t < 250.0, "threshold {t} should isolate the outlier");
+    }
+
+    /// Only TLEs whose every neighbouring pair is flagged are suspects.
+    #[test]
+    fn suspect_requires_all_adjacent_pairs_flagged() {
+        let epoch = Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap();
+        let mk = |from, to, flagged| TleResidual {
+            from_tle_id: from,
+            to_tle_id: to,
+            from_epoch: epoch,
+            to_epoch: epoch,
+            dt_s: 0,
+            ric_km: [0.0; 3],
+            total_km: 0.0,
+            flagged,
+        };
+        let residuals = vec![mk(1, 2, false), mk(2, 3, true), mk(3, 4, true), mk(4, 5, false)];
+        assert_eq!(suspects(&residuals), vec![3]);
+    }
+}
@@ -0,0 +1,153 @@
+//! Catalog snapshot: the position of every tracked object at one instant.
+//!
+//! # Packing
+//!
+//! A catalog browse needs thousands of markers, so the result is columnar
+//! rather than one object per satellite: parallel `norad_ids` / `tle_ids`
+//! arrays and flat `f32` triples (`r_km = [x0, y0, z0, x1, …]`, and the
+//! optional `geodetic = [lat0, lon0, alt0, …]`).  `f32` keeps a metre-level
+//! position at LEO radii, far finer than a marker.
+//!
+//! # Epochs and caching
+//!
+//! Snapshots are keyed by [`crate::hash::compute_snapshot`] and kept in the
+//! Redis hot cache.  A job without an explicit epoch uses
+//! [`aligned_epoch`], the latest multiple of the configured cadence
+//! (`SNAPSHOT_CADENCE_S`), so every request within one cadence period
+//! shares a key; the worker also recomputes that snapshot in the background
+//! each period (see [`crate::worker`]).
+
+use crate::db::TleRow;
+use crate::earth;
+use crate::tle_cache;
+use chrono::{DateTime, TimeZone, Utc};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Positions of a set of satellites at one epoch, packed by column.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Snapshot {
+    /// NORAD catalog number of each propagated satellite.
+    pub norad_ids: Vec<i32>,
+    /// `tles.id` of the element set used for each satellite.
+    pub tle_ids: Vec<i64>,
+    /// TEME positions in km, three values per satellite.
+    pub r_km: Vec<f32>,
+    /// WGS84 `(lat_deg, lon_deg, alt_km)`, three values per satellite, when
+    /// requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geodetic: Option<Vec<f32>>,
+    /// Satellites whose TLE failed to parse or diverged at the epoch.
+    pub failed: Vec<i32>,
+}
+
+impl Snapshot {
+    /// The same snapshot without geodetic coordinates.
+    pub fn without_geodetic(&self) -> Self {
+        Self {
+            geodetic: None,
+            ..self.clone()
+        }
+    }
+}
+
+/// Propagate every TLE to `epoch`, in parallel, keeping input order.  SGP4
+/// constants come from [`crate::tle_cache`], so a refresh re-parses only the
+/// element sets that changed.
+pub fn compute(tles: &[TleRow], epoch: &DateTime<Utc>, geodetic: bool) -> Snapshot {
+    let states: Vec<Option<[f64; 3]>> = tles
+        .par_iter()
+        .map(|row| {
+            let parsed =
+                tle_cache::init(row.id, &row.norad_id.to_string(), &row.line1, &row.line2).ok()?;
+            parsed.state_at(epoch).ok().map(|(r, _)| r)
+        })
+        .collect();
+
+    let mut snapshot = Snapshot {
+        geodetic: geodetic.then(Vec::new),
+        ..Snapshot::default()
+    };
+    for (row, state) in tles.iter().zip(states) {
+        let Some(r) = state else {
+            snapshot.failed.push(row.norad_id);
+            continue;
+        };
+        snapshot.norad_ids.push(row.norad_id);
+        snapshot.tle_ids.push(row.id);
+        snapshot.r_km.extend(r.map(|x| x as f32));
+        if let Some(packed) = snapshot.geodetic.as_mut() {
+            let (r_ecef, _) = earth::teme_to_ecef(r, [0.0; 3], epoch);
+            let (lat, lon, alt) = earth::ecef_to_geodetic(r_ecef);
+            packed.extend([lat as f32, lon as f32, alt as f32]);
+        }
+    }
+    snapshot
+}
+
+/// The latest instant at or before `now` that is a whole multiple of
+/// `cadence_s` seconds since the Unix epoch.  A zero cadence only truncates
+/// to the second.
+pub fn aligned_epoch(now: &DateTime<Utc>, cadence_s: u64) -> DateTime<Utc> {
+    let secs = now.timestamp();
+    let cadence = cadence_s.max(1) as i64;
+    Utc.timestamp_opt(secs - secs.rem_euclid(cadence), 0)
+        .single()
+        .expect("aligned epoch is in range")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::propagate;
+
+    const ISS_LINE1: &str =
+        "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9999";
+    const ISS_LINE2: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000013";
+
+    fn row(id: i64, norad_id: i32, line1: &str) -> TleRow {
+        TleRow {
+            id,
+            norad_id,
+            line1: line1.to_owned(),
+            line2: ISS_LINE2.to_owned(),
+            epoch: Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap(),
+        }
+    }
+
+    /// Packed columns line up, bad TLEs are reported, and geodetic values
+    /// match a single propagation.
+    #[test]
+    fn packs_positions_by_column() {
+        let epoch = Utc.with_ymd_and_hms(2026, 4, 26, 12, 30, 0).unwrap();
+        let tles = [row(1, 25544, ISS_LINE1), row(2, 99999, "garbage"), row(3, 25545, ISS_LINE1)];
+        let snapshot = compute(&tles, &epoch, true);
+        assert_eq!(snapshot.norad_ids, vec![25544, 25545]);
+        assert_eq!(snapshot.tle_ids, vec![1, 3]);
+        assert_eq!(snapshot.failed, vec![99999]);
+        assert_eq!(snapshot.r_km.len(), 6);
+
+        let (elements, constants) = propagate::init("ISS", ISS_LINE1, ISS_LINE2).unwrap();
+        let (r, _) = propagate::state_at(&elements, &constants, &epoch).unwrap();
+        for (packed, exact) in snapshot.r_km.iter().zip(r) {
+            assert!((f64::from(*packed) - exact).abs() < 1e-3);
+        }
+        let geodetic = snapshot.geodetic.as_ref().unwrap();
+        assert_eq!(geodetic.len(), 6);
+        assert!(geodetic[0].abs() <= 51.7, "latitude within the inclination");
+        assert!((380.0..450.0).contains(&geodetic[2]), "ISS altitude {}", geodetic[2]);
+        assert!(snapshot.without_geodetic().geodetic.is_none());
+        assert!(compute(&tles, &epoch, false).geodetic.is_none());
+    }
+
+    /// Epochs snap back to the cadence grid.
+    #[test]
+    fn aligns_to_cadence() {
+        let now = Utc.with_ymd_and_hms(2026, 4, 26, 12, 7, 42).unwrap();
+        let aligned = aligned_epoch(&now, 300);
+        assert_eq!(aligned, Utc.with_ymd_and_hms(2026, 4, 26, 12, 5, 0).unwrap());
+        assert_eq!(aligned_epoch(&aligned, 300), aligned);
+        assert_eq!(aligned_epoch(&now, 0), now);
+    }
+}
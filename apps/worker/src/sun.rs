@@ -0,0 +1,116 @@
+//! Low-precision Sun ephemeris.
+//!
+//! Uses the *Astronomical Almanac* low-precision formulae (accurate to about
+//! 0.01° in direction between 1950 and 2050).  That is far better than the
+//! analyses built on it need — solar beta angle, eclipse entry, local time of
+//! the ascending node — and avoids shipping a planetary ephemeris file.
+//!
+//! The returned vectors are geocentric and referred to the mean equator and
+//! equinox of date, which is within the TEME/J2000 tolerance documented in
+//! [`crate::propagate`].
+
+use chrono::{DateTime, Utc};
+
+/// Astronomical unit in km.
+pub const AU_KM: f64 = 149_597_870.7;
+
+/// Julian date of the J2000.0 epoch.
+const J2000_JD: f64 = 2_451_545.0;
+
+/// Julian date of an instant (UTC, no leap-second correction).
+pub fn julian_date(at: &DateTime<Utc>) -> f64 {
+    // Unix epoch is JD 2440587.5.
+    let unix_s = at.timestamp() as f64 + f64::from(at.timestamp_subsec_nanos()) * 1e-9;
+    2_440_587.5 + unix_s / 86_400.0
+}
+
+/// Geometric mean longitude of the Sun in degrees, in `[0, 360)`.
+///
+/// This is also the right ascension of the fictitious *mean Sun*, the
+/// reference for mean local solar time.
+pub fn mean_longitude_deg(at: &DateTime<Utc>) -> f64 {
+    let n = julian_date(at) - J2000_JD;
+    (280.460 + 0.985_647_4 * n).rem_euclid(360.0)
+}
+
+/// Geocentric Sun position in km.
+pub fn position_km(at: &DateTime<Utc>) -> [f64; 3] {
+    let n = julian_date(at) - J2000_JD;
+    let g = (357.528 + 0.985_600_3 * n).to_radians();
+    let lambda = (mean_longitude_deg(at) + 1.915 * g.sin() + 0.020 * (2.0 * g).sin()).to_radians();
+    let epsilon = (23.439 - 0.000_000_4 * n).to_radians();
+    let r_au = 1.000_14 - 0.016_71 * g.cos() - 0.000_14 * (2.0 * g).cos();
+    let r = r_au * AU_KM;
+    [
+        r * lambda.cos(),
+        r * epsilon.cos() * lambda.sin(),
+        r * epsilon.sin() * lambda.sin(),
+    ]
+}
+
+/// Unit vector from the Earth's centre towards the Sun.
+pub fn direction(at: &DateTime<Utc>) -> [f64; 3] {
+    crate::frames::unit(position_km(at))
+}
+
+/// Whether a satellite at `r_km` is outside the Earth's cylindrical shadow.
+///
+/// Same shadow model as [`crate::beta::eclipse_fraction`]: the umbra is a
+/// cylinder of Earth radius extending away from the Sun.
+pub fn is_sunlit(r_km: [f64; 3], sun_dir: [f64; 3]) -> bool {
+    let along = crate::frames::dot(r_km, sun_dir);
+    if along >= 0.0 {
+        return true;
+    }
+    let perp = crate::frames::sub(r_km, crate::frames::scale(sun_dir, along));
+    crate::frames::norm(perp) > crate::elements::EARTH_RADIUS_KM
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// J2000.0 is 2000-01-01 12:00 (JD 2451545.0).
+    #[test]
+    fn julian_date_at_j2000() {
+        let at = Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+        assert!((julian_date(&at) - J2000_JD).abs() < 1e-9);
+    }
+
+    /// Near the March equinox the Sun is on the equator at RA ≈ 0.
+    #[test]
+    fn sun_at_march_equinox() {
+        let at = Utc.with_ymd_and_hms(2026, 3, 20, 14, 46, 0).unwrap();
+        let d = direction(&at);
+        assert!(d[2].abs() < 0.005, "declination sine {}", d[2]);
+        assert!(d[0] > 0.999, "x component {}", d[0]);
+    }
+
+    /// Near the June solstice the declination is ≈ +23.44°.
+    #[test]
+    fn sun_at_june_solstice() {
+        let at = Utc.with_ymd_and_hms(2026, 6, 21, 8, 24, 0).unwrap();
+        let dec = direction(&at)[2].asin().to_degrees();
+        assert!((dec - 23.44).abs() < 0.05, "declination {dec}");
+    }
+
+    /// Day side is lit; directly behind the Earth is dark; far off-axis
+    /// behind the Earth is lit again.
+    #[test]
+    fn cylindrical_shadow() {
+        let sun = [1.0, 0.0, 0.0];
+        assert!(is_sunlit([7_000.0, 0.0, 0.0], sun));
+        assert!(!is_sunlit([-7_000.0, 0.0, 0.0], sun));
+        assert!(!is_sunlit([-7_000.0, 6_000.0, 0.0], sun));
+        assert!(is_sunlit([-7_000.0, 6_500.0, 0.0], sun));
+    }
+
+    /// Sun distance stays within perihelion/aphelion bounds.
+    #[test]
+    fn sun_distance_is_about_one_au() {
+        let at = Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap();
+        let r = crate::frames::norm(position_km(&at)) / AU_KM;
+        assert!((0.983..=1.017).contains(&r), "r = {r} AU");
+    }
+}
@@ -0,0 +1,375 @@
+//! Time scales: UTC, TAI, TT and UT1.
+//!
+//! All timestamps in the worker are `DateTime<Utc>`, and chrono arithmetic
+//! ignores leap seconds.  This module supplies the corrections:
+//!
+//! - **TAI − UTC** from a leap-second table — built in (through the
+//!   2017-01-01 leap second) or loaded from an IERS/NTP `leap-seconds.list`.
+//! - **TT − TAI** = 32.184 s exactly.
+//! - **UT1 − UTC** from an optional local file, else the EOP series (see
+//!   [`crate::eop`]); `0` when neither is installed (|UT1 − UTC| < 0.9 s by
+//!   definition).
+//!
+//! # Elapsed time
+//!
+//! Window offsets `t` are SI seconds: [`utc_after_si`] maps `start_at + t`
+//! through TAI, so a window straddling a leap second keeps uniform spacing
+//! and its UTC timestamps absorb the extra second.  An instant inside the
+//! inserted second (`23:59:60`) is reported as the following `00:00:00`.
+//!
+//! # Configuration
+//!
+//! The tables used process-wide are [`install`]ed once at startup from the
+//! paths in [`crate::config::Config`]; until then (and in tests) the built-in
+//! leap-second table and zero UT1 − UTC are used.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// TT − TAI in seconds.
+pub const TT_MINUS_TAI_S: f64 = 32.184;
+
+/// Modified Julian Date of the Unix epoch.
+pub const MJD_UNIX_EPOCH: f64 = 40_587.0;
+
+/// Seconds between the NTP epoch (1900-01-01) and the Unix epoch.
+const NTP_UNIX_OFFSET_S: i64 = 2_208_988_800;
+
+/// Built-in leap-second table: `(year, month, TAI − UTC from the 1st)`.
+const BUILTIN_LEAP_SECONDS: [(i32, u32, i32); 28] = [
+    (1972, 1, 10),
+    (1972, 7, 11),
+    (1973, 1, 12),
+    (1974, 1, 13),
+    (1975, 1, 14),
+    (1976, 1, 15),
+    (1977, 1, 16),
+    (1978, 1, 17),
+    (1979, 1, 18),
+    (1980, 1, 19),
+    (1981, 7, 20),
+    (1982, 7, 21),
+    (1983, 7, 22),
+    (1985, 7, 23),
+    (1988, 1, 24),
+    (1990, 1, 25),
+    (1991, 1, 26),
+    (1992, 7, 27),
+    (1993, 7, 28),
+    (1994, 7, 29),
+    (1996, 1, 30),
+    (1997, 7, 31),
+    (1999, 1, 32),
+    (2006, 1, 33),
+    (2009, 1, 34),
+    (2012, 7, 35),
+    (2015, 7, 36),
+    (2017, 1, 37),
+];
+
+/// The scale a result's timestamps are expressed in.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeScale {
+    #[default]
+    Utc,
+    Tai,
+    Tt,
+    Ut1,
+}
+
+/// TAI − UTC steps, oldest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeapSeconds {
+    /// `(UTC instant the value takes effect, TAI − UTC in seconds)`.
+    entries: Vec<(DateTime<Utc>, i32)>,
+}
+
+impl LeapSeconds {
+    /// The table compiled into the worker.
+    pub fn builtin() -> Self {
+        let entries = BUILTIN_LEAP_SECONDS
+            .iter()
+            .map(|&(y, m, dat)| (Utc.with_ymd_and_hms(y, m, 1, 0, 0, 0).unwrap(), dat))
+            .collect();
+        Self { entries }
+    }
+
+    /// Parse an IERS/NTP `leap-seconds.list` file (NTP timestamp and
+    /// TAI − UTC per line; `#` starts a comment).
+    ///
+    /// # Errors
+    /// Returns an error if a data line is malformed or the table is empty or
+    /// out of order.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut entries = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            let data = line.split('#').next().unwrap_or_default().trim();
+            if data.is_empty() {
+                continue;
+            }
+            let mut fields = data.split_whitespace();
+            let (Some(ntp), Some(dat)) = (fields.next(), fields.next()) else {
+                bail!("line {}: expected NTP timestamp and TAI-UTC", n + 1);
+            };
+            let ntp: i64 = ntp.parse().with_context(|| format!("line {}", n + 1))?;
+            let dat: i32 = dat.parse().with_context(|| format!("line {}", n + 1))?;
+            let at = Utc
+                .timestamp_opt(ntp - NTP_UNIX_OFFSET_S, 0)
+                .single()
+                .with_context(|| format!("line {}: timestamp out of range", n + 1))?;
+            entries.push((at, dat));
+        }
+        if entries.is_empty() {
+            bail!("leap-second table is empty");
+        }
+        if entries.windows(2).any(|w| w[0].0 >= w[1].0) {
+            bail!("leap-second table is not in ascending order");
+        }
+        Ok(Self { entries })
+    }
+
+    /// TAI − UTC in seconds at a UTC instant.  Before the first entry the
+    /// first value is used.
+    pub fn tai_minus_utc(&self, at: &DateTime<Utc>) -> i32 {
+        let i = self.entries.partition_point(|(from, _)| from <= at);
+        self.entries[i.saturating_sub(1)].1
+    }
+
+    /// The UTC instant of a TAI instant (carried as a `DateTime<Utc>` label).
+    fn tai_to_utc(&self, tai: DateTime<Utc>) -> DateTime<Utc> {
+        let mut utc = tai - Duration::seconds(i64::from(self.entries[0].1));
+        for &(from, dat) in &self.entries[1..] {
+            let candidate = tai - Duration::seconds(i64::from(dat));
+            if candidate < from {
+                // Inside the inserted second, the previous value overshoots.
+                return utc.min(from);
+            }
+            utc = candidate;
+        }
+        utc
+    }
+}
+
+/// UT1 − UTC series, linearly interpolated and held constant past its ends.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ut1Utc {
+    /// `(MJD, UT1 − UTC in seconds)`, ascending MJD.
+    points: Vec<(f64, f64)>,
+}
+
+impl Ut1Utc {
+    /// Build a series from `(MJD, UT1 − UTC)` points.
+    ///
+    /// # Errors
+    /// Returns an error if the series is empty or not ascending.
+    pub fn new(points: Vec<(f64, f64)>) -> Result<Self> {
+        if points.is_empty() {
+            bail!("UT1-UTC series is empty");
+        }
+        if points.windows(2).any(|w| w[0].0 >= w[1].0) {
+            bail!("UT1-UTC series is not in ascending MJD order");
+        }
+        Ok(Self { points })
+    }
+
+    /// Parse a two-column `MJD UT1-UTC` text file (`#` starts a comment).
+    ///
+    /// # Errors
+    /// Returns an error if a data line is malformed or the series is invalid.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut points = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            let data = line.split('#').next().unwrap_or_default().trim();
+            if data.is_empty() {
+                continue;
+            }
+            let mut fields = data.split_whitespace().map(str::parse::<f64>);
+            match (fields.next(), fields.next()) {
+                (Some(Ok(mjd)), Some(Ok(dut1))) => points.push((mjd, dut1)),
+                _ => bail!("line {}: expected MJD and UT1-UTC", n + 1),
+            }
+        }
+        Self::new(points)
+    }
+
+    /// UT1 − UTC in seconds at a UTC instant.
+    pub fn at(&self, at: &DateTime<Utc>) -> f64 {
+        interpolate(&self.points, mjd(at), |p| p.1)
+    }
+}
+
+/// Linear interpolation over points sorted by `.0`, clamped at both ends.
+pub(crate) fn interpolate<P: Copy>(points: &[(f64, P)], x: f64, value: impl Fn(&(f64, P)) -> f64) -> f64 {
+    let i = points.partition_point(|p| p.0 <= x);
+    if i == 0 {
+        return value(&points[0]);
+    }
+    if i == points.len() {
+        return value(&points[i - 1]);
+    }
+    let (a, b) = (&points[i - 1], &points[i]);
+    let f = (x - a.0) / (b.0 - a.0);
+    value(a) + f * (value(b) - value(a))
+}
+
+/// Modified Julian Date of a UTC instant (UTC days, no leap-second
+/// correction).
+pub fn mjd(at: &DateTime<Utc>) -> f64 {
+    let unix_s = at.timestamp() as f64 + f64::from(at.timestamp_subsec_nanos()) * 1e-9;
+    MJD_UNIX_EPOCH + unix_s / 86_400.0
+}
+
+/// UTC midnight of an integer MJD.
+pub fn mjd_to_utc(mjd: i64) -> Option<DateTime<Utc>> {
+    let date = NaiveDate::from_ymd_opt(1970, 1, 1)? + Duration::days(mjd - MJD_UNIX_EPOCH as i64);
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+}
+
+/// The leap-second table and optional UT1 series used for conversions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeScales {
+    pub leap_seconds: LeapSeconds,
+    pub ut1_utc: Option<Ut1Utc>,
+}
+
+impl TimeScales {
+    /// Built-in leap seconds, no UT1 series.
+    pub fn builtin() -> Self {
+        Self {
+            leap_seconds: LeapSeconds::builtin(),
+            ut1_utc: None,
+        }
+    }
+
+    /// Load from local files, falling back to the built-in leap-second
+    /// table when `leap_seconds_path` is `None`.
+    ///
+    /// # Errors
+    /// Returns an error if a given file cannot be read or parsed.
+    pub fn load(leap_seconds_path: Option<&Path>, ut1_utc_path: Option<&Path>) -> Result<Self> {
+        let read = |path: &Path| {
+            std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+        };
+        let leap_seconds = match leap_seconds_path {
+            Some(path) => LeapSeconds::parse(&read(path)?)
+                .with_context(|| format!("invalid leap-second table {}", path.display()))?,
+            None => LeapSeconds::builtin(),
+        };
+        let ut1_utc = match ut1_utc_path {
+            Some(path) => Some(
+                Ut1Utc::parse(&read(path)?)
+                    .with_context(|| format!("invalid UT1-UTC file {}", path.display()))?,
+            ),
+            None => None,
+        };
+        Ok(Self { leap_seconds, ut1_utc })
+    }
+
+    /// `scale − UTC` in seconds at a UTC instant.
+    pub fn offset_s(&self, scale: TimeScale, at: &DateTime<Utc>) -> f64 {
+        match scale {
+            TimeScale::Utc => 0.0,
+            TimeScale::Tai => f64::from(self.leap_seconds.tai_minus_utc(at)),
+            TimeScale::Tt => f64::from(self.leap_seconds.tai_minus_utc(at)) + TT_MINUS_TAI_S,
+            TimeScale::Ut1 => self.ut1_utc.as_ref().map_or(0.0, |s| s.at(at)),
+        }
+    }
+
+    /// SI seconds elapsed from `from` to `to`.
+    pub fn elapsed_si_s(&self, from: &DateTime<Utc>, to: &DateTime<Utc>) -> f64 {
+        let leap = self.leap_seconds.tai_minus_utc(to) - self.leap_seconds.tai_minus_utc(from);
+        (*to - *from).num_microseconds().unwrap_or(i64::MAX) as f64 * 1e-6 + f64::from(leap)
+    }
+
+    /// The UTC instant `seconds` SI seconds after `start`.
+    pub fn utc_after_si(&self, start: &DateTime<Utc>, seconds: f64) -> DateTime<Utc> {
+        let dat = self.leap_seconds.tai_minus_utc(start);
+        let tai = *start
+            + Duration::microseconds((seconds * 1e6).round() as i64)
+            + Duration::seconds(i64::from(dat));
+        self.leap_seconds.tai_to_utc(tai)
+    }
+}
+
+static INSTALLED: OnceLock<TimeScales> = OnceLock::new();
+
+/// Make `scales` the process-wide tables.  Returns `false` (and keeps the
+/// existing tables) if they were already installed or used.
+pub fn install(scales: TimeScales) -> bool {
+    INSTALLED.set(scales).is_ok()
+}
+
+/// The process-wide tables (built-in until [`install`] is called).
+pub fn scales() -> &'static TimeScales {
+    INSTALLED.get_or_init(TimeScales::builtin)
+}
+
+/// [`TimeScales::utc_after_si`] on the process-wide tables.
+pub fn utc_after_si(start: &DateTime<Utc>, seconds: f64) -> DateTime<Utc> {
+    scales().utc_after_si(start, seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, s).unwrap()
+    }
+
+    #[test]
+    fn offsets_between_scales() {
+        let s = TimeScales::builtin();
+        let at = utc(2026, 4, 26, 12, 0, 0);
+        assert_eq!(s.offset_s(TimeScale::Tai, &at), 37.0);
+        assert_eq!(s.offset_s(TimeScale::Tt, &at), 69.184);
+        assert_eq!(s.offset_s(TimeScale::Ut1, &at), 0.0);
+        assert_eq!(s.offset_s(TimeScale::Tai, &utc(2016, 12, 31, 23, 59, 59)), 36.0);
+    }
+
+    /// Samples across the 2016-12-31 leap second stay one SI second apart;
+    /// the UTC labels absorb the inserted second.
+    #[test]
+    fn si_spacing_across_leap_second() {
+        let s = TimeScales::builtin();
+        let start = utc(2016, 12, 31, 23, 59, 58);
+        let labels: Vec<_> = (0..4).map(|t| s.utc_after_si(&start, f64::from(t))).collect();
+        assert_eq!(
+            labels,
+            [
+                utc(2016, 12, 31, 23, 59, 58),
+                utc(2016, 12, 31, 23, 59, 59),
+                utc(2017, 1, 1, 0, 0, 0), // 23:59:60
+                utc(2017, 1, 1, 0, 0, 0),
+            ]
+        );
+        assert_eq!(s.elapsed_si_s(&start, &labels[3]), 3.0);
+        // Away from a leap second SI and UTC arithmetic agree.
+        let quiet = utc(2026, 4, 26, 0, 0, 0);
+        assert_eq!(s.utc_after_si(&quiet, 86_400.0), quiet + Duration::days(1));
+    }
+
+    #[test]
+    fn parse_leap_seconds_list() {
+        let text = "# comment\n#@ 3960057600\n2272060800\t10\t# 1 Jan 1972\n3692217600\t37\t# 1 Jan 2017\n";
+        let table = LeapSeconds::parse(text).unwrap();
+        assert_eq!(table.tai_minus_utc(&utc(2017, 1, 1, 0, 0, 0)), 37);
+        assert_eq!(table.tai_minus_utc(&utc(2016, 1, 1, 0, 0, 0)), 10);
+        assert!(LeapSeconds::parse("3692217600 37\n2272060800 10\n").is_err());
+        assert!(LeapSeconds::parse("# only comments\n").is_err());
+    }
+
+    #[test]
+    fn ut1_series_interpolates() {
+        let series = Ut1Utc::parse("61000 0.1\n61002 -0.1 # note\n").unwrap();
+        let mid = mjd_to_utc(61_001).unwrap();
+        assert!(series.at(&mid).abs() < 1e-12);
+        assert_eq!(series.at(&mjd_to_utc(60_000).unwrap()), 0.1);
+        assert_eq!(series.at(&mjd_to_utc(62_000).unwrap()), -0.1);
+        assert_eq!(mjd(&mid), 61_001.0);
+    }
+}
@@ -0,0 +1,183 @@
+//! Bounded LRU cache of parsed TLEs and their SGP4 constants.
+//!
+//! Parsing a TLE and running `Constants::from_elements` costs more than a
+//! handful of propagations, and the same `tle_id` arrives again and again
+//! (prefetch, batch and snapshot jobs).  Entries are keyed by `tle_id` *and*
+//! the TLE text, so a reused or corrected id never serves stale elements,
+//! plus the [`PropagationModel`] the constants were built for.  Failed parses are
+//! not cached.
+//!
+//! The lock is held only for the lookup and the insert, never while parsing;
+//! two jobs missing on the same key at once may both parse it.
+//!
+//! Hit and miss counts are kept for the process lifetime (see
+//! [`TleCache::stats`]) and logged with each completed job.
+
+use crate::propagate::{Propagator, PropagationModel};
+use anyhow::Result;
+use hashlink::LruCache;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Entries kept when `TLE_CACHE_CAPACITY` is unset.
+pub const DEFAULT_CAPACITY: usize = 4_096;
+
+/// A parsed TLE and its SGP4 constants, shared between jobs.
+pub type Parsed = Arc<Propagator>;
+
+type Key = (i64, String, PropagationModel);
+
+/// Counters for [`TleCache`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// Fraction of lookups served from the cache (`0` before any lookup).
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// LRU cache of [`Parsed`] TLEs keyed by `(tle_id, line1, line2, model)`.
+pub struct TleCache {
+    entries: Mutex<LruCache<Key, Parsed>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl TleCache {
+    /// An empty cache holding at most `capacity` entries (at least 1).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(capacity.max(1))),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// The parsed TLE for `tle_id` under `model`, parsing and caching it on
+    /// a miss.
+    ///
+    /// # Errors
+    /// Returns an error if the TLE cannot be parsed or SGP4 initialisation
+    /// fails (see [`Propagator::new`]).
+    pub fn get_or_init(
+        &self,
+        tle_id: i64,
+        name: &str,
+        line1: &str,
+        line2: &str,
+        model: PropagationModel,
+    ) -> Result<Parsed> {
+        let key = (tle_id, format!("{line1}\n{line2}"), model);
+        if let Some(parsed) = self.lock().get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(Arc::clone(parsed));
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let parsed = Arc::new(Propagator::new(name, line1, line2, model)?);
+        self.lock().insert(key, Arc::clone(&parsed));
+        Ok(parsed)
+    }
+
+    /// Current counters.
+    pub fn stats(&self) -> CacheStats {
+        let entries = self.lock();
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: entries.len(),
+            capacity: entries.capacity(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache<Key, Parsed>> {
+        // A panic mid-insert leaves the map consistent; keep using it.
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+static INSTALLED: OnceLock<TleCache> = OnceLock::new();
+
+/// Make `cache` the process-wide cache.  Returns `false` (and keeps the
+/// existing cache) if one was already installed or used.
+pub fn install(cache: TleCache) -> bool {
+    INSTALLED.set(cache).is_ok()
+}
+
+/// The process-wide cache ([`DEFAULT_CAPACITY`] entries until [`install`]
+/// is called).
+pub fn current() -> &'static TleCache {
+    INSTALLED.get_or_init(|| TleCache::new(DEFAULT_CAPACITY))
+}
+
+/// [`TleCache::get_or_init`] on the process-wide cache, with the default
+/// [`PropagationModel`].
+///
+/// # Errors
+/// Returns an error if the TLE cannot be parsed or SGP4 initialisation fails.
+pub fn init(tle_id: i64, name: &str, line1: &str, line2: &str) -> Result<Parsed> {
+    init_with(tle_id, name, line1, line2, PropagationModel::default())
+}
+
+/// [`TleCache::get_or_init`] on the process-wide cache.
+///
+/// # Errors
+/// Returns an error if the TLE cannot be parsed or SGP4 initialisation fails.
+pub fn init_with(
+    tle_id: i64,
+    name: &str,
+    line1: &str,
+    line2: &str,
+    model: PropagationModel,
+) -> Result<Parsed> {
+    current().get_or_init(tle_id, name, line1, line2, model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISS_LINE1: &str =
+        "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9999";
+    const ISS_LINE2: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000013";
+
+    /// Repeat lookups hit, new text under the same id misses, the least
+    /// recently used entry is evicted, and failures are not cached.
+    #[test]
+    fn caches_by_id_and_text() {
+        let cache = TleCache::new(2);
+        let d = PropagationModel::default();
+        let first = cache.get_or_init(1, "ISS", ISS_LINE1, ISS_LINE2, d).unwrap();
+        let again = cache.get_or_init(1, "ISS", ISS_LINE1, ISS_LINE2, d).unwrap();
+        assert!(Arc::ptr_eq(&first, &again));
+
+        // Same digit sum, so the line-2 checksum still holds.
+        let edited = ISS_LINE2.replace("51.6400", "51.5500");
+        let other = cache.get_or_init(1, "ISS", ISS_LINE1, &edited, d).unwrap();
+        assert!(!Arc::ptr_eq(&first, &other));
+        cache.get_or_init(2, "ISS", ISS_LINE1, ISS_LINE2, d).unwrap();
+        assert!(cache.get_or_init(3, "BAD", "not a tle", "not a tle", d).is_err());
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 4));
+        assert_eq!((stats.entries, stats.capacity), (2, 2));
+        assert!((stats.hit_rate() - 0.2).abs() < 1e-12);
+
+        // Entry 1 with the original text was evicted by the two newer ones.
+        let reparsed = cache.get_or_init(1, "ISS", ISS_LINE1, ISS_LINE2, d).unwrap();
+        assert!(!Arc::ptr_eq(&first, &reparsed));
+        assert_eq!(TleCache::new(4).stats().hit_rate(), 0.0);
+    }
+}
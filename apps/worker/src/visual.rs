@@ -0,0 +1,255 @@
+//! Naked-eye (optical) visibility of passes.
+//!
+//! A satellite is visible when, at the same instant:
+//!
+//! 1. it is above the observer's elevation mask (see [`crate::passes`]);
+//! 2. it is sunlit ([`crate::sun::is_sunlit`], cylindrical shadow);
+//! 3. the observer's sky is dark enough: the Sun is below the [`Twilight`]
+//!    threshold.
+//!
+//! Each pass is scanned every [`SCAN_STEP_S`] seconds and the edges of the
+//! visible portion are bisected to [`crate::passes::TIME_TOLERANCE_S`].
+//!
+//! # Magnitude
+//!
+//! With a standard magnitude `m₀` (at 1 000 km range and 90° phase angle)
+//! and a diffusely reflecting sphere, the apparent magnitude is
+//!
+//! ```text
+//! m = m₀ + 5 log₁₀(d / 1000 km) − 2.5 log₁₀(sin φ + (π − φ) cos φ)
+//! ```
+//!
+//! where `φ` is the Sun–satellite–observer phase angle (0° = fully lit).
+
+use crate::earth::{Look, Observer};
+use crate::frames;
+use crate::passes::{self, Pass, Tracker};
+use crate::propagate;
+use crate::sun;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Scan interval within a pass, in seconds.
+pub const SCAN_STEP_S: f64 = 10.0;
+
+/// Required sky darkness.  Each level admits itself and anything darker.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Twilight {
+    /// Sun below the horizon (−0.833°, upper limb with refraction).
+    Civil,
+    /// Sun below −6°.
+    #[default]
+    Nautical,
+    /// Sun below −12°.
+    Astronomical,
+}
+
+impl Twilight {
+    /// Highest Sun elevation admitted, in degrees.
+    pub fn max_sun_elevation_deg(self) -> f64 {
+        match self {
+            Self::Civil => -0.833,
+            Self::Nautical => -6.0,
+            Self::Astronomical => -12.0,
+        }
+    }
+}
+
+/// A pass with a naked-eye visible portion.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VisiblePass {
+    #[serde(flatten)]
+    pub pass: Pass,
+    /// First instant the satellite is visible.
+    pub visible_start: DateTime<Utc>,
+    /// Last instant the satellite is visible.
+    pub visible_end: DateTime<Utc>,
+    /// Highest elevation reached while visible.
+    pub max_visible_elevation_deg: f64,
+    /// Brightest (lowest) estimated magnitude while visible.  Absent when no
+    /// standard magnitude was supplied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_magnitude: Option<f64>,
+}
+
+/// Optical state of a satellite at one instant.
+struct Sighting {
+    look: Look,
+    visible: bool,
+    phase_angle_deg: f64,
+}
+
+/// Find passes in `[start, end]` with a naked-eye visible portion.
+///
+/// Passes never visible (daylight, or the satellite in shadow throughout)
+/// are dropped.  A pass whose visible part is split in two (e.g. entering
+/// shadow and leaving it again) reports the envelope of both parts.
+///
+/// # Errors
+/// Returns an error if SGP4 diverges anywhere in the window.
+pub fn visible_passes(
+    tracker: &Tracker<'_>,
+    min_elevation_deg: f64,
+    twilight: Twilight,
+    standard_magnitude: Option<f64>,
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+) -> Result<Vec<VisiblePass>> {
+    let mut out = Vec::new();
+    for pass in passes::find_passes(tracker, min_elevation_deg, start, end)? {
+        let span_s = (pass.los - pass.aos).num_milliseconds() as f64 / 1_000.0;
+        let at = |t: f64| passes::offset(&pass.aos, t);
+        let sight = |t: f64| sighting(tracker, twilight, &pass.aos, t);
+        let indicator = |t: f64| -> Result<f64> {
+            Ok(if sight(t)?.visible { 1.0 } else { -1.0 })
+        };
+
+        let mut first: Option<f64> = None;
+        let mut last: Option<f64> = None;
+        let mut max_el = f64::NEG_INFINITY;
+        let mut min_mag: Option<f64> = None;
+        let (mut prev_t, mut prev_visible) = (0.0, false);
+        let mut t = 0.0;
+        loop {
+            let s = sight(t)?;
+            if s.visible {
+                if first.is_none() {
+                    first = Some(if t == 0.0 { 0.0 } else { passes::bisect(&indicator, prev_t, t)? });
+                }
+                // Provisional end; replaced by a bisected edge if it sets.
+                last = Some(t);
+                max_el = max_el.max(s.look.elevation_deg);
+                if let Some(m0) = standard_magnitude {
+                    let m = magnitude(m0, s.look.range_km, s.phase_angle_deg);
+                    min_mag = Some(min_mag.map_or(m, |b: f64| b.min(m)));
+                }
+            } else if prev_visible {
+                last = Some(passes::bisect(&indicator, prev_t, t)?);
+            }
+            (prev_t, prev_visible) = (t, s.visible);
+            if t >= span_s {
+                break;
+            }
+            t = (t + SCAN_STEP_S).min(span_s);
+        }
+
+        if let (Some(first), Some(last)) = (first, last) {
+            out.push(VisiblePass {
+                visible_start: at(first),
+                visible_end: at(last),
+                max_visible_elevation_deg: max_el,
+                min_magnitude: min_mag,
+                pass,
+            });
+        }
+    }
+    Ok(out)
+}
+
+/// Visibility `seconds` SI seconds after `anchor`.
+fn sighting(
+    tracker: &Tracker<'_>,
+    twilight: Twilight,
+    anchor: &DateTime<Utc>,
+    seconds: f64,
+) -> Result<Sighting> {
+    let (r, v) = propagate::state_after_si(tracker.elements, tracker.constants, anchor, seconds)?;
+    let at = &passes::offset(anchor, seconds);
+    let look = tracker.observer.look(r, v, at);
+    let sun_pos = sun::position_km(at);
+    let sun_dir = frames::unit(sun_pos);
+    let sun_elevation = tracker.observer.look(sun_pos, [0.0; 3], at).elevation_deg;
+    let observer_teme = observer_teme_km(&tracker.observer, at);
+    Ok(Sighting {
+        visible: sun::is_sunlit(r, sun_dir)
+            && sun_elevation <= twilight.max_sun_elevation_deg(),
+        phase_angle_deg: phase_angle_deg(r, sun_pos, observer_teme),
+        look,
+    })
+}
+
+/// Observer position rotated from ECEF into TEME at `at`.
+fn observer_teme_km(observer: &Observer, at: &DateTime<Utc>) -> [f64; 3] {
+    let x = observer.ecef_km();
+    let (s, c) = crate::earth::gmst_rad(at).sin_cos();
+    [c * x[0] - s * x[1], s * x[0] + c * x[1], x[2]]
+}
+
+/// Sun–satellite–observer angle in degrees.
+pub fn phase_angle_deg(sat_km: [f64; 3], sun_km: [f64; 3], observer_km: [f64; 3]) -> f64 {
+    let to_sun = frames::unit(frames::sub(sun_km, sat_km));
+    let to_observer = frames::unit(frames::sub(observer_km, sat_km));
+    frames::dot(to_sun, to_observer).clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+/// Apparent magnitude from a standard magnitude (1 000 km, 90° phase).
+pub fn magnitude(standard_magnitude: f64, range_km: f64, phase_angle_deg: f64) -> f64 {
+    let phi = phase_angle_deg.to_radians();
+    let phase = (phi.sin() + (std::f64::consts::PI - phi) * phi.cos()).max(1e-6);
+    standard_magnitude + 5.0 * (range_km / 1_000.0).log10() - 2.5 * phase.log10()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    const ISS_LINE1: &str =
+        "1 25544U 98067A   26116.50000000  .00016717  00000-0  30442-3 0  9999";
+    const ISS_LINE2: &str =
+        "2 25544  51.6400 127.0000 0004000  20.0000 340.0000 15.50000000000013";
+
+    /// At the reference geometry the magnitude is the standard magnitude;
+    /// doubling the range dims by 5·log₁₀2 ≈ 1.5; full phase brightens.
+    #[test]
+    fn magnitude_reference_points() {
+        assert!((magnitude(-1.8, 1_000.0, 90.0) + 1.8).abs() < 1e-12);
+        assert!((magnitude(-1.8, 2_000.0, 90.0) - (-1.8 + 5.0 * 2f64.log10())).abs() < 1e-12);
+        assert!(magnitude(-1.8, 1_000.0, 0.0) < -1.8);
+        assert!(magnitude(-1.8, 1_000.0, 150.0) > -1.8);
+    }
+
+    #[test]
+    fn phase_angle_geometry() {
+        let sat = [7_000.0, 0.0, 0.0];
+        assert!(phase_angle_deg(sat, [1e8, 0.0, 0.0], [0.0, 0.0, 0.0]) > 179.0);
+        assert!(phase_angle_deg(sat, [-1e8, 0.0, 0.0], [0.0, 0.0, 0.0]) < 1e-6);
+    }
+
+    /// Visible portions lie inside their pass, and stricter twilight never
+    /// yields more visible time than looser.
+    #[test]
+    fn visible_portions_are_within_passes_and_nested() {
+        let (elements, constants) = propagate::init("ISS", ISS_LINE1, ISS_LINE2).unwrap();
+        let tracker = Tracker {
+            elements: &elements,
+            constants: &constants,
+            observer: Observer { lat_deg: 51.5, lon_deg: -0.13, alt_m: 20.0 },
+        };
+        let start = Utc.with_ymd_and_hms(2026, 4, 26, 12, 0, 0).unwrap();
+        let end = start + Duration::days(3);
+        let total = |tw| -> i64 {
+            let found = visible_passes(&tracker, 10.0, tw, Some(-1.8), &start, &end).unwrap();
+            for p in &found {
+                assert!(p.pass.aos <= p.visible_start && p.visible_start <= p.visible_end);
+                assert!(p.visible_end <= p.pass.los);
+                assert!(p.min_magnitude.is_some());
+                assert!(p.max_visible_elevation_deg <= p.pass.max_elevation_deg + 1e-6);
+            }
+            found.iter().map(|p| (p.visible_end - p.visible_start).num_seconds()).sum()
+        };
+        let civil = total(Twilight::Civil);
+        let astronomical = total(Twilight::Astronomical);
+        assert!(civil > 0, "ISS should be visible from London within 3 days");
+        assert!(astronomical <= civil, "{astronomical} > {civil}");
+    }
+
+    #[test]
+    fn twilight_defaults_to_nautical() {
+        assert_eq!(Twilight::default(), Twilight::Nautical);
+        let tw: Twilight = serde_json::from_str(r#""astronomical""#).unwrap();
+        assert_eq!(tw.max_sun_elevation_deg(), -12.0);
+    }
+}
@@ -1,13 +1,81 @@
 //! Redis Streams consumer loop.
 //!
-//! Reads propagation jobs from `stream:propagate` using `XREADGROUP`, calls
-//! the SGP4 propagator, writes the result to Postgres, publishes on the result
-//! channel, and acknowledges the message.
+//! Reads jobs from `stream:propagate` using `XREADGROUP`, parses each into a
+//! typed [`Job`] and dispatches it to its handler, writes any result to
+//! Postgres, publishes on the result channel, and acknowledges the message.
+//!
+//! # Concurrency
+//!
+//! Up to `concurrency` jobs (`WORKER_CONCURRENCY`, see
+//! [`crate::config::Config`]) run at once, each in its own task.  The loop
+//! only claims as many messages as there are free slots, so nothing waits in
+//! this consumer's pending list while other jobs run.  Propagation and other
+//! CPU-bound work runs on tokio's blocking pool (and rayon, for jobs that
+//! fan out internally), never on the async runtime threads.
+//!
+//! The blocking `XREADGROUP` runs on a connection of its own.  Redis holds
+//! back every later command on a connection while one is blocked, so jobs,
+//! the acker and the snapshot refresh share a second connection and their
+//! `PUBLISH`, `XACK` and `SETEX` never wait behind the read.
+//!
+//! Alongside the jobs, a background task recomputes the current catalog
+//! snapshot once per `SNAPSHOT_CADENCE_S`, unless another worker already
+//! cached it.
+//!
+//! Parsed TLEs and their SGP4 constants are shared between jobs through
+//! [`crate::tle_cache`]; each "job complete" log line carries its hit and
+//! miss counts.
+//!
+//! Within a job the order is unchanged: DB write, then publish, then `XACK`.
+//! Jobs finish in any order, but one acker task sends the `XACK`s in stream
+//! order: a job that finishes early waits for every message claimed before
+//! it.  The pending-entries list therefore only ever loses its oldest
+//! entries, and a restarted consumer redelivers everything from the oldest
+//! unfinished job on.  Redelivered jobs that had already finished are
+//! harmless: the DB writes are idempotent, so two jobs for the same `hash`
+//! produce one row.
+//!
+//! # Job kinds
+//!
+//! - `"propagate_window"` — SGP4 sampled window (see [`crate::propagate`]);
+//!   windows longer than a day are stored and published in chunks.
+//! - `"propagate_epochs"` — SGP4 states at an explicit epoch list, kept in
+//!   the hot cache under [`crate::hash::compute_epochs`] and served from it
+//!   on a repeat.
+//! - `"propagate_batch"` — one window for many satellites on a shared time
+//!   grid, each stored under its own [`crate::hash::compute`] key.
+//! - `"snapshot"` — every satellite's position at one epoch, served from
+//!   the hot cache when present (see [`crate::snapshot`]).
+//! - `"tle_residuals"` — TLE-history audit (see [`crate::residuals`]).
+//! - `"detect_maneuvers"` — maneuver detection, persisted to
+//!   `maneuver_events` (see [`crate::maneuver`]).
+//! - `"maneuver_events"` — the stored maneuvers of one satellite.
+//! - `"lifetime"` — reentry epoch and uncertainty band, persisted to
+//!   `reentry_predictions` (see [`crate::lifetime`]).
+//! - `"classify_orbit"` — orbit regime classification (see [`crate::regime`]).
+//! - `"beta_angle"` — solar beta angle series (see [`crate::beta`]).
+//! - `"doppler"` — downlink Doppler table per pass (see [`crate::doppler`]).
+//! - `"intersat_visibility"` — satellite-to-satellite line of sight (see
+//!   [`crate::intersat`]).
+//! - `"visible_passes"` — naked-eye visible passes (see [`crate::visual`]).
+//! - `"relative_motion"` — RIC/LVLH relative motion of two satellites (see
+//!   [`crate::relative`]).
+//! - `"monte_carlo"` — position uncertainty envelope from perturbed TLE
+//!   clones (see [`crate::montecarlo`]).
+//! - `"contact_plan"` — merged ground-station contact schedule (see
+//!   [`crate::contact`]).
+//! - `"footprint"` — ground footprint polygon per sample (see
+//!   [`crate::footprint`]).
 //!
 //! # Error handling
 //!
-//! - **Deserialise failure** — the message is ACKed and an error result is
-//!   published so the FastAPI waiter does not time out.
+//! - **Deserialise failure** — the message is ACKed.  If it carried a
+//!   string `job_id`, an `invalid_payload` error result is published so the
+//!   FastAPI waiter does not time out, including when `kind` is missing or
+//!   not a string.
+//! - **Unknown kind** — an `unsupported_job_kind` error result listing the
+//!   accepted kinds is published, and the message is ACKed.  Nothing falls
+//!   back to `"propagate_window"`.
 //! - **Propagation failure** — same treatment: ACK + error result.
 //! - **DB failure** — logged; error result published; message is still ACKed.
 //! - **Publish failure** — logged; the API timeout (`propagation_timeout`) will
@@ -16,13 +84,45 @@
 //! In all failure cases the message is ACKed to prevent an unbounded pending
 //! list.
 
+use crate::beta;
+use crate::contact::{self, PlanSatellite};
 use crate::db;
-use crate::job::{JobPayload, JobResult, PropagationError};
+use crate::doppler;
+use crate::elements::MeanElements;
+use crate::footprint;
+use crate::hash;
+use crate::intersat::{self, LinkEnd};
+use crate::lifetime;
+use crate::job::{
+    BatchFailure, BatchWindow, BetaAnglePayload, BetaAngleResult, ClassificationResult,
+    ClassifyOrbitPayload, ContactPlanPayload, ContactPlanResult, DetectManeuversPayload,
+    DopplerPayload, DopplerResult, FootprintPayload, FootprintResult, IntersatVisibilityPayload,
+    IntersatVisibilityResult, Job, JobHeader, JobId, JobPayload, JobResult, LifetimePayload,
+    LifetimeResult, ManeuverEventsPayload, ManeuverResult, MonteCarloPayload, MonteCarloResult,
+    PropagateBatchPayload, PropagateBatchResult, PropagateEpochsPayload, PropagateEpochsResult,
+    PropagationError, RESULT_FORMAT_SECONDS, RelativeMotionPayload, RelativeMotionResult, Sample,
+    SatelliteTle, SnapshotPayload, SnapshotResult, TleResidualsPayload, TleResidualsResult,
+    VisiblePassesPayload, VisiblePassesResult, WindowChunk,
+};
+use crate::maneuver;
+use crate::montecarlo;
+use crate::passes::{self, Tracker};
 use crate::propagate;
-use anyhow::Result;
+use crate::regime;
+use crate::relative;
+use crate::residuals;
+use crate::snapshot;
+use crate::timescale::TimeScale;
+use crate::tle_cache;
+use crate::visual;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use redis::aio::MultiplexedConnection;
 use redis::AsyncCommands;
 use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, Semaphore};
 use tracing::{error, info, warn};
 
 const STREAM_KEY: &str = "stream:propagate";
@@ -31,33 +131,68 @@ const GROUP_NAME: &str = "workers";
 const BLOCK_MS: usize = 5_000;
 /// Max messages to consume per XREADGROUP call.
 const COUNT: usize = 10;
+/// Hot-cache key prefix, followed by the result hash.
+const CACHE_PREFIX: &str = "cache:result:";
+/// Hot-cache lifetime of a `"propagate_epochs"` result, in seconds.
+const RESULT_TTL_S: u64 = 300;
+/// Shortest hot-cache lifetime of a snapshot, in seconds.
+const MIN_SNAPSHOT_TTL_S: u64 = 60;
 
 /// Run the consumer loop indefinitely.
 ///
 /// # Arguments
 /// * `pool` — SQLx connection pool for Postgres writes.
-/// * `redis` — multiplexed Redis connection.
+/// * `redis` — Redis client; the loop opens one multiplexed connection for
+///   its blocking reads and another shared by jobs, the acker and the
+///   snapshot refresh.
 /// * `worker_name` — unique name for this instance within the consumer group.
+/// * `concurrency` — most jobs in flight at once (at least 1).
+/// * `snapshot_cadence_s` — catalog snapshot period; `0` disables the
+///   background refresh.
 pub async fn run(
     pool: PgPool,
-    mut redis: MultiplexedConnection,
+    redis: &redis::Client,
     worker_name: &str,
+    concurrency: usize,
+    snapshot_cadence_s: u64,
 ) -> Result<()> {
-    info!(worker_name, "worker started, consuming from stream '{STREAM_KEY}'");
+    let mut reader = redis
+        .get_multiplexed_async_connection()
+        .await
+        .context("failed to connect to Redis for stream reads")?;
+    let redis = redis
+        .get_multiplexed_async_connection()
+        .await
+        .context("failed to connect to Redis for results")?;
+    info!(worker_name, concurrency, "worker started, consuming from stream '{STREAM_KEY}'");
+    let slots = Arc::new(Semaphore::new(concurrency.max(1)));
+    let worker_name: Arc<str> = Arc::from(worker_name);
+    let (claimed_tx, claimed_rx) = mpsc::unbounded_channel();
+    tokio::spawn(ack_in_order(redis.clone(), claimed_rx));
+    if snapshot_cadence_s > 0 {
+        tokio::spawn(refresh_snapshots(pool.clone(), redis.clone(), snapshot_cadence_s));
+    }
 
     loop {
+        // Wait for a free slot, then claim no more messages than there are
+        // free slots; slots only free up while we read, so each claimed
+        // message gets one immediately.
+        let free = {
+            let _slot = slots.acquire().await.context("job semaphore closed")?;
+            slots.available_permits() + 1
+        };
         let messages: Vec<redis::streams::StreamReadReply> = redis::cmd("XREADGROUP")
             .arg("GROUP")
             .arg(GROUP_NAME)
-            .arg(worker_name)
+            .arg(&*worker_name)
             .arg("COUNT")
-            .arg(COUNT)
+            .arg(free.min(COUNT))
             .arg("BLOCK")
             .arg(BLOCK_MS)
             .arg("STREAMS")
             .arg(STREAM_KEY)
             .arg(">")
-            .query_async(&mut redis)
+            .query_async(&mut reader)
             .await
             .unwrap_or_else(|e| {
                 warn!("XREADGROUP failed: {e}");
@@ -67,93 +202,1083 @@ pub async fn run(
         for stream_reply in messages {
             for stream_id_reply in stream_reply.keys {
                 for entry in stream_id_reply.ids {
-                    let msg_id = entry.id.clone();
-                    process_message(&pool, &mut redis, worker_name, &msg_id, &entry).await;
+                    let slot = slots.clone().acquire_owned().await.context("job semaphore closed")?;
+                    let (done_tx, done_rx) = oneshot::channel();
+                    claimed_tx
+                        .send((entry.id.clone(), done_rx))
+                        .context("acker task stopped")?;
+                    let (pool, mut redis, worker_name) =
+                        (pool.clone(), redis.clone(), worker_name.clone());
+                    tokio::spawn(async move {
+                        process_message(
+                            &pool,
+                            &mut redis,
+                            &worker_name,
+                            snapshot_cadence_s,
+                            &entry.id,
+                            &entry,
+                        )
+                        .await;
+                        let _ = done_tx.send(());
+                        drop(slot);
+                    });
                 }
             }
         }
     }
 }
 
-/// Process a single stream entry.
+/// Why a job handler failed: an error code for the API and a human detail.
+struct JobFailure {
+    code: &'static str,
+    detail: String,
+}
+
+/// Process a single stream entry.  The caller acknowledges it once this
+/// returns (see [`ack_in_order`]).
 async fn process_message(
     pool: &PgPool,
     redis: &mut MultiplexedConnection,
     worker_name: &str,
+    snapshot_cadence_s: u64,
     msg_id: &str,
     entry: &redis::streams::StreamId,
 ) {
-    // ── 1. Deserialise ────────────────────────────────────────────────────────
+    // ── 1. Deserialise envelope ──────────────────────────────────────────────
     let payload_str: String = match entry.get("payload") {
         Some(v) => v,
         None => {
             warn!(msg_id, "message missing 'payload' field — ACKing");
-            ack(redis, msg_id).await;
             return;
         }
     };
 
-    let payload: JobPayload = match serde_json::from_str(&payload_str) {
-        Ok(p) => p,
+    let header: JobHeader = match serde_json::from_str(&payload_str) {
+        Ok(h) => h,
         Err(e) => {
-            warn!(msg_id, "failed to deserialise job payload: {e}");
-            // We can't publish a typed error without a job_id; log and ACK.
-            ack(redis, msg_id).await;
+            warn!(msg_id, "failed to deserialise job header: {e}");
+            // Answer on result:{job_id} if the message names its job at all;
+            // without a job_id there is nowhere to publish, so just ACK.
+            if let Ok(JobId { job_id }) = serde_json::from_str(&payload_str) {
+                publish_error(redis, &job_id, "invalid_payload", &e.to_string()).await;
+            }
             return;
         }
     };
 
-    let job_id = payload.job_id.clone();
-    info!(job_id, msg_id, "processing propagation job");
+    let job_id = header.job_id.clone();
+    info!(job_id, msg_id, kind = %header.kind, "processing job");
 
-    // ── 2. Propagate ─────────────────────────────────────────────────────────
-    let samples = match propagate::propagate_window(
-        &payload.tle.name,
-        &payload.tle.line1,
-        &payload.tle.line2,
-        &payload.start_at,
-        payload.duration_s,
-        payload.step_s,
-        payload.include_velocity,
-    ) {
-        Ok(s) => s,
-        Err(e) => {
-            error!(job_id, "SGP4 propagation failed: {e:#}");
-            publish_error(redis, &job_id, "propagation_failed", &format!("{e:#}")).await;
-            ack(redis, msg_id).await;
-            return;
+    // ── 2. Dispatch on kind ──────────────────────────────────────────────────
+    let outcome = if !Job::is_supported(&header.kind) {
+        warn!(job_id, kind = %header.kind, "unsupported job kind");
+        Err(JobFailure {
+            code: "unsupported_job_kind",
+            detail: format!(
+                "unsupported job kind {:?}; expected one of: {}",
+                header.kind,
+                Job::KINDS.join(", ")
+            ),
+        })
+    } else {
+        match parse_payload(&job_id, &payload_str) {
+            Ok(job) => dispatch(pool, redis, snapshot_cadence_s, &job_id, job).await,
+            Err(failure) => Err(failure),
+        }
+    };
+
+    // ── 3. Publish result ────────────────────────────────────────────────────
+    match outcome {
+        Ok(result) => publish_result(redis, &job_id, &result).await,
+        Err(failure) => publish_error(redis, &job_id, failure.code, &failure.detail).await,
+    }
+
+    let tle_cache = tle_cache::current().stats();
+    info!(
+        job_id,
+        msg_id,
+        worker_name,
+        tle_cache_hits = tle_cache.hits,
+        tle_cache_misses = tle_cache.misses,
+        tle_cache_hit_rate = tle_cache.hit_rate(),
+        "job complete"
+    );
+}
+
+/// The handler registry: one arm per [`Job`] kind.  The match is exhaustive,
+/// so a new variant does not compile until it has a handler here.
+async fn dispatch(
+    pool: &PgPool,
+    redis: &mut MultiplexedConnection,
+    snapshot_cadence_s: u64,
+    job_id: &str,
+    job: Job,
+) -> Result<JobResult, JobFailure> {
+    match job {
+        Job::PropagateWindow(payload) => handle_propagate_window(pool, redis, job_id, payload).await,
+        Job::PropagateEpochs(payload) => handle_propagate_epochs(redis, job_id, payload).await,
+        Job::PropagateBatch(payload) => handle_propagate_batch(pool, job_id, payload).await,
+        Job::Snapshot(payload) => {
+            handle_snapshot(pool, redis, job_id, payload, snapshot_cadence_s).await
         }
+        Job::TleResiduals(payload) => handle_tle_residuals(pool, job_id, payload).await,
+        Job::DetectManeuvers(payload) => handle_detect_maneuvers(pool, job_id, payload).await,
+        Job::ManeuverEvents(payload) => handle_maneuver_events(pool, job_id, payload).await,
+        Job::Lifetime(payload) => handle_lifetime(pool, job_id, payload).await,
+        Job::ClassifyOrbit(payload) => run_blocking(job_id, payload, handle_classify_orbit).await,
+        Job::BetaAngle(payload) => run_blocking(job_id, payload, handle_beta_angle).await,
+        Job::Doppler(payload) => run_blocking(job_id, payload, handle_doppler).await,
+        Job::Footprint(payload) => run_blocking(job_id, payload, handle_footprint).await,
+        Job::VisiblePasses(payload) => run_blocking(job_id, payload, handle_visible_passes).await,
+        Job::ContactPlan(payload) => run_blocking(job_id, payload, handle_contact_plan).await,
+        Job::RelativeMotion(payload) => run_blocking(job_id, payload, handle_relative_motion).await,
+        Job::MonteCarlo(payload) => run_blocking(job_id, payload, handle_monte_carlo).await,
+        Job::IntersatVisibility(payload) => {
+            run_blocking(job_id, payload, handle_intersat_visibility).await
+        }
+    }
+}
+
+/// Run a synchronous handler on the blocking pool.
+async fn run_blocking<T: Send + 'static>(
+    job_id: &str,
+    payload: T,
+    handler: fn(&str, T) -> Result<JobResult, JobFailure>,
+) -> Result<JobResult, JobFailure> {
+    let id = job_id.to_owned();
+    tokio::task::spawn_blocking(move || handler(&id, payload))
+        .await
+        .unwrap_or_else(|e| {
+            error!(job_id, "job task panicked: {e}");
+            Err(JobFailure {
+                code: "propagation_failed",
+                detail: format!("worker task panicked: {e}"),
+            })
+        })
+}
+
+/// Run CPU-bound work from an async handler on the blocking pool, keeping the
+/// runtime free for other jobs' I/O.  A panic surfaces as an error.
+async fn blocking<T: Send + 'static>(work: impl FnOnce() -> Result<T> + Send + 'static) -> Result<T> {
+    tokio::task::spawn_blocking(work).await.context("worker task panicked")?
+}
+
+/// Deserialise a [`Job`], mapping failure to `invalid_payload`.
+fn parse_payload<T: serde::de::DeserializeOwned>(
+    job_id: &str,
+    payload_str: &str,
+) -> Result<T, JobFailure> {
+    serde_json::from_str(payload_str).map_err(|e| {
+        warn!(job_id, "failed to deserialise job payload: {e}");
+        JobFailure {
+            code: "invalid_payload",
+            detail: e.to_string(),
+        }
+    })
+}
+
+/// Log a failed `what` propagation of `job_id` and report it as
+/// `propagation_failed`.
+fn propagation_failed<'a>(
+    job_id: &'a str,
+    what: &'a str,
+) -> impl Fn(anyhow::Error) -> JobFailure + 'a {
+    move |e| {
+        error!(job_id, "{what} propagation failed: {e:#}");
+        JobFailure {
+            code: "propagation_failed",
+            detail: format!("{e:#}"),
+        }
+    }
+}
+
+/// `"propagate_window"`: propagate, persist to `propagated_windows`.
+///
+/// Windows longer than a day are computed, stored and published chunk by
+/// chunk (see [`propagate_chunks`]) before the final result, which then
+/// carries no samples.
+async fn handle_propagate_window(
+    pool: &PgPool,
+    redis: &mut MultiplexedConnection,
+    job_id: &str,
+    payload: JobPayload,
+) -> Result<JobResult, JobFailure> {
+    payload.validate().map_err(|detail| JobFailure {
+        code: "invalid_payload",
+        detail,
+    })?;
+
+    let (anchor, centered) = payload.anchor().map_err(|detail| JobFailure {
+        code: "invalid_payload",
+        detail,
+    })?;
+    let (result_format, model) = (payload.result_format(), payload.model);
+    let (step_ms, start_offset_ms) = (payload.step_ms, payload.start_offset_ms);
+    let (samples, chunk_count) = if payload.is_chunked() {
+        let count = propagate_chunks(pool, redis, job_id, &payload, &anchor).await?;
+        (Vec::new(), Some(count))
+    } else {
+        let (window, at) = (payload.clone(), anchor);
+        let samples = blocking(move || window_samples(&window, &at, centered))
+            .await
+            .map_err(propagation_failed(job_id, "SGP4"))?;
+        (samples, None)
     };
 
-    // ── 3. Build result ───────────────────────────────────────────────────────
-    let result = db::build_result(
-        job_id.clone(),
+    let mut result = db::build_result(
+        payload.job_id,
         payload.tle_id,
-        payload.hash.clone(),
-        payload.frame.clone(),
-        payload.start_at,
+        payload.hash,
+        payload.frame,
+        anchor,
         payload.duration_s,
         payload.step_s,
         payload.include_velocity,
         samples,
     );
+    result.centered = centered;
+    result.model = model;
+    result.result_format = result_format;
+    result.step_ms = step_ms;
+    result.start_offset_ms = start_offset_ms;
+    result.chunk_count = chunk_count;
 
-    // ── 4. Persist ────────────────────────────────────────────────────────────
     if let Err(e) = db::insert_window(pool, &result).await {
         error!(job_id, "DB insert failed: {e:#}");
-        // Publish an error so the API waiter doesn't time out; ACK anyway.
-        publish_error(redis, &job_id, "propagation_failed", &format!("DB error: {e:#}")).await;
-        ack(redis, msg_id).await;
-        return;
+        // Publish an error so the API waiter doesn't time out.
+        return Err(JobFailure {
+            code: "propagation_failed",
+            detail: format!("DB error: {e:#}"),
+        });
+    }
+
+    Ok(JobResult::Ok(Box::new(result)))
+}
+
+/// Propagate a window of at most a day in one piece: centred, millisecond
+/// (format 2) or whole-second, with the parsed TLE from [`tle_cache`].
+fn window_samples(payload: &JobPayload, anchor: &DateTime<Utc>, centered: bool) -> Result<Vec<Sample>> {
+    let tle = &payload.tle;
+    let parsed =
+        tle_cache::init_with(payload.tle_id, &tle.name, &tle.line1, &tle.line2, payload.model)?;
+    let offsets = if centered {
+        propagate::centered_offsets_ms(payload.duration_s * 1_000, payload.step_s * 1_000)
+    } else {
+        propagate::window_offsets_ms(
+            payload.start_offset_ms.unwrap_or(0),
+            payload.duration_s * 1_000,
+            payload.effective_step_ms(),
+        )
+    };
+    let mut samples =
+        propagate::sample_offsets_ms(&parsed, anchor, &offsets, payload.include_velocity)?;
+    if payload.result_format() == RESULT_FORMAT_SECONDS {
+        for s in &mut samples {
+            s.t /= 1_000;
+        }
+    }
+    Ok(samples)
+}
+
+/// Propagate a long window one day-long chunk at a time, storing each chunk
+/// in `propagated_window_chunks` and publishing it on `result:{job_id}` so
+/// neither the worker nor the subscriber holds the whole window.
+///
+/// Returns the number of chunks published.
+async fn propagate_chunks(
+    pool: &PgPool,
+    redis: &mut MultiplexedConnection,
+    job_id: &str,
+    payload: &JobPayload,
+    anchor: &DateTime<Utc>,
+) -> Result<usize, JobFailure> {
+    let in_seconds = payload.result_format() == RESULT_FORMAT_SECONDS;
+    let chunks = propagate::window_chunks_ms(
+        payload.start_offset_ms.unwrap_or(0),
+        payload.duration_s * 1_000,
+        payload.effective_step_ms(),
+    );
+    let chunk_count = chunks.len();
+    for (chunk_index, offsets) in chunks.into_iter().enumerate() {
+        let (tle_id, tle, at) = (payload.tle_id, payload.tle.clone(), *anchor);
+        let (include_velocity, model) = (payload.include_velocity, payload.model);
+        let mut samples = blocking(move || {
+            let parsed = tle_cache::init_with(tle_id, &tle.name, &tle.line1, &tle.line2, model)?;
+            propagate::sample_offsets_ms(&parsed, &at, &offsets, include_velocity)
+        })
+        .await
+        .map_err(|e| {
+            error!(job_id, chunk_index, "SGP4 propagation failed: {e:#}");
+            JobFailure {
+                code: "propagation_failed",
+                detail: format!("{e:#}"),
+            }
+        })?;
+        if in_seconds {
+            for s in &mut samples {
+                s.t /= 1_000;
+            }
+        }
+        let chunk = WindowChunk {
+            job_id: payload.job_id.clone(),
+            hash: payload.hash.clone(),
+            chunk_index,
+            chunk_count,
+            samples,
+        };
+        if let Err(e) = db::insert_window_chunk(pool, payload.tle_id, &chunk).await {
+            error!(job_id, chunk_index, "DB insert failed: {e:#}");
+            return Err(JobFailure {
+                code: "propagation_failed",
+                detail: format!("DB error: {e:#}"),
+            });
+        }
+        publish_result(redis, job_id, &JobResult::Chunk(Box::new(chunk))).await;
+    }
+    Ok(chunk_count)
+}
+
+/// `"propagate_epochs"`: propagate to each instant of an explicit list,
+/// served from the hot cache when the same list was propagated within
+/// [`RESULT_TTL_S`].
+async fn handle_propagate_epochs(
+    redis: &mut MultiplexedConnection,
+    job_id: &str,
+    payload: PropagateEpochsPayload,
+) -> Result<JobResult, JobFailure> {
+    payload.validate().map_err(|detail| JobFailure {
+        code: "invalid_payload",
+        detail,
+    })?;
+
+    let key = hash::compute_epochs(
+        payload.tle_id,
+        &payload.epochs,
+        &payload.frame,
+        payload.include_velocity,
+        payload.model,
+    );
+    if let Some(mut cached) = cached_result::<PropagateEpochsResult>(redis, &key).await {
+        info!(job_id, hash = %key, "epochs served from cache");
+        cached.job_id = payload.job_id;
+        return Ok(JobResult::Epochs(Box::new(cached)));
+    }
+
+    let (tle_id, tle, model) = (payload.tle_id, payload.tle.clone(), payload.model);
+    let (epochs, include_velocity) = (payload.epochs.clone(), payload.include_velocity);
+    let samples = blocking(move || {
+        let parsed = tle_cache::init_with(tle_id, &tle.name, &tle.line1, &tle.line2, model)?;
+        propagate::sample_epochs(&parsed, &epochs, include_velocity)
+    })
+    .await
+    .map_err(propagation_failed(job_id, "SGP4"))?;
+
+    let result = PropagateEpochsResult {
+        hash: key,
+        job_id: payload.job_id,
+        tle_id: payload.tle_id,
+        frame: payload.frame,
+        include_velocity: payload.include_velocity,
+        model: payload.model,
+        time_scale: TimeScale::Utc,
+        samples,
+        computed_at: Utc::now(),
+    };
+    // A failed SETEX only costs the next request a recomputation.
+    if let Err(e) = cache_result(redis, &result.hash, &result, RESULT_TTL_S).await {
+        warn!(job_id, "caching epochs result failed: {e:#}");
     }
+    Ok(JobResult::Epochs(Box::new(result)))
+}
+
+/// `"propagate_batch"`: one window for many satellites, each persisted to
+/// `propagated_windows` under its own [`hash::compute`] key so later
+/// single-window requests for any of them hit the cache.
+///
+/// A satellite that fails to propagate is reported in `failures`; the rest
+/// of the batch still succeeds.
+async fn handle_propagate_batch(
+    pool: &PgPool,
+    job_id: &str,
+    payload: PropagateBatchPayload,
+) -> Result<JobResult, JobFailure> {
+    payload.validate().map_err(|detail| JobFailure {
+        code: "invalid_payload",
+        detail,
+    })?;
+
+    let batch = payload.clone();
+    let outcomes = blocking(move || {
+        let satellites: Vec<&SatelliteTle> = batch.satellites.iter().collect();
+        Ok(propagate::propagate_batch(
+            &satellites,
+            &batch.start_at,
+            batch.duration_s,
+            batch.step_s,
+            batch.include_velocity,
+            batch.model,
+        ))
+    })
+    .await
+    .map_err(propagation_failed(job_id, "batch"))?;
+
+    let (mut windows, mut failures) = (Vec::new(), Vec::new());
+    for (satellite, outcome) in payload.satellites.iter().zip(outcomes) {
+        let samples = match outcome {
+            Ok(samples) => samples,
+            Err(e) => {
+                warn!(job_id, tle_id = satellite.tle_id, "SGP4 propagation failed: {e:#}");
+                failures.push(BatchFailure {
+                    tle_id: satellite.tle_id,
+                    detail: format!("{e:#}"),
+                });
+                continue;
+            }
+        };
+        let mut window = db::build_result(
+            payload.job_id.clone(),
+            satellite.tle_id,
+            hash::compute(
+                satellite.tle_id,
+                &payload.start_at,
+                payload.duration_s,
+                payload.step_s,
+                &payload.frame,
+                payload.include_velocity,
+                payload.model,
+            ),
+            payload.frame.clone(),
+            payload.start_at,
+            payload.duration_s,
+            payload.step_s,
+            payload.include_velocity,
+            samples,
+        );
+        window.model = payload.model;
+        if let Err(e) = db::insert_window(pool, &window).await {
+            error!(job_id, tle_id = satellite.tle_id, "DB insert failed: {e:#}");
+            return Err(JobFailure {
+                code: "propagation_failed",
+                detail: format!("DB error: {e:#}"),
+            });
+        }
+        windows.push(BatchWindow {
+            tle_id: window.tle_id,
+            hash: window.hash,
+            samples: window.samples,
+        });
+    }
+
+    Ok(JobResult::Batch(Box::new(PropagateBatchResult {
+        job_id: payload.job_id,
+        frame: payload.frame,
+        start_at: payload.start_at,
+        duration_s: payload.duration_s,
+        step_s: payload.step_s,
+        include_velocity: payload.include_velocity,
+        model: payload.model,
+        time_scale: TimeScale::Utc,
+        windows,
+        failures,
+        computed_at: Utc::now(),
+    })))
+}
 
-    // ── 5. Publish result ────────────────────────────────────────────────────
-    let result_json = match serde_json::to_string(&JobResult::Ok(Box::new(result))) {
+/// `"snapshot"`: the latest TLE of every satellite propagated to one epoch,
+/// from the hot cache when another job or the background refresh already
+/// computed it.
+async fn handle_snapshot(
+    pool: &PgPool,
+    redis: &mut MultiplexedConnection,
+    job_id: &str,
+    payload: SnapshotPayload,
+    cadence_s: u64,
+) -> Result<JobResult, JobFailure> {
+    let epoch = payload
+        .epoch
+        .unwrap_or_else(|| snapshot::aligned_epoch(&Utc::now(), cadence_s));
+
+    let key = hash::compute_snapshot(&epoch, payload.geodetic);
+    if let Some(mut cached) = cached_result::<SnapshotResult>(redis, &key).await {
+        info!(job_id, hash = %key, "snapshot served from cache");
+        cached.job_id = payload.job_id;
+        return Ok(JobResult::Snapshot(Box::new(cached)));
+    }
+
+    let result = compute_snapshot(pool, redis, &payload.job_id, epoch, payload.geodetic, cadence_s)
+        .await
+        .map_err(|e| {
+            error!(job_id, "snapshot failed: {e:#}");
+            JobFailure {
+                code: "propagation_failed",
+                detail: format!("{e:#}"),
+            }
+        })?;
+    Ok(JobResult::Snapshot(Box::new(result)))
+}
+
+/// Propagate the catalog to `epoch` and cache the snapshot both with and
+/// without geodetic coordinates, returning the variant asked for.
+async fn compute_snapshot(
+    pool: &PgPool,
+    redis: &mut MultiplexedConnection,
+    job_id: &str,
+    epoch: DateTime<Utc>,
+    geodetic: bool,
+    cadence_s: u64,
+) -> Result<SnapshotResult> {
+    let tles = db::fetch_latest_tles(pool).await?;
+    let full = blocking(move || Ok(snapshot::compute(&tles, &epoch, true))).await?;
+    let computed_at = Utc::now();
+    let variant = |with_geodetic: bool| SnapshotResult {
+        job_id: job_id.to_owned(),
+        hash: hash::compute_snapshot(&epoch, with_geodetic),
+        epoch,
+        frame: "eci_j2000".to_owned(),
+        time_scale: TimeScale::Utc,
+        snapshot: if with_geodetic {
+            full.clone()
+        } else {
+            full.without_geodetic()
+        },
+        computed_at,
+    };
+    let (wanted, other) = (variant(geodetic), variant(!geodetic));
+    let ttl_s = cadence_s.max(MIN_SNAPSHOT_TTL_S);
+    for cached in [&wanted, &other] {
+        cache_result(redis, &cached.hash, cached, ttl_s).await?;
+    }
+    Ok(wanted)
+}
+
+/// `SETEX` a result into the hot cache under `cache:result:{key}`.
+async fn cache_result<T: serde::Serialize>(
+    redis: &mut MultiplexedConnection,
+    key: &str,
+    result: &T,
+    ttl_s: u64,
+) -> Result<()> {
+    let json = serde_json::to_string(result).context("failed to serialise result")?;
+    redis
+        .set_ex::<_, _, ()>(format!("{CACHE_PREFIX}{key}"), json, ttl_s)
+        .await
+        .context("SETEX result failed")
+}
+
+/// A cached result, if present and readable.
+async fn cached_result<T: serde::de::DeserializeOwned>(
+    redis: &mut MultiplexedConnection,
+    key: &str,
+) -> Option<T> {
+    let json: Option<String> = redis
+        .get(format!("{CACHE_PREFIX}{key}"))
+        .await
+        .unwrap_or_else(|e| {
+            warn!(hash = key, "GET cached result failed: {e}");
+            None
+        });
+    serde_json::from_str(&json?).ok()
+}
+
+/// Recompute the current snapshot at each cadence boundary, skipping epochs
+/// another worker already cached.
+async fn refresh_snapshots(pool: PgPool, mut redis: MultiplexedConnection, cadence_s: u64) {
+    loop {
+        let epoch = snapshot::aligned_epoch(&Utc::now(), cadence_s);
+        let key = hash::compute_snapshot(&epoch, true);
+        if cached_result::<SnapshotResult>(&mut redis, &key).await.is_none() {
+            match compute_snapshot(&pool, &mut redis, "", epoch, true, cadence_s).await {
+                Ok(result) => {
+                    info!(%epoch, count = result.snapshot.norad_ids.len(), "snapshot refreshed")
+                }
+                Err(e) => warn!(%epoch, "snapshot refresh failed: {e:#}"),
+            }
+        }
+        let next = epoch + chrono::Duration::seconds(cadence_s as i64);
+        tokio::time::sleep((next - Utc::now()).to_std().unwrap_or(Duration::ZERO)).await;
+    }
+}
+
+/// `"tle_residuals"`: audit the stored TLE history of one satellite.
+async fn handle_tle_residuals(
+    pool: &PgPool,
+    job_id: &str,
+    payload: TleResidualsPayload,
+) -> Result<JobResult, JobFailure> {
+    let history = db::fetch_tle_history(pool, payload.norad_id, payload.effective_limit())
+        .await
+        .map_err(|e| {
+            error!(job_id, "TLE history query failed: {e:#}");
+            JobFailure {
+                code: "residuals_failed",
+                detail: format!("DB error: {e:#}"),
+            }
+        })?;
+    if history.len() < 2 {
+        return Err(JobFailure {
+            code: "residuals_failed",
+            detail: format!(
+                "need at least 2 TLEs for NORAD {}, found {}",
+                payload.norad_id,
+                history.len()
+            ),
+        });
+    }
+
+    let flag_km = payload.flag_km;
+    let report = blocking(move || Ok(residuals::analyse(&history, flag_km)))
+        .await
+        .map_err(|e| {
+            error!(job_id, "residual analysis failed: {e:#}");
+            JobFailure {
+                code: "residuals_failed",
+                detail: format!("{e:#}"),
+            }
+        })?;
+    Ok(JobResult::Residuals(Box::new(TleResidualsResult {
+        job_id: payload.job_id,
+        norad_id: payload.norad_id,
+        frame: "ric".to_owned(),
+        report,
+        computed_at: Utc::now(),
+    })))
+}
+
+/// `"detect_maneuvers"`: detect maneuvers in the stored TLE history of one
+/// satellite and persist them to `maneuver_events`.
+async fn handle_detect_maneuvers(
+    pool: &PgPool,
+    job_id: &str,
+    payload: DetectManeuversPayload,
+) -> Result<JobResult, JobFailure> {
+    let history = db::fetch_tle_history(pool, payload.norad_id, payload.effective_limit())
+        .await
+        .map_err(|e| {
+            error!(job_id, "TLE history query failed: {e:#}");
+            JobFailure {
+                code: "maneuvers_failed",
+                detail: format!("DB error: {e:#}"),
+            }
+        })?;
+
+    let events = blocking(move || Ok(maneuver::detect(&history)))
+        .await
+        .map_err(|e| {
+            error!(job_id, "maneuver detection failed: {e:#}");
+            JobFailure {
+                code: "maneuvers_failed",
+                detail: format!("{e:#}"),
+            }
+        })?;
+    if let Err(e) = db::insert_maneuver_events(pool, &events).await {
+        error!(job_id, "DB insert failed: {e:#}");
+        return Err(JobFailure {
+            code: "maneuvers_failed",
+            detail: format!("DB error: {e:#}"),
+        });
+    }
+
+    Ok(JobResult::Maneuvers(Box::new(ManeuverResult {
+        job_id: payload.job_id,
+        norad_id: payload.norad_id,
+        events,
+        computed_at: Utc::now(),
+    })))
+}
+
+/// `"maneuver_events"`: the maneuvers stored for one satellite.
+async fn handle_maneuver_events(
+    pool: &PgPool,
+    job_id: &str,
+    payload: ManeuverEventsPayload,
+) -> Result<JobResult, JobFailure> {
+    let events = db::fetch_maneuver_events(pool, payload.norad_id)
+        .await
+        .map_err(|e| {
+            error!(job_id, "maneuver_events query failed: {e:#}");
+            JobFailure {
+                code: "maneuvers_failed",
+                detail: format!("DB error: {e:#}"),
+            }
+        })?;
+
+    Ok(JobResult::Maneuvers(Box::new(ManeuverResult {
+        job_id: payload.job_id,
+        norad_id: payload.norad_id,
+        events,
+        computed_at: Utc::now(),
+    })))
+}
+
+/// `"lifetime"`: reentry prediction for one TLE, upserted into
+/// `reentry_predictions`.
+async fn handle_lifetime(
+    pool: &PgPool,
+    job_id: &str,
+    payload: LifetimePayload,
+) -> Result<JobResult, JobFailure> {
+    payload.validate().map_err(|detail| JobFailure {
+        code: "invalid_payload",
+        detail,
+    })?;
+
+    let (tle_id, tle, atmosphere, horizon_days) = (
+        payload.tle_id,
+        payload.tle.clone(),
+        payload.atmosphere,
+        payload.horizon_days as f64,
+    );
+    let estimate = blocking(move || {
+        let parsed = tle_cache::init(tle_id, &tle.name, &tle.line1, &tle.line2)?;
+        lifetime::estimate(&parsed, atmosphere, horizon_days)
+    })
+    .await
+    .map_err(|e| {
+        error!(job_id, "lifetime integration failed: {e:#}");
+        JobFailure {
+            code: "lifetime_failed",
+            detail: format!("{e:#}"),
+        }
+    })?;
+
+    if let Err(e) =
+        db::upsert_reentry_prediction(pool, payload.norad_id, payload.tle_id, &estimate).await
+    {
+        error!(job_id, "DB insert failed: {e:#}");
+        return Err(JobFailure {
+            code: "lifetime_failed",
+            detail: format!("DB error: {e:#}"),
+        });
+    }
+
+    Ok(JobResult::Lifetime(Box::new(LifetimeResult {
+        job_id: payload.job_id,
+        norad_id: payload.norad_id,
+        tle_id: payload.tle_id,
+        estimate,
+        computed_at: Utc::now(),
+    })))
+}
+
+/// `"classify_orbit"`: label the orbit regime of one TLE.
+fn handle_classify_orbit(
+    job_id: &str,
+    payload: ClassifyOrbitPayload,
+) -> Result<JobResult, JobFailure> {
+    let tle = &payload.tle;
+    let parsed = tle_cache::init(payload.tle_id, &tle.name, &tle.line1, &tle.line2)
+        .map_err(propagation_failed(job_id, "classification"))?;
+
+    Ok(JobResult::Classification(Box::new(ClassificationResult {
+        job_id: payload.job_id,
+        tle_id: payload.tle_id,
+        classification: regime::classify(&MeanElements::from_elements(&parsed.elements)),
+        computed_at: Utc::now(),
+    })))
+}
+
+/// `"beta_angle"`: solar beta angle and full-sun periods over a window.
+fn handle_beta_angle(job_id: &str, payload: BetaAnglePayload) -> Result<JobResult, JobFailure> {
+    payload.validate().map_err(|detail| JobFailure {
+        code: "invalid_payload",
+        detail,
+    })?;
+
+    let tle = &payload.tle;
+    let samples = tle_cache::init(payload.tle_id, &tle.name, &tle.line1, &tle.line2)
+        .and_then(|parsed| {
+            beta::beta_window(&parsed, &payload.start_at, payload.duration_s, payload.step_s)
+        })
+        .map_err(propagation_failed(job_id, "beta angle"))?;
+
+    Ok(JobResult::BetaAngle(Box::new(BetaAngleResult {
+        job_id: payload.job_id,
+        tle_id: payload.tle_id,
+        start_at: payload.start_at,
+        duration_s: payload.duration_s,
+        step_s: payload.step_s,
+        full_sun_periods: beta::full_sun_periods(&samples),
+        samples,
+        computed_at: Utc::now(),
+    })))
+}
+
+/// `"doppler"`: Doppler offset and rate over every pass in a window.
+fn handle_doppler(job_id: &str, payload: DopplerPayload) -> Result<JobResult, JobFailure> {
+    payload.validate().map_err(|detail| JobFailure {
+        code: "invalid_payload",
+        detail,
+    })?;
+
+    let failed = propagation_failed(job_id, "Doppler");
+    let tle = &payload.tle;
+    let parsed = tle_cache::init(payload.tle_id, &tle.name, &tle.line1, &tle.line2)
+        .map_err(&failed)?;
+    let tracker = Tracker {
+        elements: &parsed.elements,
+        constants: &parsed.constants,
+        observer: payload.observer,
+    };
+    let end = passes::offset(&payload.start_at, payload.duration_s as f64);
+    let passes = doppler::doppler_passes(
+        &tracker,
+        payload.frequency_hz,
+        payload.min_elevation_deg,
+        &payload.start_at,
+        &end,
+        payload.step_s,
+    )
+    .map_err(&failed)?;
+
+    Ok(JobResult::Doppler(Box::new(DopplerResult {
+        job_id: payload.job_id,
+        tle_id: payload.tle_id,
+        observer: payload.observer,
+        frequency_hz: payload.frequency_hz,
+        min_elevation_deg: payload.min_elevation_deg,
+        csv: doppler::to_csv(payload.frequency_hz, &passes),
+        passes,
+        computed_at: Utc::now(),
+    })))
+}
+
+/// `"footprint"`: sub-satellite point and footprint polygon per sample.
+fn handle_footprint(job_id: &str, payload: FootprintPayload) -> Result<JobResult, JobFailure> {
+    payload.validate().map_err(|detail| JobFailure {
+        code: "invalid_payload",
+        detail,
+    })?;
+
+    let tle = &payload.tle;
+    let samples = tle_cache::init(payload.tle_id, &tle.name, &tle.line1, &tle.line2)
+        .and_then(|parsed| {
+            footprint::footprint_window(
+                &parsed,
+                &payload.start_at,
+                payload.duration_s,
+                payload.step_s,
+                &payload.footprint,
+            )
+        })
+        .map_err(propagation_failed(job_id, "footprint"))?;
+
+    Ok(JobResult::Footprint(Box::new(FootprintResult {
+        job_id: payload.job_id,
+        tle_id: payload.tle_id,
+        start_at: payload.start_at,
+        duration_s: payload.duration_s,
+        step_s: payload.step_s,
+        footprint: payload.footprint,
+        samples,
+        computed_at: Utc::now(),
+    })))
+}
+
+/// `"visible_passes"`: naked-eye visible passes over an observer.
+fn handle_visible_passes(
+    job_id: &str,
+    payload: VisiblePassesPayload,
+) -> Result<JobResult, JobFailure> {
+    payload.validate().map_err(|detail| JobFailure {
+        code: "invalid_payload",
+        detail,
+    })?;
+
+    let failed = propagation_failed(job_id, "visible pass");
+    let tle = &payload.tle;
+    let parsed = tle_cache::init(payload.tle_id, &tle.name, &tle.line1, &tle.line2)
+        .map_err(&failed)?;
+    let tracker = Tracker {
+        elements: &parsed.elements,
+        constants: &parsed.constants,
+        observer: payload.observer,
+    };
+    let end = passes::offset(&payload.start_at, payload.duration_s as f64);
+    let passes = visual::visible_passes(
+        &tracker,
+        payload.min_elevation_deg,
+        payload.twilight,
+        payload.standard_magnitude,
+        &payload.start_at,
+        &end,
+    )
+    .map_err(&failed)?;
+
+    Ok(JobResult::VisiblePasses(Box::new(VisiblePassesResult {
+        job_id: payload.job_id,
+        tle_id: payload.tle_id,
+        observer: payload.observer,
+        min_elevation_deg: payload.min_elevation_deg,
+        twilight: payload.twilight,
+        passes,
+        computed_at: Utc::now(),
+    })))
+}
+
+/// `"contact_plan"`: merged contact schedule for a ground-station network.
+fn handle_contact_plan(job_id: &str, payload: ContactPlanPayload) -> Result<JobResult, JobFailure> {
+    payload.validate().map_err(|detail| JobFailure {
+        code: "invalid_payload",
+        detail,
+    })?;
+
+    let failed = propagation_failed(job_id, "contact plan");
+    let satellites = payload
+        .satellites
+        .iter()
+        .map(|s| {
+            Ok(PlanSatellite {
+                tle_id: s.tle_id,
+                name: s.tle.name.clone(),
+                propagator: tle_cache::init(s.tle_id, &s.tle.name, &s.tle.line1, &s.tle.line2)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()
+        .map_err(&failed)?;
+    let end = passes::offset(&payload.start_at, payload.duration_s as f64);
+    let plan = contact::plan(&payload.stations, &satellites, &payload.start_at, &end)
+        .map_err(&failed)?;
+
+    Ok(JobResult::ContactPlan(Box::new(ContactPlanResult {
+        job_id: payload.job_id,
+        start_at: payload.start_at,
+        duration_s: payload.duration_s,
+        plan,
+        computed_at: Utc::now(),
+    })))
+}
+
+/// `"intersat_visibility"`: line-of-sight intervals between two satellites.
+fn handle_intersat_visibility(
+    job_id: &str,
+    payload: IntersatVisibilityPayload,
+) -> Result<JobResult, JobFailure> {
+    payload.validate().map_err(|detail| JobFailure {
+        code: "invalid_payload",
+        detail,
+    })?;
+
+    let visibility = || {
+        let parse =
+            |s: &SatelliteTle| tle_cache::init(s.tle_id, &s.tle.name, &s.tle.line1, &s.tle.line2);
+        let (primary, secondary) = (parse(&payload.primary)?, parse(&payload.secondary)?);
+        intersat::visibility_window(
+            &LinkEnd { name: &payload.primary.tle.name, propagator: &primary },
+            &LinkEnd { name: &payload.secondary.tle.name, propagator: &secondary },
+            &payload.start_at,
+            payload.duration_s,
+            payload.step_s,
+            payload.grazing_alt_km,
+        )
+    };
+    let (samples, intervals) =
+        visibility().map_err(propagation_failed(job_id, "inter-satellite"))?;
+
+    Ok(JobResult::IntersatVisibility(Box::new(IntersatVisibilityResult {
+        job_id: payload.job_id,
+        primary_tle_id: payload.primary.tle_id,
+        secondary_tle_id: payload.secondary.tle_id,
+        start_at: payload.start_at,
+        duration_s: payload.duration_s,
+        step_s: payload.step_s,
+        grazing_alt_km: payload.grazing_alt_km,
+        samples,
+        intervals,
+        computed_at: Utc::now(),
+    })))
+}
+
+/// `"relative_motion"`: target state in the reference satellite's RIC frame.
+fn handle_relative_motion(
+    job_id: &str,
+    payload: RelativeMotionPayload,
+) -> Result<JobResult, JobFailure> {
+    payload.validate().map_err(|detail| JobFailure {
+        code: "invalid_payload",
+        detail,
+    })?;
+
+    let offsets =
+        propagate::window_offsets_ms(0, payload.duration_s * 1_000, payload.step_s * 1_000);
+    let window = |s: &SatelliteTle| -> Result<Vec<Sample>> {
+        let parsed = tle_cache::init(s.tle_id, &s.tle.name, &s.tle.line1, &s.tle.line2)?;
+        let mut samples = propagate::sample_offsets_ms(&parsed, &payload.start_at, &offsets, true)?;
+        for sample in &mut samples {
+            sample.t /= 1_000;
+        }
+        Ok(samples)
+    };
+    let samples = window(&payload.reference)
+        .and_then(|reference| {
+            let target = window(&payload.target)?;
+            relative::relative_samples(&reference, &target)
+        })
+        .map_err(propagation_failed(job_id, "relative motion"))?;
+
+    Ok(JobResult::RelativeMotion(Box::new(RelativeMotionResult {
+        job_id: payload.job_id,
+        reference_tle_id: payload.reference.tle_id,
+        target_tle_id: payload.target.tle_id,
+        frame: "ric".to_owned(),
+        start_at: payload.start_at,
+        duration_s: payload.duration_s,
+        step_s: payload.step_s,
+        samples,
+        computed_at: Utc::now(),
+    })))
+}
+
+/// `"monte_carlo"`: clone statistics from perturbed mean elements.
+fn handle_monte_carlo(job_id: &str, payload: MonteCarloPayload) -> Result<JobResult, JobFailure> {
+    payload.validate().map_err(|detail| JobFailure {
+        code: "invalid_payload",
+        detail,
+    })?;
+
+    let failed = propagation_failed(job_id, "Monte Carlo");
+    let tle = &payload.tle;
+    let parsed =
+        tle_cache::init(payload.tle_id, &tle.name, &tle.line1, &tle.line2).map_err(&failed)?;
+    let (covariance_source, covariance) = match payload.covariance {
+        Some(cov) => ("supplied", cov),
+        None => {
+            let epoch = MeanElements::from_elements(&parsed.elements).epoch;
+            let age_days = (payload.start_at - epoch).num_seconds() as f64 / 86_400.0;
+            ("age_default", montecarlo::default_covariance(age_days))
+        }
+    };
+    let run = montecarlo::run(
+        &parsed,
+        &payload.start_at,
+        payload.duration_s,
+        payload.step_s,
+        payload.clones,
+        &covariance,
+        payload.seed,
+    )
+    .map_err(&failed)?;
+
+    Ok(JobResult::MonteCarlo(Box::new(MonteCarloResult {
+        job_id: payload.job_id,
+        tle_id: payload.tle_id,
+        start_at: payload.start_at,
+        duration_s: payload.duration_s,
+        step_s: payload.step_s,
+        seed: payload.seed,
+        covariance_source: covariance_source.to_owned(),
+        covariance,
+        run,
+        computed_at: Utc::now(),
+    })))
+}
+
+/// Publish a successful result payload to `result:{job_id}`.
+async fn publish_result(redis: &mut MultiplexedConnection, job_id: &str, result: &JobResult) {
+    let result_json = match serde_json::to_string(result) {
         Ok(j) => j,
         Err(e) => {
             error!(job_id, "failed to serialise result: {e}");
-            publish_error(redis, &job_id, "propagation_failed", "serialisation error").await;
-            ack(redis, msg_id).await;
+            publish_error(redis, job_id, "propagation_failed", "serialisation error").await;
             return;
         }
     };
@@ -163,10 +1288,21 @@ async fn process_message(
     } else {
         info!(job_id, "published result on '{channel}'");
     }
+}
 
-    // ── 6. Acknowledge ───────────────────────────────────────────────────────
-    ack(redis, msg_id).await;
-    info!(job_id, msg_id, worker_name, "job complete");
+/// Acknowledge claimed messages in the order they were claimed, each once
+/// its job has finished.  A job whose task panicked is left pending for
+/// redelivery and does not hold back the ones after it.
+async fn ack_in_order(
+    mut redis: MultiplexedConnection,
+    mut claimed: mpsc::UnboundedReceiver<(String, oneshot::Receiver<()>)>,
+) {
+    while let Some((msg_id, done)) = claimed.recv().await {
+        match done.await {
+            Ok(()) => ack(&mut redis, &msg_id).await,
+            Err(_) => error!(msg_id, "job task ended without finishing; leaving it pending"),
+        }
+    }
 }
 
 /// Send `XACK stream:propagate workers {msg_id}`.
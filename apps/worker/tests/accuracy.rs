@@ -10,13 +10,36 @@
 //! within 0.1° ≈ 12 km at ISS altitude), so passing this test implies AC #1
 //! is satisfied.
 //!
+//! # Gravity model and operation mode
+//!
+//! Each [`PropagationModel`] combination has its own goldens, propagated by the
+//! Python package with the same `whichconst` / `opsmode`.  `{norad_id}.json`
+//! holds the package defaults (WGS72, improved); the other combinations live
+//! in `{norad_id}.{gravity}-{opsmode}.json`.
+//!
+//! The per-satellite tests propagate with [`PropagationModel::default`]
+//! (WGS84, improved), the model the worker runs when a job names none.
+//!
+//! Every file, defaults and combinations, is the output of
+//! `scripts/regen-goldens.py`, so the worker's tolerance also absorbs the
+//! script's time arithmetic (offsets added to a full Julian date, about 0.1 m
+//! at these offsets).
+//!
+//! # Analytic baseline
+//!
+//! The two-body and J2-secular propagators are checked against the same
+//! goldens with a loose bound: they ignore SGP4's periodic terms and drag,
+//! so they should land within tens of km over the hour, never hundreds.  A
+//! failure here points at element parsing or frame handling rather than at
+//! SGP4 itself.
+//!
 //! # Regenerating goldens
 //!
 //! Run `scripts/regen-goldens.py` and review the diff before committing.
 //! CI does **not** regenerate goldens automatically.
 
-use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use worker_lib::propagate::{Gravity, OpsMode, PropagationModel, Propagator, PropagatorKind};
 
 /// A single golden sample: time offset + reference position/velocity.
 #[derive(Debug, Deserialize)]
@@ -48,46 +71,24 @@ fn sub3(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
 
 /// Run accuracy checks for one golden file.
 ///
-/// Loads the TLE, propagates with the Rust `sgp4` crate, and asserts:
+/// Loads the TLE, propagates it under `model` through the worker's
+/// [`Propagator`], and asserts:
 /// - Position error  < 1.0 km at every sample.
 /// - Velocity error  < 0.001 km/s (= 1 m/s) at every sample.
-fn check_golden(golden: &GoldenFile) {
-    use sgp4::{Constants, Elements, MinutesSinceEpoch};
-
-    let elements = Elements::from_tle(
-        Some(golden.name.clone()),
-        golden.line1.as_bytes(),
-        golden.line2.as_bytes(),
-    )
-    .unwrap_or_else(|e| panic!("NORAD {}: TLE parse failed: {e}", golden.norad_id));
-
-    let constants = Constants::from_elements(&elements)
-        .unwrap_or_else(|e| panic!("NORAD {}: SGP4 init failed: {e}", golden.norad_id));
-
-    // TLE epoch as a chrono DateTime (for datetime_to_minutes_since_epoch).
-    let epoch_datetime = elements.datetime;
+fn check_golden(golden: &GoldenFile, model: PropagationModel) {
+    let propagator = Propagator::new(&golden.name, &golden.line1, &golden.line2, model)
+        .unwrap_or_else(|e| panic!("NORAD {}: SGP4 init failed: {e:#}", golden.norad_id));
 
     for golden_sample in &golden.samples {
-        // Compute the absolute sample time.
-        let sample_dt = epoch_datetime + chrono::Duration::seconds(golden_sample.t);
-
-        // Propagation offset in minutes from TLE epoch.
-        let sample_utc: DateTime<Utc> = DateTime::from_naive_utc_and_offset(sample_dt, Utc);
-        let minutes = elements
-            .datetime_to_minutes_since_epoch(&sample_utc.naive_utc())
+        // Golden offsets are seconds from the TLE epoch.
+        let prediction = propagator
+            .predict(golden_sample.t as f64 / 60.0)
             .unwrap_or_else(|e| {
                 panic!(
-                    "NORAD {}: minutes_since_epoch failed at t={}s: {e}",
-                    golden.norad_id, golden_sample.t
-                )
-            });
-
-        let prediction = constants
-            .propagate(MinutesSinceEpoch(minutes.0))
-            .unwrap_or_else(|e| {
-                panic!(
-                    "NORAD {}: SGP4 diverged at t={}s: {e}",
-                    golden.norad_id, golden_sample.t
+                    "NORAD {} ({}): SGP4 diverged at t={}s: {e:#}",
+                    golden.norad_id,
+                    model.label(),
+                    golden_sample.t
                 )
             });
 
@@ -96,9 +97,10 @@ fn check_golden(golden: &GoldenFile) {
 
         assert!(
             pos_err < 1.0,
-            "NORAD {} t={}s: position error {:.4} km ≥ 1.0 km  \
+            "NORAD {} ({}) t={}s: position error {:.4} km ≥ 1.0 km  \
              (rust={:?}, python={:?})",
             golden.norad_id,
+            model.label(),
             golden_sample.t,
             pos_err,
             prediction.position,
@@ -107,9 +109,10 @@ fn check_golden(golden: &GoldenFile) {
 
         assert!(
             vel_err < 0.001,
-            "NORAD {} t={}s: velocity error {:.6} km/s ≥ 0.001 km/s  \
+            "NORAD {} ({}) t={}s: velocity error {:.6} km/s ≥ 0.001 km/s  \
              (rust={:?}, python={:?})",
             golden.norad_id,
+            model.label(),
             golden_sample.t,
             vel_err,
             prediction.velocity,
@@ -118,42 +121,179 @@ fn check_golden(golden: &GoldenFile) {
     }
 }
 
-/// Load a golden file from `tests/golden/sgp4/{norad_id}.json`.
-fn load_golden(norad_id: u32) -> GoldenFile {
-    let path = format!(
-        "{}/tests/golden/sgp4/{norad_id}.json",
-        env!("CARGO_MANIFEST_DIR")
-    );
+/// The five curated satellites with goldens.
+const NORAD_IDS: [u32; 5] = [25544, 20580, 44713, 36585, 33591];
+
+/// The Python package's defaults, which `{norad_id}.json` was generated with.
+const PYTHON_DEFAULT: PropagationModel = PropagationModel {
+    propagator: PropagatorKind::Sgp4,
+    gravity: Gravity::Wgs72,
+    opsmode: OpsMode::Improved,
+};
+
+/// Load the golden file for `model` from `tests/golden/sgp4/`.
+fn load_golden(norad_id: u32, model: PropagationModel) -> GoldenFile {
+    let file = if model == PYTHON_DEFAULT {
+        format!("{norad_id}.json")
+    } else {
+        format!("{norad_id}.{}.json", model.label().replace(':', "-"))
+    };
+    let path = format!("{}/tests/golden/sgp4/{file}", env!("CARGO_MANIFEST_DIR"));
     let json = std::fs::read_to_string(&path)
         .unwrap_or_else(|e| panic!("cannot read golden file '{path}': {e}"));
     serde_json::from_str(&json)
         .unwrap_or_else(|e| panic!("cannot parse golden file '{path}': {e}"))
 }
 
+/// Check every satellite's goldens for one model.
+fn check_model(gravity: Gravity, opsmode: OpsMode) {
+    let model = PropagationModel {
+        gravity,
+        opsmode,
+        ..PYTHON_DEFAULT
+    };
+    for norad_id in NORAD_IDS {
+        check_golden(&load_golden(norad_id, model), model);
+    }
+}
+
 // ── Per-satellite accuracy tests ─────────────────────────────────────────────
 
 /// ISS — AC #1 canonical satellite.  Must be within 1 km at t = 0 (epoch).
 #[test]
 fn accuracy_iss_25544() {
-    check_golden(&load_golden(25544));
+    let model = PropagationModel::default();
+    check_golden(&load_golden(25544, model), model);
 }
 
 #[test]
 fn accuracy_hubble_20580() {
-    check_golden(&load_golden(20580));
+    let model = PropagationModel::default();
+    check_golden(&load_golden(20580, model), model);
 }
 
 #[test]
 fn accuracy_starlink_44713() {
-    check_golden(&load_golden(44713));
+    let model = PropagationModel::default();
+    check_golden(&load_golden(44713, model), model);
 }
 
 #[test]
 fn accuracy_gps_36585() {
-    check_golden(&load_golden(36585));
+    let model = PropagationModel::default();
+    check_golden(&load_golden(36585, model), model);
 }
 
 #[test]
 fn accuracy_noaa19_33591() {
-    check_golden(&load_golden(33591));
+    let model = PropagationModel::default();
+    check_golden(&load_golden(33591, model), model);
+}
+
+/// Bound on the analytic propagators' distance from SGP4 over the golden
+/// samples (up to one hour), in km.
+const ANALYTIC_BOUND_KM: f64 = 50.0;
+
+/// Check that `propagator` stays within [`ANALYTIC_BOUND_KM`] of every
+/// golden position.
+fn check_analytic(propagator: PropagatorKind) {
+    let model = PropagationModel {
+        propagator,
+        ..PYTHON_DEFAULT
+    };
+    for norad_id in NORAD_IDS {
+        let golden = load_golden(norad_id, PYTHON_DEFAULT);
+        let analytic = Propagator::new(&golden.name, &golden.line1, &golden.line2, model)
+            .unwrap_or_else(|e| panic!("NORAD {norad_id}: init failed: {e:#}"));
+        for golden_sample in &golden.samples {
+            let prediction = analytic.predict(golden_sample.t as f64 / 60.0).unwrap();
+            let pos_err = norm3(sub3(prediction.position, golden_sample.r_km));
+            assert!(
+                pos_err < ANALYTIC_BOUND_KM,
+                "NORAD {norad_id} ({}) t={}s: {pos_err:.1} km from SGP4",
+                model.label(),
+                golden_sample.t,
+            );
+        }
+    }
+}
+
+// ── Analytic baseline tests ──────────────────────────────────────────────────
+
+#[test]
+fn baseline_two_body() {
+    check_analytic(PropagatorKind::TwoBody);
+}
+
+#[test]
+fn baseline_j2_secular() {
+    check_analytic(PropagatorKind::J2Secular);
+}
+
+// ── Per-model accuracy tests ─────────────────────────────────────────────────
+
+/// Bound on a non-default model's distance from the default goldens over the
+/// golden samples, in km.  WGS84's constants move the positions by tens of
+/// metres over the hour.  AFSPC mode instead changes the Greenwich sidereal
+/// time formula and the deep-space node handling at low inclination; over
+/// the hour that leaves these five orbits unchanged, so the AFSPC cases sit
+/// as close as their gravity model's improved-mode counterpart.
+const MODEL_BOUND_KM: f64 = 0.1;
+
+#[test]
+fn models_near_default() {
+    for (gravity, opsmode) in [
+        (Gravity::Wgs72, OpsMode::Afspc),
+        (Gravity::Wgs84, OpsMode::Improved),
+        (Gravity::Wgs84, OpsMode::Afspc),
+    ] {
+        let model = PropagationModel {
+            gravity,
+            opsmode,
+            ..PYTHON_DEFAULT
+        };
+        for norad_id in NORAD_IDS {
+            let golden = load_golden(norad_id, PYTHON_DEFAULT);
+            let propagator = Propagator::new(&golden.name, &golden.line1, &golden.line2, model)
+                .unwrap_or_else(|e| panic!("NORAD {norad_id}: init failed: {e:#}"));
+            let mut max_err: f64 = 0.0;
+            for golden_sample in &golden.samples {
+                let prediction = propagator.predict(golden_sample.t as f64 / 60.0).unwrap();
+                max_err = max_err.max(norm3(sub3(prediction.position, golden_sample.r_km)));
+            }
+            assert!(
+                max_err < MODEL_BOUND_KM,
+                "NORAD {norad_id} ({}): {max_err:.4} km from the default goldens",
+                model.label(),
+            );
+            // WGS84 must actually reach SGP4, not fall back to WGS72.
+            if gravity == Gravity::Wgs84 {
+                assert!(
+                    max_err > 1e-3,
+                    "NORAD {norad_id} ({}): identical to WGS72",
+                    model.label(),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn accuracy_wgs72_improved() {
+    check_model(Gravity::Wgs72, OpsMode::Improved);
+}
+
+#[test]
+fn accuracy_wgs72_afspc() {
+    check_model(Gravity::Wgs72, OpsMode::Afspc);
+}
+
+#[test]
+fn accuracy_wgs84_improved() {
+    check_model(Gravity::Wgs84, OpsMode::Improved);
+}
+
+#[test]
+fn accuracy_wgs84_afspc() {
+    check_model(Gravity::Wgs84, OpsMode::Afspc);
 }
@@ -52,12 +52,16 @@ async fn pg_pool() -> PgPool {
         .expect("failed to connect to Postgres")
 }
 
-/// Connect to Redis using `REDIS_URL`.
-async fn redis_conn() -> MultiplexedConnection {
+/// Redis client for `REDIS_URL`.
+fn redis_client() -> redis::Client {
     let url = std::env::var("REDIS_URL")
         .unwrap_or_else(|_| "redis://localhost:6379/0".to_owned());
-    let client = redis::Client::open(url.as_str()).expect("invalid Redis URL");
-    client
+    redis::Client::open(url.as_str()).expect("invalid Redis URL")
+}
+
+/// Connect to Redis using `REDIS_URL`.
+async fn redis_conn() -> MultiplexedConnection {
+    redis_client()
         .get_multiplexed_async_connection()
         .await
         .expect("failed to connect to Redis")
@@ -181,10 +185,9 @@ async fn test_happy_path() {
 
     // Start the worker in a background task.
     let pool_clone = pool.clone();
-    let redis_for_worker = redis_conn().await;
     tokio::spawn(async move {
         use worker_lib::worker;
-        let _ = worker::run(pool_clone, redis_for_worker, "integration-test-worker").await;
+        let _ = worker::run(pool_clone, &redis_client(), "integration-test-worker", 2, 0).await;
     });
 
     // Wait for the pubsub message (up to 15 s).
@@ -235,10 +238,9 @@ async fn test_idempotency() {
 
     // Start worker.
     let pool_clone = pool.clone();
-    let redis_for_worker = redis_conn().await;
     tokio::spawn(async move {
         use worker_lib::worker;
-        let _ = worker::run(pool_clone, redis_for_worker, "integration-test-worker-idem").await;
+        let _ = worker::run(pool_clone, &redis_client(), "integration-test-worker-idem", 2, 0).await;
     });
 
     // Give the worker time to process both messages.
@@ -256,3 +258,141 @@ async fn test_idempotency() {
 
     cleanup(&pool, &mut redis, &[hash]).await;
 }
+
+/// Concurrency test: results of jobs running side by side must not wait
+/// behind the worker's blocking `XREADGROUP` (`BLOCK 5000`).
+///
+/// Two jobs are claimed together with slots to spare, so the loop is back in
+/// a blocking read while they run.  Both results must arrive well inside the
+/// block time.
+#[tokio::test]
+async fn test_concurrent_results_not_blocked_by_read() {
+    /// The worker's `XREADGROUP` block time.
+    const BLOCK: Duration = Duration::from_millis(5_000);
+
+    let pool = pg_pool().await;
+    let mut redis = redis_conn().await;
+
+    let tle_id = ensure_iss_tle(&pool).await;
+    let jobs = [
+        (
+            "integration-test-concurrent-0000-0001",
+            "sha256:integration-test-concurrent-hash-000000000000000000000000001",
+        ),
+        (
+            "integration-test-concurrent-0000-0002",
+            "sha256:integration-test-concurrent-hash-000000000000000000000000002",
+        ),
+    ];
+    let hashes: Vec<&str> = jobs.iter().map(|(_, hash)| *hash).collect();
+    cleanup(&pool, &mut redis, &hashes).await;
+
+    let mut pubsub = redis_client()
+        .get_async_pubsub()
+        .await
+        .expect("failed to get pubsub connection");
+    for (job_id, _) in &jobs {
+        pubsub
+            .subscribe(format!("result:{job_id}"))
+            .await
+            .expect("subscribe");
+    }
+
+    // Start the worker with more slots than jobs and let it reach its first
+    // blocking read.
+    let pool_clone = pool.clone();
+    tokio::spawn(async move {
+        use worker_lib::worker;
+        let _ =
+            worker::run(pool_clone, &redis_client(), "integration-test-worker-concurrent", 4, 0)
+                .await;
+    });
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let started = tokio::time::Instant::now();
+    for (job_id, hash) in &jobs {
+        let payload_json = build_job_json(job_id, tle_id, hash);
+        let _: String = redis
+            .xadd("stream:propagate", "*", &[("payload", &payload_json)])
+            .await
+            .expect("XADD");
+    }
+
+    let received = timeout(Duration::from_secs(15), async {
+        let mut stream = pubsub.on_message();
+        for _ in &jobs {
+            futures_util::StreamExt::next(&mut stream).await;
+        }
+    })
+    .await;
+    let elapsed = started.elapsed();
+
+    assert!(received.is_ok(), "timed out waiting for both results");
+    assert!(
+        elapsed < BLOCK / 2,
+        "results took {elapsed:?}; they must not wait behind the {BLOCK:?} XREADGROUP block"
+    );
+
+    cleanup(&pool, &mut redis, &hashes).await;
+}
+
+/// Epoch-list cache test: a repeated `propagate_epochs` job is served from
+/// `cache:result:{hash}` instead of being recomputed.
+#[tokio::test]
+async fn test_epochs_served_from_cache() {
+    let pool = pg_pool().await;
+    let mut redis = redis_conn().await;
+    let tle_id = ensure_iss_tle(&pool).await;
+
+    let epochs_job = |job_id: &str| {
+        serde_json::json!({
+            "job_id": job_id,
+            "kind": "propagate_epochs",
+            "tle_id": tle_id,
+            "tle": { "name": ISS_NAME, "line1": ISS_LINE1, "line2": ISS_LINE2 },
+            "epochs": ["2026-04-26T12:00:00Z", "2026-04-26T12:03:07.250Z", "2026-04-26T12:00:01Z"],
+            "frame": "eci_j2000",
+            "include_velocity": false
+        })
+        .to_string()
+    };
+
+    let pool_clone = pool.clone();
+    tokio::spawn(async move {
+        use worker_lib::worker;
+        let _ = worker::run(pool_clone, &redis_client(), "integration-test-worker-epochs", 2, 0).await;
+    });
+
+    let mut results = Vec::new();
+    for job_id in ["integration-test-epochs-0000-0001", "integration-test-epochs-0000-0002"] {
+        let mut pubsub = redis_client()
+            .get_async_pubsub()
+            .await
+            .expect("failed to get pubsub connection");
+        pubsub.subscribe(format!("result:{job_id}")).await.expect("subscribe");
+        let _: String = redis
+            .xadd("stream:propagate", "*", &[("payload", &epochs_job(job_id))])
+            .await
+            .expect("XADD");
+        let msg = timeout(Duration::from_secs(15), async {
+            let mut stream = pubsub.on_message();
+            futures_util::StreamExt::next(&mut stream).await
+        })
+        .await
+        .expect("timed out waiting for epochs result")
+        .expect("pubsub closed");
+        let payload: String = msg.get_payload().expect("payload");
+        results.push(serde_json::from_str::<serde_json::Value>(&payload).unwrap());
+    }
+
+    let hash = results[0]["hash"].as_str().expect("epochs result carries a hash");
+    let cached: Option<String> = redis.get(format!("cache:result:{hash}")).await.expect("GET");
+    assert!(cached.is_some(), "epochs result must be in the hot cache");
+    assert_eq!(results[1]["job_id"], "integration-test-epochs-0000-0002");
+    assert_eq!(
+        results[0]["computed_at"], results[1]["computed_at"],
+        "the repeat must be served from the cache"
+    );
+
+    let _: redis::RedisResult<()> = redis.del(format!("cache:result:{hash}")).await;
+}
@@ -5,7 +5,10 @@ Reads TLEs from ``apps/api/data/celestrak-fallback.json`` and propagates at
 ``t ∈ {0, 60, 600, 3600}`` seconds using the Python ``sgp4`` package
 (Vallado SGP4, same algorithm as the Rust ``sgp4`` crate).
 
-Writes ``apps/worker/tests/golden/sgp4/{norad_id}.json``.
+Writes ``apps/worker/tests/golden/sgp4/{norad_id}.json`` with the package
+defaults (WGS72 gravity, improved opsmode) and
+``{norad_id}.{gravity}-{opsmode}.json`` for every other combination the
+worker's ``gravity`` / ``opsmode`` payload fields accept.
 
 Usage::
 
@@ -41,7 +44,7 @@ import sys
 from pathlib import Path
 
 try:
-    from sgp4.api import Satrec
+    from sgp4.api import WGS72, WGS84, Satrec
     from sgp4.conveniences import jday
 except ImportError:
     print(
@@ -53,6 +56,16 @@ except ImportError:
 # Propagation time offsets in seconds.
 OFFSETS_S: list[int] = [0, 60, 600, 3600]
 
+# (gravity, opsmode) label → (sgp4 whichconst, sgp4 opsmode).  The first entry
+# is the package default and keeps the plain ``{norad_id}.json`` name.
+MODELS: dict[tuple[str, str], tuple[int, str]] = {
+    ("wgs72", "improved"): (WGS72, "i"),
+    ("wgs72", "afspc"): (WGS72, "a"),
+    ("wgs84", "improved"): (WGS84, "i"),
+    ("wgs84", "afspc"): (WGS84, "a"),
+}
+DEFAULT_MODEL = ("wgs72", "improved")
+
 
 def parse_tle_epoch(line1: str) -> tuple[int, float]:
     """Return ``(year, day_frac)`` from TLE Line 1 epoch field (chars 18–31).
@@ -73,9 +86,29 @@ def generate_golden(
     line1: str,
     line2: str,
     offsets: list[int],
+    model: tuple[str, str] = DEFAULT_MODEL,
 ) -> dict:  # type: ignore[type-arg]
-    """Propagate a TLE at each offset and return the golden dict."""
-    sat = Satrec.twoline2rv(line1, line2)
+    """Propagate a TLE at each offset under ``model`` and return the golden dict."""
+    whichconst, opsmode = MODELS[model]
+    sat = Satrec.twoline2rv(line1, line2, whichconst)
+    if opsmode != "i":
+        # twoline2rv always initialises in improved mode; re-run sgp4init
+        # with the same elements in the requested mode.
+        sat.sgp4init(
+            whichconst,
+            opsmode,
+            sat.satnum,
+            sat.jdsatepoch + sat.jdsatepochF - 2433281.5,
+            sat.bstar,
+            sat.ndot,
+            sat.nddot,
+            sat.ecco,
+            sat.argpo,
+            sat.inclo,
+            sat.mo,
+            sat.no_kozai,
+            sat.nodeo,
+        )
 
     # Derive the TLE epoch Julian date.
     year, day_frac = parse_tle_epoch(line1)
@@ -149,13 +182,16 @@ def main() -> None:
         line1: str = sat["line1"]
         line2: str = sat["line2"]
 
-        golden = generate_golden(norad_id, name, line1, line2, OFFSETS_S)
-        out_path = output_dir / f"{norad_id}.json"
-        with out_path.open("w") as fh:
-            json.dump(golden, fh, indent=2)
-        print(f"  wrote {out_path.relative_to(repo_root)} ({name})")
+        for model in MODELS:
+            golden = generate_golden(norad_id, name, line1, line2, OFFSETS_S, model)
+            suffix = "" if model == DEFAULT_MODEL else ".{}-{}".format(*model)
+            out_path = output_dir / f"{norad_id}{suffix}.json"
+            with out_path.open("w") as fh:
+                json.dump(golden, fh, indent=2)
+            print(f"  wrote {out_path.relative_to(repo_root)} ({name})")
 
-    print(f"\nGenerated {len(satellites)} golden files in {output_dir.relative_to(repo_root)}")
+    count = len(satellites) * len(MODELS)
+    print(f"\nGenerated {count} golden files in {output_dir.relative_to(repo_root)}")
     print("Review the diff before committing — any change requires a reviewed PR.")
 
 